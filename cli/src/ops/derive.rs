@@ -39,7 +39,7 @@ pub fn options<'a>() -> App<'a> {
             .long("format")
             .short('F')
             .takes_value(true)
-            .help("Format to decode, valid values: json, yaml"),
+            .help("Format to decode, valid values: json, yaml, openapi"),
     );
 
     let out = out.arg(
@@ -71,30 +71,35 @@ pub fn entry(reporter: &mut dyn Reporter, matches: &ArgMatches) -> Result<()> {
         _ => RpPackage::parse("io.github.reproto"),
     };
 
-    let format: Box<dyn derive::Format> = match matches
+    let format = matches
         .try_get_one::<String>("format")
         .ok()
-        .and_then(|f| Some(f?.as_str()))
-    {
-        Some("yaml") => Box::new(derive::Yaml),
-        None | Some("json") => Box::new(derive::Json),
-        Some(value) => return Err(format!("Unsupported format: {}", value).into()),
-    };
+        .and_then(|f| Some(f?.as_str()));
 
     let source = match matches.try_get_one::<String>("file") {
         Ok(Some(file)) => Source::from_path(file),
         _ => Source::stdin(),
     };
 
-    let derive = derive::Derive::new(root_name, format, Some(package_prefix.clone()));
-
-    let decl = derive::derive(derive, &source)?;
+    let decls = match format {
+        Some("openapi") => derive::open_api(&root_name, &source)?,
+        format => {
+            let format: Box<dyn derive::Format> = match format {
+                Some("yaml") => Box::new(derive::Yaml),
+                None | Some("json") => Box::new(derive::Json),
+                Some(value) => return Err(format!("Unsupported format: {}", value).into()),
+            };
+
+            let derive = derive::Derive::new(root_name, format, Some(package_prefix.clone()));
+            vec![derive::derive(derive, &source)?]
+        }
+    };
 
     let file = ast::File {
         comment: vec!["Generated from reproto derive CLI".to_string().into()],
         attributes: vec![],
         uses: vec![],
-        decls: vec![decl],
+        decls,
     };
 
     let input = compile::Input::File(
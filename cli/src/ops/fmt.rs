@@ -0,0 +1,98 @@
+//! Format specifications.
+
+use crate::utils::load_manifest;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reproto_core::errors::{Error, Result};
+use reproto_core::{Diagnostics, Reporter, Source};
+use std::fs;
+
+pub fn options<'a>() -> App<'a> {
+    let out = SubCommand::with_name("fmt").about("Format specifications");
+
+    let out = out.arg(
+        Arg::with_name("check")
+            .long("check")
+            .help("Check that files are formatted, without modifying them"),
+    );
+
+    let out = out.arg(
+        Arg::with_name("file")
+            .multiple(true)
+            .help("Files to format, otherwise all files in the manifest paths are formatted"),
+    );
+
+    out
+}
+
+pub fn entry(reporter: &mut dyn Reporter, m: &ArgMatches) -> Result<()> {
+    let check = m.try_contains_id("check").unwrap_or_default();
+
+    let files: Vec<&String> = m
+        .try_get_many::<String>("file")
+        .ok()
+        .flatten()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let sources = if files.is_empty() {
+        let manifest = load_manifest(m)?;
+
+        let mut resolver =
+            env::path_resolver(&manifest)?.ok_or("no paths to format in manifest")?;
+
+        resolver
+            .resolve_packages()?
+            .into_iter()
+            .map(|r| r.source)
+            .collect::<Vec<_>>()
+    } else {
+        files.into_iter().map(Source::from_path).collect()
+    };
+
+    let mut failed = false;
+    let mut unformatted = Vec::new();
+
+    for source in sources {
+        let path = match source.path() {
+            Some(path) => path.to_owned(),
+            None => continue,
+        };
+
+        let content = parser::read_to_string(source.read()?)?;
+        let mut diag = Diagnostics::new(source);
+
+        let formatted = match reproto_fmt::format(&mut diag, &content) {
+            Ok(formatted) => formatted,
+            Err(reproto_fmt::Error) => {
+                reporter.diagnostics(diag);
+                failed = true;
+                continue;
+            }
+        };
+
+        if formatted == content {
+            continue;
+        }
+
+        if check {
+            unformatted.push(Error::from(format!("not formatted: {}", path.display())));
+            continue;
+        }
+
+        log::info!("formatting: {}", path.display());
+
+        fs::write(&path, formatted)
+            .map_err(|e| format!("failed to write: {}: {}", path.display(), e))?;
+    }
+
+    if !unformatted.is_empty() {
+        return Err(Error::new("some files are not formatted").with_suppressed(unformatted));
+    }
+
+    if failed {
+        return Err("failed to format some files".into());
+    }
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ mod build;
 mod check;
 mod derive;
 mod doc;
+mod fmt;
 mod init;
 mod language_server;
 mod publish;
@@ -129,6 +130,7 @@ pub fn options<'a>(out: App<'a>) -> App<'a> {
     let out = out.subcommand(build_args(doc::options()));
     let out = out.subcommand(build_args(watch::options()));
     let out = out.subcommand(base_args(check::options()));
+    let out = out.subcommand(base_args(fmt::options()));
     let out = out.subcommand(base_args(publish::options()));
     let out = out.subcommand(base_args(update::options()));
     let out = out.subcommand(base_args(self_update::options()));
@@ -175,6 +177,7 @@ pub fn entry(
         "check" => return check::entry(reporter, matches),
         "derive" => return derive::entry(reporter, matches),
         "doc" => return doc::entry(reporter, matches),
+        "fmt" => return fmt::entry(reporter, matches),
         "init" => return init::entry(fs, matches),
         "publish" => return publish::entry(reporter, matches),
         "repo" => return repo::entry(matches),
@@ -355,6 +355,17 @@ pub fn semck_check(
             RemoveVariant(c, field) => {
                 diag.err(current, field, format!("{}: variant removed", c.describe()));
             }
+            RemoveDeprecated(c, what, reg) => {
+                diag.err(
+                    current,
+                    reg,
+                    format!(
+                        "{}: deprecated {} removed, which is only permitted in a major version",
+                        c.describe(),
+                        what
+                    ),
+                );
+            }
             AddField(c, field) => {
                 diag.err(source_to, field, format!("{}: field added", c.describe()));
             }
@@ -446,6 +457,22 @@ pub fn semck_check(
                     format!("from `{}`", FmtChannel(from_channel.as_ref())),
                 );
             }
+            TypeParametersChange(c, from_parameters, from, to_parameters, to) => {
+                diag.err(
+                    source_to,
+                    to,
+                    format!(
+                        "{}: type parameters changed to `<{}>`",
+                        c.describe(),
+                        to_parameters.join(", ")
+                    ),
+                );
+                diag.info(
+                    current,
+                    from,
+                    format!("from `<{}>`", from_parameters.join(", ")),
+                );
+            }
         }
 
         return Ok(());
@@ -458,6 +485,7 @@ pub fn semck_check(
         impl<'a, F> fmt::Display for FmtChannel<'a, F>
         where
             F: Flavor,
+            F::Type: fmt::Display,
         {
             fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
                 match self.0 {
@@ -1 +1,3 @@
+#![deny(deprecated)]
+
 pub mod generated;
@@ -3,8 +3,13 @@ import PackageDescription
 
 let package = Package(
     name: "reproto-swift-it",
+    dependencies: [
+        .package(url: "https://github.com/grpc/grpc-swift.git", .exact("1.0.0")),
+    ],
     targets: [
         .target(name: "ReprotoTest", dependencies: ["Models"]),
-        .target(name: "Models", dependencies: []),
+        .target(name: "Models", dependencies: [
+            .product(name: "GRPC", package: "grpc-swift"),
+        ]),
     ]
 )
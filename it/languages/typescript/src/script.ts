@@ -0,0 +1,14 @@
+import {Entry} from '../generated/test.js';
+import * as readline from 'readline';
+
+const rl = readline.createInterface({
+  input: process.stdin,
+  output: process.stdout,
+  terminal: false
+});
+
+rl.on('line', function(line: string) {
+  const data = JSON.parse(line);
+  const decoded = Entry.decode(data);
+  process.stdout.write(`#<>${JSON.stringify(decoded.encode())}\n`);
+})
@@ -65,7 +65,7 @@ pub enum Attribute<'input> {
 
 /// A type.
 ///
-/// For example: `u32`, `::Relative::Name`, or `bytes`.
+/// For example: `u32`, `::Relative::Name`, `Page<string>`, or `bytes`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type<'input> {
     Double,
@@ -84,6 +84,8 @@ pub enum Type<'input> {
     DateTime,
     Name {
         name: Spanned<Name<'input>>,
+        /// Type arguments, if the name refers to a generic declaration.
+        arguments: Vec<Spanned<Type<'input>>>,
     },
     Array {
         inner: Box<Spanned<Type<'input>>>,
@@ -104,6 +106,8 @@ pub enum Decl<'input> {
     Interface(Item<'input, InterfaceBody<'input>>),
     Enum(Item<'input, EnumBody<'input>>),
     Service(Item<'input, ServiceBody<'input>>),
+    Const(Item<'input, ConstBody<'input>>),
+    Alias(Item<'input, AliasBody<'input>>),
 }
 
 impl<'input> Decl<'input> {
@@ -117,6 +121,8 @@ impl<'input> Decl<'input> {
             Interface(ref body) => &body.name,
             Enum(ref body) => &body.name,
             Service(ref body) => &body.name,
+            Const(ref body) => &body.name,
+            Alias(ref body) => &body.name,
         };
 
         Spanned::map(Spanned::as_ref(name), |n| n.as_ref())
@@ -132,6 +138,8 @@ impl<'input> Decl<'input> {
             Interface(ref body) => body.decls(),
             Enum(ref body) => body.decls(),
             Service(ref body) => body.decls(),
+            Const(_) => Vec::new(),
+            Alias(_) => Vec::new(),
         };
 
         decls.into_iter()
@@ -147,8 +155,48 @@ impl<'input> Decl<'input> {
             Interface(ref body) => &body.comment,
             Enum(ref body) => &body.comment,
             Service(ref body) => &body.comment,
+            Const(ref body) => &body.comment,
+            Alias(ref body) => &body.comment,
         }
     }
+
+    /// Attributes.
+    pub fn attributes(&self) -> &Vec<Spanned<Attribute<'input>>> {
+        use self::Decl::*;
+
+        match *self {
+            Type(ref body) => &body.attributes,
+            Tuple(ref body) => &body.attributes,
+            Interface(ref body) => &body.attributes,
+            Enum(ref body) => &body.attributes,
+            Service(ref body) => &body.attributes,
+            Const(ref body) => &body.attributes,
+            Alias(ref body) => &body.attributes,
+        }
+    }
+}
+
+/// The body of a type alias.
+///
+/// ```ignore
+/// type <name> = <ty>;
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct AliasBody<'input> {
+    pub name: Spanned<Cow<'input, str>>,
+    pub ty: Spanned<Type<'input>>,
+}
+
+/// The body of a constant declaration.
+///
+/// ```ignore
+/// const <name>: <ty> = <value>;
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConstBody<'input> {
+    pub name: Spanned<Cow<'input, str>>,
+    pub ty: Spanned<Type<'input>>,
+    pub value: Spanned<Value<'input>>,
 }
 
 /// The body of an enum declaration.
@@ -192,7 +240,7 @@ pub enum EnumMember<'input> {
 /// A field.
 ///
 /// ```ignore
-/// <name><modifier>: <ty> as <field_as>
+/// <name><modifier>: <ty> as <field_as> = <default>
 /// ```
 #[derive(Debug, PartialEq, Eq)]
 pub struct Field<'input> {
@@ -200,6 +248,8 @@ pub struct Field<'input> {
     pub name: Cow<'input, str>,
     pub ty: Spanned<Type<'input>>,
     pub field_as: Option<String>,
+    /// Value of the field when it is absent.
+    pub default: Option<Spanned<Value<'input>>>,
     /// If the end-of-line indicator present.
     /// A `false` value should indicate an error.
     pub endl: bool,
@@ -257,7 +307,7 @@ pub enum Name<'input> {
 /// The body of an interface declaration
 ///
 /// ```ignore
-/// interface <name> {
+/// interface <name><T, ...> {
 ///   <members>
 ///   <sub_types>
 /// }
@@ -265,6 +315,7 @@ pub enum Name<'input> {
 #[derive(Debug, PartialEq, Eq)]
 pub struct InterfaceBody<'input> {
     pub name: Spanned<Cow<'input, str>>,
+    pub type_parameters: Vec<Spanned<Cow<'input, str>>>,
     pub members: Vec<TypeMember<'input>>,
     pub sub_types: Vec<Item<'input, SubType<'input>>>,
 }
@@ -427,13 +478,14 @@ pub struct SubType<'input> {
 /// The body of a tuple
 ///
 /// ```ignore
-/// tuple <name> {
+/// tuple <name><T, ...> {
 ///     <members>
 /// }
 /// ```
 #[derive(Debug, PartialEq, Eq)]
 pub struct TupleBody<'input> {
     pub name: Spanned<Cow<'input, str>>,
+    pub type_parameters: Vec<Spanned<Cow<'input, str>>>,
     pub members: Vec<TypeMember<'input>>,
 }
 
@@ -468,13 +520,14 @@ impl<'input> TupleBody<'input> {
 /// The body of a type
 ///
 /// ```ignore
-/// type <name> {
+/// type <name><T, ...> {
 ///     <members>
 /// }
 /// ```
 #[derive(Debug, PartialEq, Eq)]
 pub struct TypeBody<'input> {
     pub name: Spanned<Cow<'input, str>>,
+    pub type_parameters: Vec<Spanned<Cow<'input, str>>>,
     pub members: Vec<TypeMember<'input>>,
 }
 
@@ -107,6 +107,18 @@ decl_codegen! {
         pub(crate) inner: &'a mut Vec<csharp::Tokens>,
     }
 
+    /// Generate code for the newtype wrapper of an alias.
+    newtype<'a> {
+        /// The identifier of the newtype.
+        pub(crate) ident: &'a str,
+        /// Field holding the wrapped value.
+        pub(crate) field: &'a Spanned<Field>,
+        /// Annotations generated.
+        pub(crate) annotations: &'a mut Vec<csharp::Tokens>,
+        /// Inner code generated.
+        pub(crate) inner: &'a mut Vec<csharp::Tokens>,
+    }
+
     /// Generate code for an interface.
     interface<'a> {
         /// The identifier of the interface.
@@ -123,6 +135,14 @@ decl_codegen! {
         pub(crate) inner: &'a mut Vec<csharp::Tokens>,
     }
 
+    /// Generate code for a service.
+    service<'a> {
+        /// The service being generated.
+        pub(crate) body: &'a RpServiceBody,
+        /// Declarations generated for the service.
+        pub(crate) decls: &'a mut Vec<csharp::Tokens>,
+    }
+
     /// Generate annotations for a tagged constructor.
     interface_tag_constructor_arg<'a> {
         /// The tag.
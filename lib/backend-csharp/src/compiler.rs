@@ -7,7 +7,7 @@ use genco::prelude::*;
 use genco::tokens::from_fn;
 use naming::Naming as _;
 use reproto_core::errors::Result;
-use reproto_core::{Handle, RelativePathBuf, Spanned};
+use reproto_core::{Handle, RelativePathBuf, RpValidate, Spanned};
 use std::rc::Rc;
 use trans::Translated;
 
@@ -17,6 +17,9 @@ pub struct Compiler {
     string_builder: csharp::Import,
     string: csharp::Import,
     object: csharp::Import,
+    regex: csharp::Import,
+    encoding: csharp::Import,
+    argument_exception: csharp::Import,
     to_upper_snake: naming::ToUpperSnake,
 }
 
@@ -30,12 +33,22 @@ impl Compiler {
             string_builder: csharp::import("System.Text", "StringBuilder"),
             string: csharp::import("System", "String"),
             object: csharp::import("System", "Object"),
+            regex: csharp::import("System.Text.RegularExpressions", "Regex"),
+            encoding: csharp::import("System.Text", "Encoding"),
+            argument_exception: csharp::import("System", "ArgumentException"),
             to_upper_snake: naming::to_upper_snake(),
         }
     }
 
     pub(crate) fn compile(&self, handle: &dyn Handle) -> Result<()> {
         for decl in self.env.toplevel_decl_iter() {
+            // NB: resolved aliases have nothing to generate.
+            if let RpDecl::Alias(_) = decl {
+                if !self.opt.newtype {
+                    continue;
+                }
+            }
+
             self.compile_decl(handle, decl)?;
         }
 
@@ -62,8 +75,18 @@ impl Compiler {
         let path = path.join(format!("{}.cs", decl.ident()));
         log::debug!("+class: {}", path);
 
-        let file = quote! {
-            $(ref t => self.process_decl(t, decl)?)
+        let file = match decl {
+            // NB: top-level constants need a class to live in.
+            RpDecl::Const(body) => quote! {
+                $(csharp::block_comment(&body.comment))
+                $(obsolete(body.deprecated.as_ref()))
+                public static class $(&body.ident) {
+                    public const $(&body.ty) Value = $(self.literal(&body.ty, &body.value));
+                }
+            },
+            decl => quote! {
+                $(ref t => self.process_decl(t, decl)?)
+            },
         };
 
         let config = csharp::Config::default().with_namespace(namespace);
@@ -83,6 +106,7 @@ impl Compiler {
 
         quote_in! { *t =>
             $(csharp::block_comment(&body.comment))
+            $(obsolete(body.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public enum $(&body.ident)$(self.enum_type(body.enum_type)) {
                 $(for v in &body.variants join (,$['\r']) {
@@ -103,6 +127,7 @@ impl Compiler {
 
         quote_in! { *t =>
             $(csharp::block_comment(&body.comment))
+            $(obsolete(body.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public class $(&body.ident) {
                 $(for f in &body.fields join ($['\n']) => $(self.field(f)))
@@ -132,6 +157,7 @@ impl Compiler {
 
         quote_in! { *t =>
             $(csharp::block_comment(&body.comment))
+            $(obsolete(body.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public class $(&body.ident) {
                 $(for f in &body.fields join ($['\n']) => $(self.field(f)))
@@ -169,6 +195,7 @@ impl Compiler {
 
         quote_in! { *t =>
             $(csharp::block_comment(&body.comment))
+            $(obsolete(body.deprecated.as_ref()))
             $(for a in annotations => $a)
             public abstract class $(&body.ident) {
                 $(self.interface_sub_type_strategy(&body.ident, &body.sub_type_strategy, &tag_annotations))
@@ -188,7 +215,70 @@ impl Compiler {
         Ok(())
     }
 
-    fn process_service(&self, _: &mut csharp::Tokens, _: &RpServiceBody) -> Result<()> {
+    fn process_service(&self, t: &mut csharp::Tokens, body: &RpServiceBody) -> Result<()> {
+        let mut decls = Vec::new();
+        self.opt.gen.service(body, &mut decls);
+
+        quote_in! { *t =>
+            $(for d in decls join ($['\n']) => $d)
+        }
+
+        Ok(())
+    }
+
+    fn process_const(&self, t: &mut csharp::Tokens, body: &RpConstBody) -> Result<()> {
+        quote_in! { *t =>
+            $(csharp::block_comment(&body.comment))
+            $(obsolete(body.deprecated.as_ref()))
+            public const $(&body.ty) $(&body.ident) = $(self.literal(&body.ty, &body.value));
+        }
+
+        Ok(())
+    }
+
+    /// Aliases are resolved unless they are generated as newtype wrappers.
+    fn process_alias(&self, t: &mut csharp::Tokens, body: &Spanned<RpAliasBody>) -> Result<()> {
+        if !self.opt.newtype {
+            return Ok(());
+        }
+
+        let field = Spanned::new(
+            Field::newtype(body.ty.clone(), body.validate.clone()),
+            body.span(),
+        );
+
+        let fields = std::slice::from_ref(&field);
+
+        let mut annotations = Vec::new();
+        let mut inner = Vec::new();
+        self.opt
+            .gen
+            .newtype(&body.ident, &field, &mut annotations, &mut inner);
+
+        quote_in! { *t =>
+            $(csharp::block_comment(&body.comment))
+            $(obsolete(body.deprecated.as_ref()))
+            $(for a in annotations join ($['\r']) => $a)
+            public class $(&body.ident) {
+                public $(&field.ty) $(&field.var) {
+                    get;
+                }
+
+                public $(&body.ident)($(&field.ty) $(&field.var)) {
+                    $(self.validate(&field))
+                    $(self.assign(&field))
+                }
+
+                $(self.equals(&body.ident, fields))
+
+                $(self.get_hash_code(fields))
+
+                $(self.to_string(&body.ident, fields))
+
+                $(for i in inner join ($['\n']) => $i)
+            }
+        }
+
         Ok(())
     }
 
@@ -209,6 +299,12 @@ impl Compiler {
             RpDecl::Service(ty) => {
                 self.process_service(t, ty)?;
             }
+            RpDecl::Const(body) => {
+                self.process_const(t, body)?;
+            }
+            RpDecl::Alias(body) => {
+                self.process_alias(t, body)?;
+            }
         }
 
         Ok(())
@@ -236,7 +332,8 @@ impl Compiler {
                 $(for f in fields join (,$['\r']) => $(self.constructor_arg(f)))
             ) : base(TypeField) {
                 $(for f in fields join ($['\r']) {
-                    this.$(&f.var) = $(&f.var);
+                    $(self.validate(f))
+                    $(self.assign(f))
                 })
             }
         }
@@ -275,6 +372,7 @@ impl Compiler {
 
         quote_fn! {
             $(csharp::block_comment(&sub_type.comment))
+            $(obsolete(sub_type.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public class $(&sub_type.ident) : $(&body.ident) {
                 $(for f in &fields join ($['\n']) => $(self.field(f)))
@@ -328,8 +426,43 @@ impl Compiler {
         let mut ann = Vec::new();
         self.opt.gen.class_constructor_arg(f, &mut ann);
 
+        // NB: fields with a default value accept null, which is replaced by the default.
         quote_fn! {
-            $(for a in ann join ($['\r']) => $a) $(f.field_type()) $(&f.var)
+            $(for a in ann join ($['\r']) => $a) $(if f.default.is_some() && !f.ty.is_nullable() {
+                $(&f.ty)?
+            } else {
+                $(f.field_type())
+            }) $(&f.var)
+        }
+    }
+
+    /// Assign a constructor argument to its field, falling back to the default value if it has one.
+    fn assign<'f>(&'f self, f: &'f Field) -> impl FormatInto<Csharp> + 'f {
+        quote_fn! {
+            $(match &f.default {
+                Some(default) => this.$(&f.var) = $(&f.var) ?? $(self.literal(&f.ty, default));,
+                None => this.$(&f.var) = $(&f.var);,
+            })
+        }
+    }
+
+    /// Build a literal of the given type.
+    fn literal(&self, ty: &Type, literal: &RpLiteral) -> Tokens<Csharp> {
+        match literal {
+            RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+            RpLiteral::Number(number) => {
+                let suffix = match *ty {
+                    Type::Primitive(Primitive::Long) => "L",
+                    Type::Primitive(Primitive::UInt) => "U",
+                    Type::Primitive(Primitive::ULong) => "UL",
+                    Type::Primitive(Primitive::Float) => "F",
+                    Type::Primitive(Primitive::Double) => "D",
+                    _ => "",
+                };
+
+                quote!($(format!("{}{}", number, suffix)))
+            }
+            RpLiteral::Boolean(boolean) => quote!($(boolean.to_string())),
         }
     }
 
@@ -347,12 +480,85 @@ impl Compiler {
                 $(for f in fields join (,$['\r']) => $(self.constructor_arg(f)))
             ) {
                 $(for f in fields join ($['\r']) {
-                    this.$(&f.var) = $(&f.var);
+                    $(self.validate(f))
+                    $(self.assign(f))
                 })
             }
         }
     }
 
+    /// Build checks for the validation rules of the given field.
+    fn validate<'f>(&'f self, f: &'f Field) -> impl FormatInto<Csharp> + 'f {
+        from_fn(move |t| {
+            let validate = match &f.validate {
+                Some(validate) => validate,
+                None => return,
+            };
+
+            let mut checks = Vec::new();
+
+            match validate {
+                RpValidate::Number(validate) => {
+                    if let Some(min) = &validate.min {
+                        checks.push(quote! {
+                            if ($(&f.var) < $(min.to_string())) {
+                                throw new $(&self.argument_exception)($(quoted(format!("{}: must be greater than or equal to {}", f.ident, min))));
+                            }
+                        });
+                    }
+
+                    if let Some(max) = &validate.max {
+                        checks.push(quote! {
+                            if ($(&f.var) > $(max.to_string())) {
+                                throw new $(&self.argument_exception)($(quoted(format!("{}: must be less than or equal to {}", f.ident, max))));
+                            }
+                        });
+                    }
+                }
+                RpValidate::String(validate) => {
+                    if let Some(pattern) = &validate.pattern {
+                        let pattern = pattern.to_string();
+
+                        checks.push(quote! {
+                            if (!$(&self.regex).IsMatch($(&f.var), $(quoted(format!("^(?:{})\\z", pattern))))) {
+                                throw new $(&self.argument_exception)($(quoted(format!("{}: must match pattern `{}`", f.ident, pattern))));
+                            }
+                        });
+                    }
+
+                    if let Some(min_length) = validate.min_length {
+                        checks.push(quote! {
+                            if ($(&self.encoding).UTF32.GetByteCount($(&f.var)) / 4 < $(min_length.to_string())) {
+                                throw new $(&self.argument_exception)($(quoted(format!("{}: must be at least {} characters long", f.ident, min_length))));
+                            }
+                        });
+                    }
+
+                    if let Some(max_length) = validate.max_length {
+                        checks.push(quote! {
+                            if ($(&self.encoding).UTF32.GetByteCount($(&f.var)) / 4 > $(max_length.to_string())) {
+                                throw new $(&self.argument_exception)($(quoted(format!("{}: must be at most {} characters long", f.ident, max_length))));
+                            }
+                        });
+                    }
+                }
+            }
+
+            // NB: missing values are not checked by the constructor, so only validate present ones.
+            if f.is_optional() || f.ty.is_nullable() {
+                quote_in! { *t =>
+                    if ($(&f.var) != null) {
+                        $(for c in checks join ($['\r']) => $c)
+                    }
+                }
+            } else {
+                quote_in! { *t =>
+                    $(for c in checks join ($['\r']) => $c)
+                }
+            }
+        })
+    }
+
     /// Process the variant value.
     fn variant_value<'f>(
         &'f self,
@@ -384,6 +590,7 @@ impl Compiler {
 
         quote_fn! {
             $(csharp::block_comment(variant.comment))
+            $(obsolete(variant.deprecated))
             $(for a in annotations join ($['\r']) => $a)
             $name$(self.variant_value(enum_type, variant))
         }
@@ -395,6 +602,7 @@ impl Compiler {
 
         quote_fn! {
             $(csharp::block_comment(&f.comment))
+            $(obsolete(f.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public $(f.field_type()) $(&f.var) {
                 get;
@@ -488,3 +696,22 @@ impl Compiler {
         })
     }
 }
+
+/// An `[Obsolete]` attribute, if the item is deprecated.
+pub(crate) fn obsolete(deprecated: Option<&RpDeprecated>) -> impl FormatInto<Csharp> + '_ {
+    from_fn(move |t| {
+        let deprecated = match deprecated {
+            Some(deprecated) => deprecated,
+            None => return,
+        };
+
+        let obsolete = csharp::import("System", "Obsolete");
+
+        quote_in! { *t =>
+            $(match deprecated.message() {
+                Some(message) => [$obsolete($(quoted(message)))],
+                None => [$obsolete],
+            })
+        }
+    })
+}
@@ -8,7 +8,8 @@ use naming::Naming;
 use reproto_core::errors::Result;
 use reproto_core::{
     CoreFlavor, Diagnostics, Flavor, FlavorField, FlavorTranslator, PackageTranslator,
-    RpNumberKind, RpNumberType, RpNumberValidate, RpStringType, Spanned, Translate, Translator,
+    RpNumberKind, RpNumberType, RpNumberValidate, RpStringType, RpValidate, Spanned, Translate,
+    Translator,
 };
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -158,6 +159,19 @@ impl Field {
     }
 }
 
+impl Field {
+    /// Build the field holding the value of a newtype wrapper.
+    pub(crate) fn newtype(ty: Type, validate: Option<RpValidate>) -> Self {
+        let mut inner = RpField::new("value", ty);
+        inner.validate = validate;
+
+        Field {
+            var: Rc::new(String::from("Value")),
+            inner,
+        }
+    }
+}
+
 impl Deref for Field {
     type Target = RpField;
 
@@ -172,6 +186,20 @@ impl FlavorField for Field {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct CsharpEndpoint {
+    pub(crate) endpoint: RpEndpoint,
+    pub(crate) http1: Option<RpEndpointHttp1>,
+}
+
+impl Deref for CsharpEndpoint {
+    type Target = RpEndpoint;
+
+    fn deref(&self) -> &Self::Target {
+        &self.endpoint
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) enum CsharpFlavor {}
 
@@ -179,7 +207,7 @@ impl Flavor for CsharpFlavor {
     type Type = Type;
     type Name = Spanned<RpName>;
     type Field = Field;
-    type Endpoint = RpEndpoint;
+    type Endpoint = CsharpEndpoint;
     type Package = RpPackage;
     type EnumType = EnumType;
 }
@@ -215,7 +243,7 @@ impl FlavorTranslator for CsharpFlavorTranslator {
     type Source = CoreFlavor;
     type Target = CsharpFlavor;
 
-    reproto_core::translator_defaults!(Self, local_name, endpoint);
+    reproto_core::translator_defaults!(Self, local_name);
 
     fn translate_field<T>(
         &self,
@@ -300,6 +328,21 @@ impl FlavorTranslator for CsharpFlavorTranslator {
         }
     }
 
+    fn translate_endpoint<T>(
+        &self,
+        translator: &T,
+        diag: &mut Diagnostics,
+        endpoint: RpEndpoint<CoreFlavor>,
+    ) -> Result<CsharpEndpoint>
+    where
+        T: Translator<Source = CoreFlavor, Target = CsharpFlavor>,
+    {
+        let endpoint = endpoint.translate(diag, translator)?;
+        let http1 = RpEndpointHttp1::from_endpoint(&endpoint);
+
+        Ok(CsharpEndpoint { endpoint, http1 })
+    }
+
     fn translate_package(&self, source: RpVersionedPackage) -> Result<RpPackage> {
         self.packages.translate_package(source)
     }
@@ -119,12 +119,16 @@ impl Lang for CsharpLang {
 #[derive(Debug)]
 pub enum CsharpModule {
     JsonNet,
+    HttpClient,
+    Newtype,
 }
 
 impl TryFromToml for CsharpModule {
     fn try_from_string(path: &Path, id: &str, value: String) -> Result<Self> {
         let result = match id {
             "Json.NET" => CsharpModule::JsonNet,
+            "HttpClient" => CsharpModule::HttpClient,
+            "newtype" => CsharpModule::Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -134,6 +138,8 @@ impl TryFromToml for CsharpModule {
     fn try_from_value(path: &Path, id: &str, value: toml::Value) -> Result<Self> {
         let result = match id {
             "Json.NET" => CsharpModule::JsonNet,
+            "HttpClient" => CsharpModule::HttpClient,
+            "newtype" => CsharpModule::Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -141,28 +147,46 @@ impl TryFromToml for CsharpModule {
     }
 }
 
-fn setup_options<'a>(modules: Vec<CsharpModule>) -> Options {
+fn setup_options(modules: Vec<CsharpModule>) -> Result<Options> {
     let mut options = Options::new();
 
+    let json_net = modules.iter().any(|m| matches!(m, CsharpModule::JsonNet));
+
     for module in modules {
         match module {
             CsharpModule::JsonNet => {
                 module::json_net::initialize(&mut options);
             }
+            CsharpModule::HttpClient => {
+                if !json_net {
+                    return Err(
+                        "HttpClient: the `Json.NET` module is required to serialize messages"
+                            .into(),
+                    );
+                }
+
+                module::http_client::initialize(&mut options);
+            }
+            CsharpModule::Newtype => {
+                module::newtype::initialize(&mut options);
+            }
         };
     }
 
-    options
+    Ok(options)
 }
 
 fn compile(handle: &dyn Handle, session: Session<CoreFlavor>, manifest: Manifest) -> Result<()> {
+    let modules = checked_modules(manifest.modules)?;
+    let options = setup_options(modules)?;
+
     let packages = session.packages()?;
 
-    let session = session.translate(flavored::CsharpFlavorTranslator::new(packages))?;
+    let session = session
+        .with_newtypes(options.newtype)
+        .translate(flavored::CsharpFlavorTranslator::new(packages))?;
     let session = Rc::new(session);
 
-    let modules = checked_modules(manifest.modules)?;
-    let options = setup_options(modules);
     let compiler = Compiler::new(session.clone(), options);
 
     compiler.compile(handle)
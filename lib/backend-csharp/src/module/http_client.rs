@@ -0,0 +1,167 @@
+//! Module that generates HttpClient-based clients for services.
+//!
+//! Messages are serialized with Json.NET.
+
+use crate::codegen;
+use crate::compiler::obsolete;
+use crate::flavored::*;
+use crate::Options;
+use genco::prelude::*;
+use naming::Naming;
+use std::rc::Rc;
+
+pub fn initialize(opt: &mut Options) {
+    let codegen = Rc::new(Codegen::new());
+    opt.gen.service.push(codegen);
+}
+
+struct Codegen {
+    to_lower_camel: naming::ToLowerCamel,
+    to_upper_camel: naming::ToUpperCamel,
+
+    string: csharp::Import,
+    uri: csharp::Import,
+    encoding: csharp::Import,
+    task: csharp::Import,
+
+    http_client: csharp::Import,
+    http_method: csharp::Import,
+    http_request_message: csharp::Import,
+    string_content: csharp::Import,
+
+    json_convert: csharp::Import,
+}
+
+impl Codegen {
+    pub fn new() -> Self {
+        Self {
+            to_lower_camel: naming::to_lower_camel(),
+            to_upper_camel: naming::to_upper_camel(),
+
+            string: csharp::import("System", "String"),
+            uri: csharp::import("System", "Uri"),
+            encoding: csharp::import("System.Text", "Encoding"),
+            task: csharp::import("System.Threading.Tasks", "Task"),
+
+            http_client: csharp::import("System.Net.Http", "HttpClient"),
+            http_method: csharp::import("System.Net.Http", "HttpMethod"),
+            http_request_message: csharp::import("System.Net.Http", "HttpRequestMessage"),
+            string_content: csharp::import("System.Net.Http", "StringContent"),
+
+            json_convert: csharp::import("Newtonsoft.Json", "JsonConvert"),
+        }
+    }
+
+    /// Name of the local variable for an argument.
+    fn var(&self, arg: &RpEndpointArgument) -> String {
+        self.to_lower_camel.convert(arg.safe_ident())
+    }
+
+    /// Build the path of an endpoint, formatted through `String.Format`.
+    fn path(&self, path: &RpPathSpec) -> csharp::Tokens {
+        let mut format = String::new();
+        let mut args = Vec::new();
+
+        for step in &path.steps {
+            format.push('/');
+
+            for part in &step.parts {
+                match part {
+                    RpPathPart::Segment(s) => {
+                        format.push_str(&s.replace('{', "{{").replace('}', "}}"));
+                    }
+                    RpPathPart::Variable(arg) => {
+                        format.push_str(&format!("{{{}}}", args.len()));
+                        args.push(
+                            quote!($(&self.uri).EscapeDataString($(self.var(arg)).ToString())),
+                        );
+                    }
+                }
+            }
+        }
+
+        if args.is_empty() {
+            return quote!($(quoted(format)));
+        }
+
+        quote!($(&self.string).Format($(quoted(format)), $(for a in args join (, ) => $a)))
+    }
+
+    /// Build the client method for a single endpoint.
+    fn endpoint<'a>(
+        &'a self,
+        e: &'a CsharpEndpoint,
+        http: &'a RpEndpointHttp1,
+    ) -> impl FormatInto<Csharp> + 'a {
+        let ident = format!("{}Async", self.to_upper_camel.convert(e.ident()));
+
+        let response = match (&http.response, &e.http.accept) {
+            (Some(_), RpAccept::Text) => quote!($(&self.task)<$(&self.string)>),
+            (Some(ty), RpAccept::Json) => quote!($(&self.task)<$ty>),
+            (None, _) => quote!($(&self.task)),
+        };
+
+        quote_fn! {
+            $(csharp::block_comment(&e.comment))
+            $(obsolete(e.deprecated.as_ref()))
+            public async $response $ident($(for a in &e.arguments join (, ) => $(a.channel.ty()) $(self.var(a)))) {
+                using (var httpRequest_ = new $(&self.http_request_message)(new $(&self.http_method)($(quoted(http.method.as_str()))), this.url + $(self.path(&http.path)))) {
+                    $(if let Some(req) = &e.request {
+                        httpRequest_.Content = new $(&self.string_content)($(&self.json_convert).SerializeObject($(self.var(req))), $(&self.encoding).UTF8, "application/json");
+                        $['\n']
+                    })
+                    using (var httpResponse_ = await this.client.SendAsync(httpRequest_).ConfigureAwait(false)) {
+                        httpResponse_.EnsureSuccessStatusCode();
+                        $(match (&http.response, &e.http.accept) {
+                            (Some(ty), RpAccept::Json) => {
+                                $['\n']
+                                var body_ = await httpResponse_.Content.ReadAsStringAsync().ConfigureAwait(false);
+                                return $(&self.json_convert).DeserializeObject<$ty>(body_);
+                            }
+                            (Some(_), RpAccept::Text) => {
+                                $['\n']
+                                return await httpResponse_.Content.ReadAsStringAsync().ConfigureAwait(false);
+                            }
+                            (None, _) => {}
+                        })
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl codegen::service::Codegen for Codegen {
+    fn generate(&self, args: codegen::service::Args<'_>) {
+        let codegen::service::Args { body, decls } = args;
+
+        let ident = format!("{}_HttpClient", body.ident);
+
+        let endpoints = body
+            .endpoints
+            .iter()
+            .flat_map(|e| e.http1.as_ref().map(|http| (e, http)))
+            .collect::<Vec<_>>();
+
+        decls.push(quote! {
+            $(csharp::block_comment(&body.comment))
+            $(obsolete(body.deprecated.as_ref()))
+            public class $(&ident) {
+                private readonly $(&self.http_client) client;
+                private readonly $(&self.string) url;
+
+                $(if let Some(url) = &body.http.url {
+                    public $(&ident)($(&self.http_client) client) : this(client, $(quoted(url.as_str()))) {
+                    }
+                    $['\n']
+                })
+                public $(&ident)($(&self.http_client) client, $(&self.string) url) {
+                    this.client = client;
+                    this.url = url.TrimEnd('/');
+                }
+
+                $(for (e, http) in endpoints join ($['\n']) => $(self.endpoint(e, http)))
+            }
+        });
+    }
+}
@@ -14,6 +14,7 @@ pub fn initialize(opt: &mut Options) {
     opt.gen.enum_type.push(codegen.clone());
     opt.gen.enum_variant.push(codegen.clone());
     opt.gen.tuple.push(codegen.clone());
+    opt.gen.newtype.push(codegen.clone());
     opt.gen.interface.push(codegen.clone());
     opt.gen.interface_tag_constructor_arg.push(codegen.clone());
 }
@@ -203,6 +204,37 @@ impl codegen::tuple::Codegen for Codegen {
     }
 }
 
+impl codegen::newtype::Codegen for Codegen {
+    fn generate(&self, args: codegen::newtype::Args<'_>) {
+        let object = &self.object;
+        let json_writer = &self.json_writer;
+        let json_reader = &self.json_reader;
+        let json_serializer = &self.json_serializer;
+        let json_converter = &self.json_converter;
+
+        args.annotations.push(quote! {
+            [$json_converter(typeof($(args.ident).Json_Net_Converter))]
+        });
+
+        args.inner.push(quote! {
+            public class Json_Net_Converter : $json_converter {
+                public override bool CanConvert(System.Type objectType) {
+                    return objectType == typeof($(args.ident));
+                }
+
+                public override void WriteJson($json_writer writer, $object obj, $json_serializer serializer) {
+                    $(args.ident) o = ($(args.ident))obj;
+                    serializer.Serialize(writer, o.$(&args.field.var));
+                }
+
+                public override $object ReadJson($json_reader reader, System.Type objectType, $object existingValue, $json_serializer serializer) {
+                    return new $(args.ident)(serializer.Deserialize<$(&args.field.ty)>(reader));
+                }
+            }
+        });
+    }
+}
+
 impl codegen::interface::Codegen for Codegen {
     fn generate(&self, args: codegen::interface::Args<'_>) {
         match args.sub_type_strategy {
@@ -1 +1,3 @@
+pub(super) mod http_client;
 pub(super) mod json_net;
+pub(super) mod newtype;
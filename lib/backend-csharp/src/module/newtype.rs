@@ -0,0 +1,5 @@
+use crate::Options;
+
+pub fn initialize(opt: &mut Options) {
+    opt.newtype = true;
+}
@@ -17,6 +17,8 @@ pub struct Options {
     pub build_to_string: bool,
     /// Do not generate methods in service interface.
     pub suppress_service_methods: bool,
+    /// Generate aliases as newtype wrappers?
+    pub newtype: bool,
     /// Access to registered generators.
     pub(crate) gen: Generators,
 }
@@ -31,6 +33,7 @@ impl Options {
             build_equals: true,
             build_to_string: true,
             suppress_service_methods: false,
+            newtype: false,
             gen: Generators::default(),
         }
     }
@@ -0,0 +1,24 @@
+use crate::flavored::RpServiceBody;
+use genco::lang::Dart;
+use genco::Tokens;
+use reproto_core::errors::Result;
+use std::rc::Rc;
+
+pub(crate) struct ServiceAdded<'a> {
+    pub(crate) body: &'a RpServiceBody,
+    pub(crate) type_body: &'a mut Tokens<Dart>,
+}
+
+/// Generate service-based code.
+pub(crate) trait ServiceCodegen {
+    fn generate(&self, e: ServiceAdded<'_>) -> Result<()>;
+}
+
+impl<T> ServiceCodegen for Rc<T>
+where
+    T: ServiceCodegen,
+{
+    fn generate(&self, e: ServiceAdded<'_>) -> Result<()> {
+        self.as_ref().generate(e)
+    }
+}
@@ -1,8 +1,9 @@
 //! Backend for Dart
 
+use crate::codegen::ServiceAdded;
 use crate::flavored::*;
 use crate::utils::Comments;
-use crate::{EXT, TYPE_SEP};
+use crate::{Options, EXT, TYPE_SEP};
 use backend::PackageProcessor;
 use genco::prelude::*;
 use genco::tokens::{static_literal, ItemStr};
@@ -12,18 +13,20 @@ use trans::Translated;
 
 pub struct Compiler<'a> {
     pub env: &'a Translated<DartFlavor>,
+    options: Options,
     handle: &'a dyn Handle,
     map_of_strings: Type,
     list_of_dynamic: Type,
 }
 
 impl<'a> Compiler<'a> {
-    pub fn new(env: &'a Translated<DartFlavor>, handle: &'a dyn Handle) -> Self {
+    pub fn new(env: &'a Translated<DartFlavor>, options: Options, handle: &'a dyn Handle) -> Self {
         let map_of_strings = Type::map(Type::String, Type::Dynamic);
         let list_of_dynamic = Type::list(Type::Dynamic);
 
         Self {
             env,
+            options,
             handle,
             map_of_strings,
             list_of_dynamic,
@@ -97,20 +100,31 @@ impl<'a> Compiler<'a> {
                         quote_in!{ *t =>
                             var $id_dyn = _data[$(quoted(field.name()))];
 
-                            $(if field.is_optional() {
-                                $(&field.ty) $id = null;
+                            $(match &field.default {
+                                _ if field.is_optional() => {
+                                    $(&field.ty) $id = null;
 
-                                if ($id_dyn != null) {
-                                    $e
-                                    $id = $d;
+                                    if ($id_dyn != null) {
+                                        $e
+                                        $id = $d;
+                                    }
                                 }
-                            } else {
-                                if ($id_dyn == null) {
-                                    throw "expected value but was null";
+                                Some(default) => {
+                                    $(&field.ty) $id = $(self.literal(default));
+
+                                    if ($id_dyn != null) {
+                                        $e
+                                        $id = $d;
+                                    }
                                 }
+                                None => {
+                                    if ($id_dyn == null) {
+                                        throw "expected value but was null";
+                                    }
 
-                                $e
-                                final $(&field.ty) $id = $d;
+                                    $e
+                                    final $(&field.ty) $id = $d;
+                                }
                             })
                         }
                     })
@@ -121,6 +135,15 @@ impl<'a> Compiler<'a> {
         }
     }
 
+    /// Build a literal value.
+    fn literal(&self, literal: &RpLiteral) -> dart::Tokens {
+        match literal {
+            RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+            RpLiteral::Number(number) => quote!($(number.to_string())),
+            RpLiteral::Boolean(boolean) => quote!($(boolean.to_string())),
+        }
+    }
+
     /// Build a tuple decode function.
     fn decode_tuple_fn(&self, t: &mut dart::Tokens, name: &ItemStr, fields: &[Spanned<RpField>]) {
         let mut vars = Vec::new();
@@ -450,7 +473,59 @@ impl<'el> PackageProcessor<'el, DartFlavor> for Compiler<'el> {
         Ok(())
     }
 
-    fn process_service(&self, _: &mut Self::Out, _: &RpServiceBody) -> Result<()> {
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody) -> Result<()> {
+        let name = &self.convert_type_name(&body.name);
+
+        quote_in! { *out =>
+            $(Comments(&body.comment))
+            const $(&body.ty) $name = $(self.literal(&body.value));
+        }
+
+        Ok(())
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody) -> Result<()> {
+        if !self.options.newtype {
+            return Ok(());
+        }
+
+        let name = &self.convert_type_name(&body.name);
+        let data = static_literal("data");
+        let (d, e) = body.ty.decode(quote!($data));
+
+        quote_in! { *out =>
+            $(Comments(&body.comment))
+            class $name {
+                final $(&body.ty) value;
+
+                $name(this.value);
+
+                static $name decode(dynamic $data) {
+                    if ($data == null) {
+                        throw "expected value but was null";
+                    }
+
+                    $e
+                    return $name($d);
+                }
+
+                dynamic encode() {
+                    return $(body.ty.encode(quote!(this.value)));
+                }
+            }
+        };
+
+        Ok(())
+    }
+
+    fn process_service(&self, out: &mut Self::Out, body: &RpServiceBody) -> Result<()> {
+        for g in &self.options.service_generators {
+            g.generate(ServiceAdded {
+                body,
+                type_body: out,
+            })?;
+        }
+
         Ok(())
     }
 }
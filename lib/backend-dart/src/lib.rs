@@ -1,8 +1,12 @@
+mod codegen;
 mod compiler;
 mod flavored;
+mod module;
 mod utils;
 
+use crate::codegen::ServiceCodegen;
 use crate::compiler::Compiler;
+use backend::Initializer;
 use manifest::{Lang, Manifest, NoModule, TryFromToml};
 use reproto_core::errors::Result;
 use reproto_core::{CoreFlavor, Handle};
@@ -94,22 +98,77 @@ impl Lang for DartLang {
 }
 
 #[derive(Debug)]
-pub enum DartModule {}
+pub(crate) enum DartModule {
+    Http(module::HttpConfig),
+    Newtype,
+}
 
 impl TryFromToml for DartModule {
     fn try_from_string(path: &Path, id: &str, value: String) -> Result<Self> {
-        return NoModule::illegal(path, id, value);
+        use self::DartModule::*;
+
+        let result = match id {
+            "http" => Http(module::HttpConfig::default()),
+            "newtype" => Newtype,
+            _ => return NoModule::illegal(path, id, value),
+        };
+
+        Ok(result)
     }
 
     fn try_from_value(path: &Path, id: &str, value: toml::Value) -> Result<Self> {
-        return NoModule::illegal(path, id, value);
+        use self::DartModule::*;
+
+        let result = match id {
+            "http" => Http(value.try_into()?),
+            "newtype" => Newtype,
+            _ => return NoModule::illegal(path, id, value),
+        };
+
+        Ok(result)
+    }
+}
+
+pub(crate) struct Options {
+    pub(crate) service_generators: Vec<Box<dyn ServiceCodegen>>,
+    /// Generate aliases as wrapper classes.
+    pub(crate) newtype: bool,
+}
+
+impl Options {
+    pub(crate) fn new() -> Options {
+        Options {
+            service_generators: Vec::new(),
+            newtype: false,
+        }
     }
 }
 
+fn setup_options(modules: Vec<DartModule>) -> Result<Options> {
+    use self::DartModule::*;
+
+    let mut options = Options::new();
+
+    for module in modules {
+        let initializer: Box<dyn Initializer<Options = Options>> = match module {
+            Http(config) => Box::new(module::Http::new(config)),
+            Newtype => Box::new(module::Newtype::new()),
+        };
+
+        initializer.initialize(&mut options)?;
+    }
+
+    Ok(options)
+}
+
 fn compile(handle: &dyn Handle, session: Session<CoreFlavor>, manifest: Manifest) -> Result<()> {
-    let _: Vec<DartModule> = manifest::checked_modules(manifest.modules)?;
+    let modules: Vec<DartModule> = manifest::checked_modules(manifest.modules)?;
+    let options = setup_options(modules)?;
+
     let packages = session.packages()?;
-    let session = session.translate(flavored::DartFlavorTranslator::new(packages.clone()))?;
+    let session = session
+        .with_newtypes(options.newtype)
+        .translate(flavored::DartFlavorTranslator::new(packages.clone()))?;
 
-    Compiler::new(&session, handle).compile()
+    Compiler::new(&session, options, handle).compile()
 }
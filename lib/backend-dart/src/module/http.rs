@@ -0,0 +1,223 @@
+//! Module that generates `package:http` clients for services.
+
+use crate::codegen::{ServiceAdded, ServiceCodegen};
+use crate::flavored::*;
+use crate::utils::Comments;
+use crate::{Options, TYPE_SEP};
+use backend::Initializer;
+use genco::prelude::*;
+use naming::Naming;
+use reproto_core::errors::Result;
+use serde::Deserialize;
+
+/// Identifiers used by the generated client, which arguments must not shadow.
+const RESERVED: &[&str] = &["data", "http", "httpRequest", "httpResponse"];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {}
+
+pub(crate) struct Module {
+    #[allow(dead_code)]
+    config: Config,
+}
+
+impl Module {
+    pub(crate) fn new(config: Config) -> Module {
+        Module { config }
+    }
+}
+
+struct HttpServiceCodegen {
+    to_lower_camel: naming::ToLowerCamel,
+    client: dart::Import,
+    request: dart::Import,
+    response: dart::Import,
+    json_encode: dart::Import,
+    json_decode: dart::Import,
+}
+
+impl HttpServiceCodegen {
+    fn new() -> Self {
+        Self {
+            to_lower_camel: naming::to_lower_camel(),
+            client: dart::import("package:http/http.dart", "Client").with_alias("http"),
+            request: dart::import("package:http/http.dart", "Request").with_alias("http"),
+            response: dart::import("package:http/http.dart", "Response").with_alias("http"),
+            json_encode: dart::import("dart:convert", "jsonEncode"),
+            json_decode: dart::import("dart:convert", "jsonDecode"),
+        }
+    }
+
+    /// Name of the parameter for an argument.
+    ///
+    /// Names which are used by the generated client are suffixed with an underscore.
+    fn var(&self, arg: &RpEndpointArgument) -> String {
+        let var = arg.safe_ident();
+
+        if RESERVED.contains(&var) {
+            return format!("{}_", var);
+        }
+
+        var.to_string()
+    }
+
+    /// Build the url of an HTTP endpoint, relative to the base url of the client.
+    fn url(&self, path: &RpPathSpec) -> dart::Tokens {
+        let mut parts = vec![quote!(_url)];
+        let mut segment = String::new();
+
+        for step in &path.steps {
+            segment.push('/');
+
+            for part in &step.parts {
+                match part {
+                    RpPathPart::Segment(s) => segment.push_str(s),
+                    RpPathPart::Variable(arg) => {
+                        parts.push(quote!($(quoted(std::mem::take(&mut segment)))));
+                        parts.push(self.path_encode(arg));
+                    }
+                }
+            }
+        }
+
+        if !segment.is_empty() {
+            parts.push(quote!($(quoted(segment))));
+        }
+
+        quote!($(for p in parts join ( + ) => $p))
+    }
+
+    /// Encode a path variable, using the encoded value of the argument.
+    fn path_encode(&self, arg: &RpEndpointArgument) -> dart::Tokens {
+        let ty = arg.channel.ty();
+        let var = quote!($(self.var(arg)));
+
+        match ty {
+            Type::String => quote!(Uri.encodeComponent($var)),
+            ty => quote!(Uri.encodeComponent($(ty.encode(var)).toString())),
+        }
+    }
+
+    /// Build the client method calling a single HTTP endpoint.
+    fn endpoint(
+        &self,
+        t: &mut dart::Tokens,
+        exception: &str,
+        e: &DartEndpoint,
+        http: &RpEndpointHttp1,
+    ) {
+        let ident = self.to_lower_camel.convert(e.safe_ident());
+
+        let response = match (&http.response, &e.http.accept) {
+            (Some(ty), RpAccept::Json) => quote!(Future<$ty>),
+            (Some(_), RpAccept::Text) => quote!(Future<String>),
+            (None, _) => quote!(Future<void>),
+        };
+
+        quote_in! { *t =>
+            $(Comments(&e.comment))
+            $response $ident($(for a in &e.arguments join (, ) => $(a.channel.ty()) $(self.var(a)))) async {
+                var httpRequest = $(&self.request)($(quoted(http.method.as_str())), Uri.parse($(self.url(&http.path))));
+                $(if let Some(req) = &e.request {
+                    httpRequest.headers["Content-Type"] = "application/json";
+                    httpRequest.body = $(&self.json_encode)($(req.channel.ty().encode(quote!($(self.var(req))))));
+                })
+
+                var httpResponse = await $(&self.response).fromStream(await _client.send(httpRequest));
+
+                if (httpResponse.statusCode < 200 || httpResponse.statusCode >= 300) {
+                    throw $exception(httpResponse.statusCode, httpResponse.reasonPhrase, httpResponse.body);
+                }
+                $(if let Some(ty) = &http.response =>
+                    $(match e.http.accept {
+                        RpAccept::Json => {
+                            $['\n']
+                            $(ref t {
+                                let (d, checks) = ty.decode(quote!(data));
+
+                                quote_in! { *t =>
+                                    var data = $(&self.json_decode)(httpResponse.body);
+                                    $(if !checks.is_empty() {
+                                        $['\n']
+                                        $checks
+                                    })
+                                    $['\n']
+                                    return $d;
+                                }
+                            })
+                        }
+                        RpAccept::Text => {
+                            $['\n']
+                            return httpResponse.body;
+                        }
+                    })
+                )
+            }
+        }
+    }
+}
+
+impl ServiceCodegen for HttpServiceCodegen {
+    fn generate(&self, ServiceAdded { body, type_body }: ServiceAdded) -> Result<()> {
+        let name = &body.name.join(TYPE_SEP);
+        let exception = &format!("{}_HttpException", name);
+
+        let endpoints = body
+            .endpoints
+            .iter()
+            .flat_map(|e| e.http1.as_ref().map(|http| (e, http)))
+            .collect::<Vec<_>>();
+
+        quote_in! { *type_body =>
+            $(Comments([format!("Exception thrown when a request to `{}` does not have a successful status.", name)]))
+            class $exception implements Exception {
+                final int statusCode;
+                final String reasonPhrase;
+                final String body;
+
+                $exception(this.statusCode, this.reasonPhrase, this.body);
+
+                toString() => $[str]($[const](exception): $(statusCode) $(reasonPhrase));
+            }
+
+            $(Comments(&body.comment))
+            class $(name)_Http {
+                final $(&self.client) _client;
+                final String _url;
+
+                $(match &body.http.url {
+                    Some(url) => {
+                        $(name)_Http({$(&self.client) client, String url = $(quoted(url.as_str()))})
+                    }
+                    None => {
+                        $(name)_Http({$(&self.client) client, String url})
+                    }
+                })
+                    : _client = client ?? $(&self.client)(),
+                      _url = url {
+                    if (_url == null) {
+                        throw ArgumentError.notNull("url");
+                    }
+                }
+
+                $(for (e, http) in endpoints join ($['\n']) {
+                    $(ref t => self.endpoint(t, exception, e, http))
+                })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Initializer for Module {
+    type Options = Options;
+
+    fn initialize(&self, options: &mut Options) -> Result<()> {
+        options
+            .service_generators
+            .push(Box::new(HttpServiceCodegen::new()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+mod http;
+mod newtype;
+
+pub(crate) use self::http::{Config as HttpConfig, Module as Http};
+pub(crate) use self::newtype::Module as Newtype;
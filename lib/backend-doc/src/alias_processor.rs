@@ -0,0 +1,35 @@
+//! Processor for type aliases.
+
+use crate::doc_builder::DocBuilder;
+use crate::macros::FormatAttribute;
+use crate::processor::Processor;
+use reproto_core::errors::Result;
+use reproto_core::flavored::*;
+
+define_processor!(AliasProcessor, RpAliasBody, self,
+    process => {
+        self.write_doc(|| {
+            let id = self.body.name.join("_");
+
+            html!(self, section {id => &id, class => "section-content section-alias"} => {
+                self.section_title("alias", &self.body.name)?;
+
+                html!(self, div {class => "section-body"} => {
+                    html!(self, h2 {class => "alias-title"} => {
+                        html!(self, span {} ~ "=");
+                        self.write_type(&self.body.ty)?;
+                    });
+                });
+
+                self.deprecated(self.body.deprecated.as_ref())?;
+                self.doc(&self.body.comment)?;
+            });
+
+            Ok(())
+        })
+    };
+
+    current_package => &self.body.name.package;
+);
+
+impl<'p> AliasProcessor<'p> {}
@@ -14,6 +14,8 @@ use syntect::highlighting::Theme;
 use syntect::parsing::SyntaxSet;
 use trans::Translated;
 
+use crate::alias_processor::AliasProcessor;
+use crate::const_processor::ConstProcessor;
 use crate::doc_builder::DocBuilder;
 use crate::enum_processor::EnumProcessor;
 use crate::index_processor::{Data as IndexData, IndexProcessor};
@@ -128,6 +130,22 @@ impl<'a> DocCompiler<'a> {
                 body,
             }
             .process(),
+            RpDecl::Const(ref body) => ConstProcessor {
+                out,
+                session: &self.session,
+                syntax: (self.syntax_theme, self.syntax_set),
+                root: &root,
+                body,
+            }
+            .process(),
+            RpDecl::Alias(ref body) => AliasProcessor {
+                out,
+                session: &self.session,
+                syntax: (self.syntax_theme, self.syntax_set),
+                root: &root,
+                body,
+            }
+            .process(),
         }
     }
 
@@ -14,6 +14,7 @@ define_processor!(EnumProcessor, RpEnumBody, self,
 
             html!(self, section {id => &id, class => "section-content section-enum"} => {
                 self.section_title("enum", &self.body.name)?;
+                self.deprecated(self.body.deprecated.as_ref())?;
                 self.doc(&self.body.comment)?;
 
                 self.variants_overview(&self.body.variants)?;
@@ -68,6 +69,7 @@ impl<'p> EnumProcessor<'p> {
                 }
             });
 
+            self.deprecated(variant.deprecated)?;
             self.doc(variant.comment)?;
         }
 
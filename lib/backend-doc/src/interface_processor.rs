@@ -13,6 +13,7 @@ define_processor!(InterfaceProcessor, RpInterfaceBody, self,
 
             html!(self, section {id => &id, class => "section-content section-interface"} => {
                 self.section_title("interface", &self.body.name)?;
+                self.deprecated(self.body.deprecated.as_ref())?;
                 self.doc(&self.body.comment)?;
 
                 self.fields_overview(&self.body.fields)?;
@@ -57,6 +58,7 @@ impl<'p> InterfaceProcessor<'p> {
             self.full_name_without_package(&sub_type.name)?;
         });
 
+        self.deprecated(sub_type.deprecated.as_ref())?;
         self.doc(&self.body.comment)?;
 
         let fields = self.body.fields.iter().chain(sub_type.fields.iter());
@@ -2,6 +2,8 @@
 
 #[macro_use]
 mod macros;
+mod alias_processor;
+mod const_processor;
 mod doc_builder;
 mod doc_compiler;
 mod enum_processor;
@@ -28,6 +30,7 @@ use manifest::Manifest;
 use reproto_core::errors::Result;
 use reproto_core::CoreFlavor;
 use std::collections::HashMap;
+use std::path::Path;
 use syntect::dumps::from_binary;
 use syntect::highlighting::{Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
@@ -115,6 +118,25 @@ where
         .or_else(|| manifest.doc.syntax_theme.as_deref())
         .unwrap_or(DEFAULT_SYNTAX_THEME);
 
+    let theme = matches
+        .try_get_one::<String>("theme")
+        .ok()
+        .and_then(|theme| Some(theme?.as_str()))
+        .unwrap_or(DEFAULT_THEME);
+
+    with_themes(syntax_theme, theme, themes, f)
+}
+
+/// Load and execute the provided clojure with the named themes.
+fn with_themes<F>(
+    syntax_theme: &str,
+    theme: &str,
+    themes: &HashMap<&'static str, &'static [u8]>,
+    f: F,
+) -> Result<()>
+where
+    F: FnOnce(&Theme, &SyntaxSet, &[u8]) -> Result<()>,
+{
     let default_theme: Theme = Default::default();
     let theme_set = load_theme_set();
     let syntax_set = load_syntax_set();
@@ -130,12 +152,6 @@ where
         &default_theme
     };
 
-    let theme = matches
-        .try_get_one::<String>("theme")
-        .ok()
-        .and_then(|theme| Some(theme?.as_str()))
-        .unwrap_or(DEFAULT_THEME);
-
     let theme_css = if let Some(theme_css) = themes.get(theme) {
         theme_css
     } else {
@@ -253,3 +269,27 @@ pub fn compile(
 
     Ok(())
 }
+
+/// Compile documentation for the given session into a directory, using the default themes.
+pub fn compile_to_path(session: Session<CoreFlavor>, out: &Path) -> Result<()> {
+    let session = session.translate_default()?;
+    let themes = build_themes();
+
+    with_themes(
+        DEFAULT_SYNTAX_THEME,
+        DEFAULT_THEME,
+        &themes,
+        |syntax_theme, syntax_set, theme_css| {
+            let compiler = DocCompiler {
+                session,
+                out_path: out.to_owned(),
+                skip_static: false,
+                theme_css,
+                syntax_theme,
+                syntax_set,
+            };
+
+            compiler.compile()
+        },
+    )
+}
@@ -44,6 +44,8 @@ define_processor!(PackageProcessor, Data<'session>, self,
             let mut enums = Vec::new();
             let mut tuples = Vec::new();
             let mut services = Vec::new();
+            let mut consts = Vec::new();
+            let mut aliases = Vec::new();
 
             for decl in self.body.file.for_each_decl() {
                 match *decl {
@@ -52,6 +54,8 @@ define_processor!(PackageProcessor, Data<'session>, self,
                     RpDecl::Enum(ref en) => enums.push(en),
                     RpDecl::Tuple(ref tuple) => tuples.push(tuple),
                     RpDecl::Service(ref service) => services.push(service),
+                    RpDecl::Const(ref body) => consts.push(body),
+                    RpDecl::Alias(ref body) => aliases.push(body),
                 }
             }
 
@@ -69,6 +73,8 @@ define_processor!(PackageProcessor, Data<'session>, self,
                 types_section!(self, enums, "Enums");
                 types_section!(self, tuples, "Tuples");
                 types_section!(self, services, "Services");
+                types_section!(self, consts, "Constants");
+                types_section!(self, aliases, "Aliases");
             });
 
             Ok(())
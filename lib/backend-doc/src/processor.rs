@@ -100,6 +100,29 @@ pub trait Processor<'session> {
         Ok(())
     }
 
+    /// Render a deprecation notice, if the item is deprecated.
+    fn deprecated(&self, deprecated: Option<&RpDeprecated>) -> Result<()> {
+        let deprecated = match deprecated {
+            Some(deprecated) => deprecated,
+            None => return Ok(()),
+        };
+
+        html!(self, div {class => "deprecated"} => {
+            html!(self, span {class => "deprecated-title"} ~ "Deprecated");
+
+            if let Some(since) = &deprecated.since {
+                html!(self, span {class => "deprecated-since"} ~
+                    Escape(format!("since {}", since).as_str()));
+            }
+
+            if let Some(note) = &deprecated.note {
+                self.markdown(note)?;
+            }
+        });
+
+        Ok(())
+    }
+
     fn primitive(&self, name: &str) -> Result<()> {
         html!(self, span {class => format!("type-{} type-primitive", name)} ~ name);
         Ok(())
@@ -122,6 +145,25 @@ pub trait Processor<'session> {
                     self.full_name_without_package(name)?;
                 });
             }
+            RpType::Generic { name, arguments } => {
+                html!(self, span {class => "type-generic"} => {
+                    self.full_name_without_package(name)?;
+                    html!(self, span {class => "type-generic-left"} ~ "&lt;");
+
+                    for (i, argument) in arguments.iter().enumerate() {
+                        if i > 0 {
+                            html!(self, span {class => "type-generic-sep"} ~ ",");
+                        }
+
+                        self.write_type(argument)?;
+                    }
+
+                    html!(self, span {class => "type-generic-right"} ~ "&gt;");
+                });
+            }
+            RpType::Parameter { name } => {
+                html!(self, span {class => "type-parameter"} ~ Escape(name.as_str()));
+            }
             RpType::Array { inner } => {
                 html!(self, span {class => "type-array"} => {
                     html!(self, span {class => "type-array-left"} ~ "[");
@@ -219,6 +261,7 @@ pub trait Processor<'session> {
             }
         });
 
+        self.deprecated(field.deprecated.as_ref())?;
         self.doc(&field.comment)?;
 
         Ok(())
@@ -265,6 +308,7 @@ pub trait Processor<'session> {
             self.full_name_without_package(&decl.name())?;
         });
 
+        self.deprecated(decl.deprecated())?;
         self.doc(decl.comment().iter().take(1))?;
         Ok(())
     }
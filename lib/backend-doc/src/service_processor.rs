@@ -16,6 +16,7 @@ define_processor!(ServiceProcessor, RpServiceBody, self,
             html!(self, section {id => &id, class => "section-content section-service"} => {
                 self.section_title("service", &self.body.name)?;
 
+                self.deprecated(self.body.deprecated.as_ref())?;
                 self.doc(&self.body.comment)?;
 
                 for endpoint in &self.body.endpoints {
@@ -82,6 +83,7 @@ impl<'p> ServiceProcessor<'p> {
             }
         });
 
+        self.deprecated(endpoint.deprecated.as_ref())?;
         self.doc(&endpoint.comment)?;
         Ok(())
     }
@@ -13,6 +13,7 @@ define_processor!(TupleProcessor, RpTupleBody, self,
 
             html!(self, section {id => &id, class => "section-content section-tuple"} => {
                 self.section_title("tuple", &self.body.name)?;
+                self.deprecated(self.body.deprecated.as_ref())?;
                 self.doc(&self.body.comment)?;
 
                 html!(self, div {class => "section-body"} => {
@@ -13,6 +13,7 @@ define_processor!(TypeProcessor, RpTypeBody, self,
 
             html!(self, section {id => &id, class => "section-content section-type"} => {
                 self.section_title("type", &self.body.name)?;
+                self.deprecated(self.body.deprecated.as_ref())?;
                 self.doc(&self.body.comment)?;
 
                 html!(self, div {class => "section-body"} => {
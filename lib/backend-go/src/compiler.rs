@@ -1,7 +1,10 @@
 //! Backend for Go
 
 use crate::flavored::*;
-use crate::{EnumAdded, FieldAdded, FileSpec, InterfaceAdded, Options, Tags, TupleAdded, EXT};
+use crate::{
+    EnumAdded, FieldAdded, FileSpec, InterfaceAdded, Options, ServiceAdded, StructAdded, Tags,
+    TupleAdded, EXT,
+};
 use backend::PackageProcessor;
 use genco::prelude::*;
 use genco::tokens::{FormatInto, ItemStr};
@@ -78,6 +81,14 @@ impl<'a> Compiler<'a> {
                     })
                 })
             }
+
+            $(for g in &self.options.struct_gens join ($['\n']) {
+                $(ref container => g.generate(StructAdded {
+                    container,
+                    name,
+                    fields,
+                })?)
+            })
         };
 
         Ok(())
@@ -237,4 +248,44 @@ impl<'el> PackageProcessor<'el, GoFlavor> for Compiler<'el> {
 
         Ok(())
     }
+
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody) -> Result<()> {
+        quote_in! { out.0 =>
+            $(Comments(&body.comment))
+            const $(&body.name) $(&body.ty) = $(match &body.value {
+                RpLiteral::String(string) => $(quoted(string.as_str())),
+                RpLiteral::Number(number) => $(number.to_string()),
+                RpLiteral::Boolean(boolean) => $(boolean.to_string()),
+            })
+        }
+
+        Ok(())
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody) -> Result<()> {
+        quote_in! { out.0 =>
+            $(Comments(&body.comment))
+            $(if self.options.newtype {
+                type $(&body.name) $(&body.ty)
+            } else {
+                type $(&body.name) = $(&body.ty)
+            })
+        }
+
+        Ok(())
+    }
+
+    fn process_service(&self, out: &mut Self::Out, body: &RpServiceBody) -> Result<()> {
+        quote_in! { out.0 =>
+            $(for g in &self.options.service_gens join ($['\n']) {
+                $(ref container => g.generate(ServiceAdded {
+                    container,
+                    name: &body.name,
+                    body,
+                })?)
+            })
+        }
+
+        Ok(())
+    }
 }
@@ -17,6 +17,20 @@ use std::ops::Deref;
 use std::rc::Rc;
 use trans::Packages;
 
+#[derive(Debug, Clone)]
+pub(crate) struct GoEndpoint {
+    pub(crate) endpoint: RpEndpoint,
+    pub(crate) http1: Option<RpEndpointHttp1>,
+}
+
+impl Deref for GoEndpoint {
+    type Target = RpEndpoint;
+
+    fn deref(&self) -> &Self::Target {
+        &self.endpoint
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Primitive {
     U32,
@@ -108,7 +122,7 @@ impl Flavor for GoFlavor {
     type Type = Type;
     type Name = GoName;
     type Field = RpField;
-    type Endpoint = RpEndpoint;
+    type Endpoint = GoEndpoint;
     type Package = RpPackage;
     type EnumType = Type;
 }
@@ -146,7 +160,7 @@ impl FlavorTranslator for GoFlavorTranslator {
     type Source = CoreFlavor;
     type Target = GoFlavor;
 
-    reproto_core::translator_defaults!(Self, field, endpoint);
+    reproto_core::translator_defaults!(Self, field);
 
     fn translate_number(&self, number: RpNumberType) -> Result<Type> {
         Ok(match number.kind {
@@ -227,6 +241,21 @@ impl FlavorTranslator for GoFlavorTranslator {
         return Ok(Type::local(ident));
     }
 
+    fn translate_endpoint<T>(
+        &self,
+        translator: &T,
+        diag: &mut Diagnostics,
+        endpoint: RpEndpoint<CoreFlavor>,
+    ) -> Result<GoEndpoint>
+    where
+        T: Translator<Source = CoreFlavor, Target = GoFlavor>,
+    {
+        let endpoint = endpoint.translate(diag, translator)?;
+        let http1 = RpEndpointHttp1::from_endpoint(&endpoint);
+
+        Ok(GoEndpoint { endpoint, http1 })
+    }
+
     fn translate_local_name<T>(
         &self,
         translator: &T,
@@ -10,7 +10,7 @@ use genco::tokens::{FormatInto, ItemStr, Tokens};
 use manifest::{Lang, Manifest, NoModule, TryFromToml};
 use naming::Naming;
 use reproto_core::errors::Result;
-use reproto_core::{CoreFlavor, Handle};
+use reproto_core::{CoreFlavor, Handle, Spanned};
 use std::any::Any;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -46,6 +46,8 @@ impl Lang for GoLang {
 #[derive(Debug)]
 pub enum GoModule {
     EncodingJson,
+    NetHttp,
+    Newtype,
 }
 
 impl TryFromToml for GoModule {
@@ -54,6 +56,8 @@ impl TryFromToml for GoModule {
 
         let result = match id {
             "encoding/json" => EncodingJson,
+            "net/http" => NetHttp,
+            "newtype" => Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -65,6 +69,8 @@ impl TryFromToml for GoModule {
 
         let result = match id {
             "encoding/json" => EncodingJson,
+            "net/http" => NetHttp,
+            "newtype" => Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -73,19 +79,26 @@ impl TryFromToml for GoModule {
 }
 
 pub(crate) struct Options {
+    /// Generate aliases as distinct named types.
+    pub(crate) newtype: bool,
     pub(crate) field_gens: Vec<Box<dyn FieldCodegen>>,
+    pub(crate) struct_gens: Vec<Box<dyn StructCodegen>>,
     pub(crate) enum_gens: Vec<Box<dyn EnumCodegen>>,
     pub(crate) tuple_gens: Vec<Box<dyn TupleCodegen>>,
     pub(crate) interface_gens: Vec<Box<dyn InterfaceCodegen>>,
+    pub(crate) service_gens: Vec<Box<dyn ServiceCodegen>>,
 }
 
 impl Options {
     pub(crate) fn new() -> Options {
         Options {
+            newtype: false,
             field_gens: Vec::new(),
+            struct_gens: Vec::new(),
             enum_gens: Vec::new(),
             tuple_gens: Vec::new(),
             interface_gens: Vec::new(),
+            service_gens: Vec::new(),
         }
     }
 }
@@ -95,11 +108,24 @@ fn options(modules: Vec<GoModule>) -> Result<Options> {
 
     let mut options = Options::new();
 
+    let encoding_json = modules.iter().any(|m| matches!(m, EncodingJson));
+
     for m in modules {
         log::debug!("+module: {:?}", m);
 
         let initializer: Box<dyn Initializer<Options = Options>> = match m {
             EncodingJson => Box::new(module::EncodingJson::new()),
+            NetHttp => {
+                if !encoding_json {
+                    return Err(
+                        "net/http: the `encoding/json` module is required to serialize messages"
+                            .into(),
+                    );
+                }
+
+                Box::new(module::NetHttp::new())
+            }
+            Newtype => Box::new(module::Newtype::new()),
         };
 
         initializer.initialize(&mut options)?;
@@ -142,6 +168,15 @@ pub(crate) struct FieldAdded<'a> {
 
 codegen!(FieldCodegen, FieldAdded);
 
+/// Event emitted when a struct has been added.
+pub(crate) struct StructAdded<'a> {
+    pub container: &'a mut Tokens<Go>,
+    pub name: &'a GoName,
+    pub fields: &'a [Spanned<RpField>],
+}
+
+codegen!(StructCodegen, StructAdded);
+
 /// Event emitted when an enum has been added
 pub(crate) struct EnumAdded<'a> {
     pub container: &'a mut Tokens<Go>,
@@ -169,6 +204,15 @@ pub(crate) struct InterfaceAdded<'a> {
 
 codegen!(InterfaceCodegen, InterfaceAdded);
 
+/// Event emitted when a service has been added.
+pub(crate) struct ServiceAdded<'a> {
+    pub container: &'a mut Tokens<Go>,
+    pub name: &'a GoName,
+    pub body: &'a RpServiceBody,
+}
+
+codegen!(ServiceCodegen, ServiceAdded);
+
 /// Structure for Tags - a type of Go metadata
 pub(crate) struct Tags {
     values: BTreeMap<ItemStr, Vec<ItemStr>>,
@@ -233,9 +277,12 @@ impl FormatInto<Go> for Tags {
 fn compile(handle: &dyn Handle, session: Session<CoreFlavor>, manifest: Manifest) -> Result<()> {
     let packages = session.packages()?;
 
-    let session = session.translate(flavored::GoFlavorTranslator::new(packages))?;
-
     let modules = manifest::checked_modules(manifest.modules)?;
     let options = options(modules)?;
+
+    let session = session
+        .with_newtypes(options.newtype)
+        .translate(flavored::GoFlavorTranslator::new(packages))?;
+
     Compiler::new(&session, options, handle).compile()
 }
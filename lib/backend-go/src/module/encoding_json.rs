@@ -3,7 +3,7 @@
 use crate::flavored::*;
 use crate::{
     EnumAdded, EnumCodegen, FieldAdded, FieldCodegen, InterfaceAdded, InterfaceCodegen, Options,
-    TupleAdded, TupleCodegen,
+    StructAdded, StructCodegen, TupleAdded, TupleCodegen,
 };
 use backend::Initializer;
 use genco::prelude::*;
@@ -24,6 +24,7 @@ impl Initializer for Module {
     fn initialize(&self, options: &mut Self::Options) -> Result<()> {
         let codegen = Rc::new(Codegen::new());
         options.field_gens.push(Box::new(codegen.clone()));
+        options.struct_gens.push(Box::new(codegen.clone()));
         options.enum_gens.push(Box::new(codegen.clone()));
         options.tuple_gens.push(Box::new(codegen.clone()));
         options.interface_gens.push(Box::new(codegen.clone()));
@@ -63,6 +64,52 @@ impl FieldCodegen for Codegen {
     }
 }
 
+impl StructCodegen for Codegen {
+    fn generate(&self, e: StructAdded) -> Result<()> {
+        let StructAdded {
+            container,
+            name,
+            fields,
+        } = e;
+
+        let defaults = fields
+            .iter()
+            .filter_map(|f| f.default.as_ref().map(|d| (f, d)))
+            .collect::<Vec<_>>();
+
+        if defaults.is_empty() {
+            return Ok(());
+        }
+
+        // NB: the fields are populated with their defaults before unmarshaling, so that absent
+        // fields retain them. The local type avoids recursing into this method.
+        quote_in! { *container =>
+            func (this *$name) UnmarshalJSON(b []byte) error {
+                type plain $name
+
+                data := plain{
+                    $(for (f, d) in defaults join ($['\r']) {
+                        $(f.safe_ident()): $(match d {
+                            RpLiteral::String(string) => $(quoted(string.as_str())),
+                            RpLiteral::Number(number) => $(number.to_string()),
+                            RpLiteral::Boolean(boolean) => $(boolean.to_string()),
+                        }),
+                    })
+                }
+
+                if err := $(&self.unmarshal)(b, &data); err != nil {
+                    return err
+                }
+
+                *this = $name(data)
+                return nil
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl EnumCodegen for Codegen {
     fn generate(&self, e: EnumAdded) -> Result<()> {
         let EnumAdded {
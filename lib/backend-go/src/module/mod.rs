@@ -1,3 +1,7 @@
 mod encoding_json;
+mod net_http;
+mod newtype;
 
 pub(crate) use self::encoding_json::Module as EncodingJson;
+pub(crate) use self::net_http::Module as NetHttp;
+pub(crate) use self::newtype::Module as Newtype;
@@ -0,0 +1,515 @@
+//! net/http module for Go
+//!
+//! Generates clients, service interfaces, and `http.Handler` routers for services.
+
+use crate::compiler::Comments;
+use crate::flavored::*;
+use crate::{Options, ServiceAdded, ServiceCodegen};
+use backend::Initializer;
+use genco::prelude::*;
+use naming::Naming;
+use reproto_core::errors::Result;
+
+/// Identifiers used by the generated code, which arguments must not shadow.
+const RESERVED: &[&str] = &[
+    // imported packages.
+    "bytes", "context", "fmt", "http", "io", "ioutil", "json", "strconv", "strings", "url",
+    // parameters, results, and locals.
+    "ctx", "err", "httpReq", "httpRes", "r", "reqBody", "reqJson", "resBody", "result", "segments",
+    "service", "this", "w",
+];
+
+pub(crate) struct Module {}
+
+impl Module {
+    pub fn new() -> Module {
+        Module {}
+    }
+}
+
+impl Initializer for Module {
+    type Options = Options;
+
+    fn initialize(&self, options: &mut Self::Options) -> Result<()> {
+        options.service_gens.push(Box::new(Codegen::new()));
+        Ok(())
+    }
+}
+
+struct Codegen {
+    to_upper_camel: naming::ToUpperCamel,
+    to_lower_camel: naming::ToLowerCamel,
+
+    context: go::Import,
+    bytes_reader: go::Import,
+    io_reader: go::Import,
+    write_string: go::Import,
+    read_all: go::Import,
+    errorf: go::Import,
+    sprint: go::Import,
+    trim_right: go::Import,
+    trim: go::Import,
+    split: go::Import,
+    quote: go::Import,
+    parse_int: go::Import,
+    parse_uint: go::Import,
+    parse_float: go::Import,
+    parse_bool: go::Import,
+    path_escape: go::Import,
+    path_unescape: go::Import,
+
+    marshal: go::Import,
+    unmarshal: go::Import,
+    new_decoder: go::Import,
+    new_encoder: go::Import,
+
+    client: go::Import,
+    handler: go::Import,
+    handler_func: go::Import,
+    response_writer: go::Import,
+    request: go::Import,
+    new_request: go::Import,
+    error: go::Import,
+    not_found: go::Import,
+    status_ok: go::Import,
+    status_bad_request: go::Import,
+    status_internal_server_error: go::Import,
+}
+
+impl Codegen {
+    pub fn new() -> Codegen {
+        Self {
+            to_upper_camel: naming::to_upper_camel(),
+            to_lower_camel: naming::to_lower_camel(),
+
+            context: go::import("context", "Context"),
+            bytes_reader: go::import("bytes", "NewReader"),
+            io_reader: go::import("io", "Reader"),
+            write_string: go::import("io", "WriteString"),
+            read_all: go::import("io/ioutil", "ReadAll"),
+            errorf: go::import("fmt", "Errorf"),
+            sprint: go::import("fmt", "Sprint"),
+            trim_right: go::import("strings", "TrimRight"),
+            trim: go::import("strings", "Trim"),
+            split: go::import("strings", "Split"),
+            quote: go::import("strconv", "Quote"),
+            parse_int: go::import("strconv", "ParseInt"),
+            parse_uint: go::import("strconv", "ParseUint"),
+            parse_float: go::import("strconv", "ParseFloat"),
+            parse_bool: go::import("strconv", "ParseBool"),
+            path_escape: go::import("net/url", "PathEscape"),
+            path_unescape: go::import("net/url", "PathUnescape"),
+
+            marshal: go::import("encoding/json", "Marshal"),
+            unmarshal: go::import("encoding/json", "Unmarshal"),
+            new_decoder: go::import("encoding/json", "NewDecoder"),
+            new_encoder: go::import("encoding/json", "NewEncoder"),
+
+            client: go::import("net/http", "Client"),
+            handler: go::import("net/http", "Handler"),
+            handler_func: go::import("net/http", "HandlerFunc"),
+            response_writer: go::import("net/http", "ResponseWriter"),
+            request: go::import("net/http", "Request"),
+            new_request: go::import("net/http", "NewRequestWithContext"),
+            error: go::import("net/http", "Error"),
+            not_found: go::import("net/http", "NotFound"),
+            status_ok: go::import("net/http", "StatusOK"),
+            status_bad_request: go::import("net/http", "StatusBadRequest"),
+            status_internal_server_error: go::import("net/http", "StatusInternalServerError"),
+        }
+    }
+
+    /// Name of the method corresponding to an endpoint.
+    fn method(&self, e: &GoEndpoint) -> String {
+        self.to_upper_camel.convert(e.ident())
+    }
+
+    /// Name of the local variable for an argument.
+    ///
+    /// Names which are used by the generated code are suffixed with an underscore.
+    fn var(&self, arg: &RpEndpointArgument) -> String {
+        let var = self.to_lower_camel.convert(arg.safe_ident());
+
+        if RESERVED.contains(&var.as_str()) {
+            return format!("{}_", var);
+        }
+
+        var
+    }
+
+    /// The signature of the method corresponding to an endpoint, shared by the client and the
+    /// service interface.
+    fn signature(&self, e: &GoEndpoint, http: &RpEndpointHttp1, named: bool) -> Tokens<Go> {
+        let result = match (&http.response, &e.http.accept, named) {
+            (Some(ty), RpAccept::Json, true) => quote!((result $ty, err error)),
+            (Some(ty), RpAccept::Json, false) => quote!(($ty, error)),
+            (Some(_), RpAccept::Text, true) => quote!((result string, err error)),
+            (Some(_), RpAccept::Text, false) => quote!((string, error)),
+            (None, _, true) => quote!((err error)),
+            (None, _, false) => quote!(error),
+        };
+
+        quote! {
+            $(self.method(e))(ctx $(&self.context)$(for a in &e.arguments => , $(self.var(a)) $(a.channel.ty()))) $result
+        }
+    }
+
+    /// Build the url of an endpoint, relative to the base url of the client.
+    fn url(&self, path: &RpPathSpec) -> Tokens<Go> {
+        let mut parts = vec![quote!(this.url)];
+        let mut segment = String::new();
+
+        for step in &path.steps {
+            segment.push('/');
+
+            for part in &step.parts {
+                match part {
+                    RpPathPart::Segment(s) => segment.push_str(s),
+                    RpPathPart::Variable(arg) => {
+                        parts.push(quote!($(quoted(std::mem::take(&mut segment)))));
+                        parts.push(self.path_encode(arg));
+                    }
+                }
+            }
+        }
+
+        if !segment.is_empty() {
+            parts.push(quote!($(quoted(segment))));
+        }
+
+        quote!($(for p in parts join ( + ) => $p))
+    }
+
+    /// Encode a path variable.
+    ///
+    /// Types which are not primitive are encoded with their JSON representation, as long as it is
+    /// a string, like for string-based enums.
+    fn path_encode(&self, arg: &RpEndpointArgument) -> Tokens<Go> {
+        let var = &self.var(arg);
+
+        match arg.channel.ty() {
+            Type::String => quote!($(&self.path_escape)($var)),
+            Type::Primitive { .. } => quote!($(&self.path_escape)($(&self.sprint)($var))),
+            _ => quote!($(&self.path_escape)($(var)Path)),
+        }
+    }
+
+    /// Build the client method calling a single endpoint.
+    fn client_method(
+        &self,
+        t: &mut Tokens<Go>,
+        ident: &str,
+        e: &GoEndpoint,
+        http: &RpEndpointHttp1,
+    ) {
+        let encoded = e
+            .arguments
+            .iter()
+            .filter(|a| !matches!(a.channel.ty(), Type::String | Type::Primitive { .. }))
+            .filter(|a| path_variables(&http.path).any(|v| v.safe_ident() == a.safe_ident()))
+            .collect::<Vec<_>>();
+
+        quote_in! { *t =>
+            $(Comments(&e.comment))
+            func (this *$ident) $(self.signature(e, http, true)) {
+                $(for a in encoded join ($['\n']) {
+                    $(self.var(a))Json, err := $(&self.marshal)($(self.var(a)))
+
+                    if err != nil {
+                        return
+                    }
+
+                    var $(self.var(a))Path string
+
+                    if err = $(&self.unmarshal)($(self.var(a))Json, &$(self.var(a))Path); err != nil {
+                        return
+                    }
+                    $['\n']
+                })
+                var reqBody $(&self.io_reader)
+                $(if let Some(req) = &e.request {
+                    $['\n']
+                    reqJson, err := $(&self.marshal)($(self.var(req)))
+
+                    if err != nil {
+                        return
+                    }
+
+                    reqBody = $(&self.bytes_reader)(reqJson)
+                })
+
+                httpReq, err := $(&self.new_request)(ctx, $(quoted(http.method.as_str())), $(self.url(&http.path)), reqBody)
+
+                if err != nil {
+                    return
+                }
+                $(if e.request.is_some() {
+                    $['\n']
+                    httpReq.Header.Set("Content-Type", "application/json")
+                })
+
+                httpRes, err := this.client.Do(httpReq)
+
+                if err != nil {
+                    return
+                }
+
+                defer httpRes.Body.Close()
+
+                if httpRes.StatusCode < 200 || httpRes.StatusCode >= 300 {
+                    err = $(&self.errorf)("request failed: %s", httpRes.Status)
+                    return
+                }
+                $(match (&http.response, &e.http.accept) {
+                    (Some(_), RpAccept::Json) => {
+                        $['\n']
+                        err = $(&self.new_decoder)(httpRes.Body).Decode(&result)
+                    }
+                    (Some(_), RpAccept::Text) => {
+                        $['\n']
+                        resBody, err := $(&self.read_all)(httpRes.Body)
+
+                        if err != nil {
+                            return
+                        }
+
+                        result = string(resBody)
+                    }
+                    (None, _) => {}
+                })
+                return
+            }
+        }
+    }
+
+    /// Build the case routing a request to a single endpoint.
+    fn route(&self, t: &mut Tokens<Go>, e: &GoEndpoint, http: &RpEndpointHttp1) -> Result<()> {
+        let mut conditions = vec![quote!(r.Method == $(quoted(http.method.as_str())))];
+        let mut variables = Vec::new();
+
+        let steps = if http.path.steps.is_empty() {
+            1
+        } else {
+            http.path.steps.len()
+        };
+
+        conditions.push(quote!(len(segments) == $steps));
+
+        if http.path.steps.is_empty() {
+            conditions.push(quote!(segments[0] == ""));
+        }
+
+        for (i, step) in http.path.steps.iter().enumerate() {
+            match step.parts.as_slice() {
+                [] => conditions.push(quote!(segments[$i] == "")),
+                [RpPathPart::Segment(s)] => {
+                    conditions.push(quote!(segments[$i] == $(quoted(s.as_str()))))
+                }
+                [RpPathPart::Variable(arg)] => variables.push((i, arg)),
+                _ => {
+                    return Err(format!(
+                    "net/http: path segments which mix variables and text are not supported: {}",
+                    http.path
+                )
+                    .into())
+                }
+            }
+        }
+
+        let arguments = e.arguments.iter().map(|a| self.var(a));
+
+        quote_in! { *t =>
+            case $(for c in conditions join ( && ) => $c):
+                $(for (i, arg) in variables join ($['\n']) {
+                    $(ref t => self.path_decode(t, i, arg))
+                })
+                $(if let Some(req) = &e.request {
+                    $['\n']
+                    var $(self.var(req)) $(req.channel.ty())
+
+                    if err := $(&self.new_decoder)(r.Body).Decode(&$(self.var(req))); err != nil {
+                        $(&self.error)(w, err.Error(), $(&self.status_bad_request))
+                        return
+                    }
+                })
+                $['\n']
+                $(match (&http.response, &e.http.accept) {
+                    (Some(_), RpAccept::Json) => {
+                        result, err := service.$(self.method(e))(r.Context()$(for a in arguments => , $a))
+
+                        if err != nil {
+                            $(&self.error)(w, err.Error(), $(&self.status_internal_server_error))
+                            return
+                        }
+
+                        w.Header().Set("Content-Type", "application/json")
+                        $(&self.new_encoder)(w).Encode(result)
+                    }
+                    (Some(_), RpAccept::Text) => {
+                        result, err := service.$(self.method(e))(r.Context()$(for a in arguments => , $a))
+
+                        if err != nil {
+                            $(&self.error)(w, err.Error(), $(&self.status_internal_server_error))
+                            return
+                        }
+
+                        w.Header().Set("Content-Type", "text/plain; charset=utf-8")
+                        $(&self.write_string)(w, result)
+                    }
+                    (None, _) => {
+                        if err := service.$(self.method(e))(r.Context()$(for a in arguments => , $a)); err != nil {
+                            $(&self.error)(w, err.Error(), $(&self.status_internal_server_error))
+                            return
+                        }
+
+                        w.WriteHeader($(&self.status_ok))
+                    }
+                })
+        }
+
+        Ok(())
+    }
+
+    /// Decode the path variable found in the given segment.
+    fn path_decode(&self, t: &mut Tokens<Go>, i: usize, arg: &RpEndpointArgument) {
+        let var = &self.var(arg);
+        let ty = arg.channel.ty();
+
+        let raw = match ty {
+            Type::String => quote!($var),
+            _ => quote!($(var)Path),
+        };
+
+        quote_in! { *t =>
+            $(&raw), err := $(&self.path_unescape)(segments[$i])
+
+            if err != nil {
+                $(&self.error)(w, err.Error(), $(&self.status_bad_request))
+                return
+            }
+        }
+
+        let parse = match ty {
+            Type::String => return,
+            Type::Primitive { primitive } => {
+                let parsed = match primitive {
+                    Primitive::U32 => quote!($(&self.parse_uint)($(&raw), 10, 32)),
+                    Primitive::U64 => quote!($(&self.parse_uint)($(&raw), 10, 64)),
+                    Primitive::I32 => quote!($(&self.parse_int)($(&raw), 10, 32)),
+                    Primitive::I64 => quote!($(&self.parse_int)($(&raw), 10, 64)),
+                    Primitive::F32 => quote!($(&self.parse_float)($(&raw), 32)),
+                    Primitive::F64 => quote!($(&self.parse_float)($(&raw), 64)),
+                    Primitive::Bool => quote!($(&self.parse_bool)($(&raw))),
+                };
+
+                quote! {
+                    $(var)Parsed, err := $parsed
+
+                    if err != nil {
+                        $(&self.error)(w, err.Error(), $(&self.status_bad_request))
+                        return
+                    }
+
+                    $var := $(*primitive)($(var)Parsed)
+                }
+            }
+            ty => quote! {
+                var $var $ty
+
+                if err := $(&self.unmarshal)([]byte($(&self.quote)($(&raw))), &$var); err != nil {
+                    $(&self.error)(w, err.Error(), $(&self.status_bad_request))
+                    return
+                }
+            },
+        };
+
+        quote_in! { *t =>
+            $['\n']
+            $parse
+        }
+    }
+}
+
+impl ServiceCodegen for Codegen {
+    fn generate(&self, e: ServiceAdded) -> Result<()> {
+        let ServiceAdded {
+            container,
+            name,
+            body,
+        } = e;
+
+        let client = &format!("{}_Client", name.name);
+        let service = &format!("{}_Service", name.name);
+
+        let endpoints = body
+            .endpoints
+            .iter()
+            .flat_map(|e| e.http1.as_ref().map(|http| (e, http)))
+            .collect::<Vec<_>>();
+
+        let mut routes = Vec::new();
+
+        for (e, http) in &endpoints {
+            let mut t = Tokens::new();
+            self.route(&mut t, e, http)?;
+            routes.push(t);
+        }
+
+        quote_in! { *container =>
+            $(Comments(&body.comment))
+            type $client struct {
+                client *$(&self.client)
+                url string
+            }
+
+            func New$(client)(client *$(&self.client), url string) *$client {
+                $(if let Some(default_url) = &body.http.url {
+                    if url == "" {
+                        url = $(quoted(default_url.as_str()))
+                    }
+                    $['\n']
+                })
+                return &$client{client: client, url: $(&self.trim_right)(url, "/")}
+            }
+            $(for (e, http) in &endpoints {
+                $['\n']
+                $(ref t => self.client_method(t, client, e, http))
+            })
+
+            $(Comments(&body.comment))
+            type $service interface {
+                $(for (e, http) in &endpoints join ($['\r']) {
+                    $(Comments(&e.comment))
+                    $(self.signature(e, http, false))
+                })
+            }
+
+            func New$(name.name.as_str())_Handler(service $service) $(&self.handler) {
+                return $(&self.handler_func)(func(w $(&self.response_writer), r *$(&self.request)) {
+                    $(if !routes.is_empty() {
+                        segments := $(&self.split)($(&self.trim)(r.URL.EscapedPath(), "/"), "/")
+                        $['\n']
+                    })
+                    switch {
+                    $(for r in routes join ($['\n']) => $r)
+                    default:
+                        $(&self.not_found)(w, r)
+                    }
+                })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterate over all variables in the given path.
+fn path_variables(path: &RpPathSpec) -> impl Iterator<Item = &RpEndpointArgument> {
+    path.steps
+        .iter()
+        .flat_map(|s| &s.parts)
+        .flat_map(|p| match p {
+            RpPathPart::Variable(arg) => Some(arg),
+            RpPathPart::Segment(_) => None,
+        })
+}
@@ -0,0 +1,22 @@
+//! newtype module for Go
+
+use crate::Options;
+use backend::Initializer;
+use reproto_core::errors::Result;
+
+pub(crate) struct Module {}
+
+impl Module {
+    pub fn new() -> Module {
+        Module {}
+    }
+}
+
+impl Initializer for Module {
+    type Options = Options;
+
+    fn initialize(&self, options: &mut Self::Options) -> Result<()> {
+        options.newtype = true;
+        Ok(())
+    }
+}
@@ -77,6 +77,16 @@ decl_codegen! {
         pub(crate) inner: &'a mut Vec<java::Tokens>,
     }
 
+    /// Generator used for services.
+    service<'a> {
+        /// The identifier for the service.
+        pub(crate) ident: &'a str,
+        /// The service being generated for.
+        pub(crate) body: &'a RpServiceBody,
+        /// Inner content to add to the service interface.
+        pub(crate) inner: &'a mut Vec<java::Tokens>,
+    }
+
     /// Generator used for interface sub-types.
     interface_sub_type<'a> {
         /// The sub type strategy associated with the interface.
@@ -133,4 +143,12 @@ decl_codegen! {
         /// Annotations to add to the class field.
         pub(crate) annotations: &'a mut Vec<java::Tokens>,
     }
+
+    /// Generator used for newtype wrappers of aliases.
+    newtype<'a> {
+        /// Annotations to add to the constructor.
+        pub(crate) constructor: &'a mut Vec<java::Tokens>,
+        /// Annotations to add to the getter of the wrapped value.
+        pub(crate) getter: &'a mut Vec<java::Tokens>,
+    }
 }
@@ -1,13 +1,14 @@
 //! Java backend for reproto
 
 use crate::flavored::*;
+use crate::module;
 use crate::Options;
 use genco::fmt;
 use genco::prelude::*;
 use genco::tokens::from_fn;
 use naming::Naming;
 use reproto_core::errors::Result;
-use reproto_core::{Handle, RelativePathBuf, Spanned};
+use reproto_core::{Handle, RelativePathBuf, RpNumber, RpValidate, Spanned};
 use trans::Translated;
 
 #[allow(unused)]
@@ -21,6 +22,8 @@ pub(crate) struct Compiler<'a> {
     object: java::Import,
     string: java::Import,
     illegal_argument: java::Import,
+    pattern: java::Import,
+    completable_future: java::Import,
 }
 
 impl<'a> Compiler<'a> {
@@ -35,11 +38,20 @@ impl<'a> Compiler<'a> {
             object: java::import("java.lang", "Object"),
             string: java::import("java.lang", "String"),
             illegal_argument: java::import("java.lang", "IllegalArgumentException"),
+            pattern: java::import("java.util.regex", "Pattern"),
+            completable_future: java::import("java.util.concurrent", "CompletableFuture"),
         }
     }
 
     pub(crate) fn compile(&self, handle: &dyn Handle) -> Result<()> {
         for decl in self.env.toplevel_decl_iter() {
+            // NB: resolved aliases have nothing to generate.
+            if let RpDecl::Alias(_) = decl {
+                if !self.options.newtype {
+                    continue;
+                }
+            }
+
             let package = decl.name().package.join(".");
 
             let path = decl
@@ -101,8 +113,13 @@ impl<'a> Compiler<'a> {
                 let mut ann = Vec::new();
                 self.options.gen.class_constructor_arg(f, &mut ann);
 
+                // NB: fields with a default value accept null, which is replaced by the default.
                 arguments.push(quote! {
-                    $(for a in ann => $a$[' '])$(f.field_type()) $(f.safe_ident())
+                    $(for a in ann => $a$[' '])$(if f.default.is_some() {
+                        $(f.ty.clone().into_boxed())
+                    } else {
+                        $(f.field_type())
+                    }) $(f.safe_ident())
                 });
             }
 
@@ -115,9 +132,18 @@ impl<'a> Compiler<'a> {
                     $(for a in arguments join (,$['\r']) => $a)
                 ) {
                     $(for f in fields join ($['\r']) {
-                        $(if !f.is_optional() && !f.ty.is_primitive() {
-                            $(&self.objects).requireNonNull($(f.safe_ident()), $[str]($[const](&f.ident): must not be null));
+                        $(match &f.default {
+                            Some(default) => {
+                                if ($(f.safe_ident()) == null) {
+                                    $(f.safe_ident()) = $(self.literal(&f.ty, default));
+                                }
+                            }
+                            None if !f.is_optional() && !f.ty.is_primitive() => {
+                                $(&self.objects).requireNonNull($(f.safe_ident()), $[str]($[const](&f.ident): must not be null));
+                            }
+                            None => (),
                         })
+                        $(self.validate(f))
                         this.$(f.safe_ident()) = $(f.safe_ident());
                     })
                 }
@@ -125,6 +151,123 @@ impl<'a> Compiler<'a> {
         })
     }
 
+    /// Build the literal for the default value of a field.
+    fn literal(&self, ty: &Type, literal: &RpLiteral) -> java::Tokens {
+        match literal {
+            RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+            RpLiteral::Number(number) => {
+                // NB: the literal might be boxed, so it needs a suffix matching its type.
+                let suffix = match *ty {
+                    Type::Primitive { primitive } | Type::Boxed { primitive } => match primitive {
+                        Primitive::Long => "L",
+                        Primitive::Float => "F",
+                        Primitive::Double => "D",
+                        _ => "",
+                    },
+                    _ => "",
+                };
+
+                quote!($(format!("{}{}", number, suffix)))
+            }
+            RpLiteral::Boolean(boolean) => quote!($(boolean.to_string())),
+        }
+    }
+
+    /// Check the validation rules of a field, if it has any.
+    fn validate<'f>(&'f self, f: &'f Field) -> impl FormatInto<Java> + 'f {
+        from_fn(move |t| {
+            let validate = match &f.validate {
+                Some(validate) => validate,
+                None => return,
+            };
+
+            let ident = f.safe_ident();
+
+            let value = if f.is_optional() {
+                quote!($ident.get())
+            } else {
+                quote!($ident)
+            };
+
+            let mut checks = Vec::new();
+
+            match validate {
+                RpValidate::Number(validate) => {
+                    let long = matches!(
+                        f.ty,
+                        Type::Primitive {
+                            primitive: Primitive::Long
+                        }
+                    );
+
+                    // NB: bounds might not fit into an int literal.
+                    let bound = |n: &RpNumber| {
+                        if long {
+                            format!("{}L", n)
+                        } else {
+                            n.to_string()
+                        }
+                    };
+
+                    if let Some(min) = &validate.min {
+                        checks.push(quote! {
+                            if ($(&value) < $(bound(min))) {
+                                throw new $(&self.illegal_argument)($(quoted(format!("{}: must be greater than or equal to {}", f.ident, min))));
+                            }
+                        });
+                    }
+
+                    if let Some(max) = &validate.max {
+                        checks.push(quote! {
+                            if ($(&value) > $(bound(max))) {
+                                throw new $(&self.illegal_argument)($(quoted(format!("{}: must be less than or equal to {}", f.ident, max))));
+                            }
+                        });
+                    }
+                }
+                RpValidate::String(validate) => {
+                    if let Some(pattern) = &validate.pattern {
+                        let pattern = pattern.to_string();
+
+                        checks.push(quote! {
+                            if (!$(&self.pattern).matches($(quoted(&pattern)), $(&value))) {
+                                throw new $(&self.illegal_argument)($(quoted(format!("{}: must match pattern `{}`", f.ident, pattern))));
+                            }
+                        });
+                    }
+
+                    if let Some(min_length) = validate.min_length {
+                        checks.push(quote! {
+                            if ($(&value).codePointCount(0, $(&value).length()) < $(min_length.to_string())) {
+                                throw new $(&self.illegal_argument)($(quoted(format!("{}: must be at least {} characters long", f.ident, min_length))));
+                            }
+                        });
+                    }
+
+                    if let Some(max_length) = validate.max_length {
+                        checks.push(quote! {
+                            if ($(&value).codePointCount(0, $(&value).length()) > $(max_length.to_string())) {
+                                throw new $(&self.illegal_argument)($(quoted(format!("{}: must be at most {} characters long", f.ident, max_length))));
+                            }
+                        });
+                    }
+                }
+            }
+
+            if f.is_optional() {
+                quote_in! { *t =>
+                    if ($ident.isPresent()) {
+                        $(for c in checks join ($['\r']) => $c)
+                    }
+                }
+            } else {
+                quote_in! { *t =>
+                    $(for c in checks join ($['\r']) => $c)
+                }
+            }
+        })
+    }
+
     fn process_enum(&self, t: &mut java::Tokens, depth: usize, body: &RpEnumBody) -> Result<()> {
         let mut inner = Vec::new();
 
@@ -133,17 +276,17 @@ impl<'a> Compiler<'a> {
             .enum_ty(&body.ident, &body.enum_type, &mut inner);
 
         quote_in! {*t =>
-            $(java::block_comment(&body.comment))
+            $(doc(&body.comment, body.deprecated.as_ref()))
             public $(if depth > 0 => static) enum $(&body.ident) {
                 $(match &body.variants {
                     RpVariants::String { variants } => {
                         $(for variant in variants join (,$['\r']) {
-                            $(self.to_upper.convert(variant.ident()))($(quoted(&variant.value)))
+                            $(if variant.deprecated.is_some() => @Deprecated$[' '])$(self.to_upper.convert(variant.ident()))($(quoted(&variant.value)))
                         })
                     }
                     RpVariants::Number { variants } => {
                         $(for variant in variants join (,$['\r']) {
-                            $(self.to_upper.convert(variant.ident()))($(match body.enum_type.as_primitive() {
+                            $(if variant.deprecated.is_some() => @Deprecated$[' '])$(self.to_upper.convert(variant.ident()))($(match body.enum_type.as_primitive() {
                                 Some(Primitive::Long) => $(display(&variant.value))L,
                                 _ => $(display(&variant.value)),
                             }))
@@ -175,7 +318,7 @@ impl<'a> Compiler<'a> {
             .tuple(&body.ident, &body.fields, &mut inner, &mut annotations);
 
         quote_in! { *t =>
-            $(java::block_comment(&body.comment))
+            $(doc(&body.comment, body.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public $(if depth > 0 => static) class $(&body.ident) {
                 $(for f in &body.fields join ($['\r']) {
@@ -218,7 +361,7 @@ impl<'a> Compiler<'a> {
             .class(&body.ident, &body.fields, &mut inner, &mut annotations);
 
         quote_in! { *t =>
-            $(java::block_comment(&body.comment))
+            $(doc(&body.comment, body.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public $(if depth > 0 => static) class $(&body.ident) {
                 $(for f in &body.fields join ($['\r']) {
@@ -269,7 +412,7 @@ impl<'a> Compiler<'a> {
         );
 
         quote_in! { *t =>
-            $(java::block_comment(&body.comment))
+            $(doc(&body.comment, body.deprecated.as_ref()))
             $(for a in annotations join ($['\r']) => $a)
             public $(if depth > 0 => static) interface $(&body.ident) {
                 $(for f in &body.fields join ($['\n']) {
@@ -291,7 +434,7 @@ impl<'a> Compiler<'a> {
                             .interface_sub_type(&body.sub_type_strategy, &mut annotations);
 
                         quote_in!{*t =>
-                            $(java::block_comment(&s.comment))
+                            $(doc(&s.comment, s.deprecated.as_ref()))
                             $(for a in annotations join ($['\r']) => $a)
                             public static class $(&s.ident) implements $(&body.ident) {
                                 $(for f in &fields join ($['\r']) {
@@ -341,7 +484,117 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
-    fn process_service(&self, _: &mut java::Tokens, _: usize, _: &RpServiceBody) -> Result<()> {
+    fn process_service(
+        &self,
+        t: &mut java::Tokens,
+        depth: usize,
+        body: &RpServiceBody,
+    ) -> Result<()> {
+        if self.options.grpc {
+            for e in &body.endpoints {
+                module::grpc::check(e)?;
+            }
+        }
+
+        let mut inner = Vec::new();
+        self.options.gen.service(&body.ident, body, &mut inner);
+
+        // streaming endpoints are not supported.
+        let endpoints = body.endpoints.iter().filter(|e| e.is_unary());
+
+        quote_in! { *t =>
+            $(doc(&body.comment, body.deprecated.as_ref()))
+            public $(if depth > 0 => static) interface $(&body.ident) {
+                $(for e in endpoints join ($['\n']) {
+                    $(doc(&e.comment, e.deprecated.as_ref()))
+                    $(&self.completable_future)<$(e.response_ty())> $(e.safe_ident())($(e.arguments()));
+                })
+
+                $(for d in &body.decls join ($['\n']) {
+                    $(ref t => self.process_decl(t, depth + 1, d)?)
+                })
+
+                $(for i in inner join ($['\n']) => $i)
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_const(&self, t: &mut java::Tokens, depth: usize, body: &RpConstBody) -> Result<()> {
+        let value = self.literal(&body.ty, &body.value);
+
+        // NB: top-level constants need a class to live in.
+        if depth == 0 {
+            quote_in! { *t =>
+                $(doc(&body.comment, body.deprecated.as_ref()))
+                public final class $(&body.ident) {
+                    public static final $(&body.ty) VALUE = $value;
+
+                    private $(&body.ident)() {
+                    }
+                }
+            }
+        } else {
+            quote_in! { *t =>
+                $(doc(&body.comment, body.deprecated.as_ref()))
+                public static final $(&body.ty) $(&body.ident) = $value;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Aliases are resolved unless they are generated as newtype wrappers.
+    fn process_alias(
+        &self,
+        t: &mut java::Tokens,
+        depth: usize,
+        body: &Spanned<RpAliasBody>,
+    ) -> Result<()> {
+        if !self.options.newtype {
+            return Ok(());
+        }
+
+        let field = Spanned::new(
+            Field::newtype(body.ty.clone(), body.validate.clone()),
+            body.span(),
+        );
+
+        let fields = std::slice::from_ref(&field);
+
+        let mut constructor = Vec::new();
+        let mut getter = Vec::new();
+
+        self.options.gen.newtype(&mut constructor, &mut getter);
+
+        quote_in! { *t =>
+            $(doc(&body.comment, body.deprecated.as_ref()))
+            public $(if depth > 0 => static) class $(&body.ident) {
+                private final $(&field.ty) value;
+
+                $(for a in constructor join ($['\r']) => $a)
+                public $(&body.ident)(final $(&field.ty) value) {
+                    $(if !field.ty.is_primitive() {
+                        $(&self.objects).requireNonNull(value, "value: must not be null");
+                    })
+                    $(self.validate(&field))
+                    this.value = value;
+                }
+
+                $(for a in getter join ($['\r']) => $a)
+                public $(&field.ty) getValue() {
+                    return this.value;
+                }
+
+                $(self.to_string(&body.ident, fields))
+
+                $(self.hash_code(fields))
+
+                $(self.equals(&body.ident, fields))
+            }
+        }
+
         Ok(())
     }
 
@@ -362,6 +615,12 @@ impl<'a> Compiler<'a> {
             RpDecl::Service(ty) => {
                 self.process_service(t, depth, ty)?;
             }
+            RpDecl::Const(body) => {
+                self.process_const(t, depth, body)?;
+            }
+            RpDecl::Alias(body) => {
+                self.process_alias(t, depth, body)?;
+            }
         }
 
         Ok(())
@@ -377,6 +636,7 @@ impl<'a> Compiler<'a> {
             let name = self.to_upper.convert(&f.ident);
 
             quote_in! {*t =>
+                $(if f.deprecated.is_some() => @Deprecated)
                 public void set$name(final $(&f.ty) $(f.safe_ident()));
             }
         })
@@ -392,6 +652,7 @@ impl<'a> Compiler<'a> {
             let name = self.to_upper.convert(&f.ident);
 
             quote_in! { *t =>
+                $(if f.deprecated.is_some() => @Deprecated)
                 $(if is_override => @Override)
                 public void set$name(final $(&f.ty) $ident) {
                     this.$ident = $ident;
@@ -416,7 +677,7 @@ impl<'a> Compiler<'a> {
             };
 
             quote_in! {*t =>
-                $(java::block_comment(&f.comment))
+                $(doc(&f.comment, f.deprecated.as_ref()))
                 public $(f.field_type()) get$name();
             }
         })
@@ -446,7 +707,7 @@ impl<'a> Compiler<'a> {
             };
 
             quote_in! { *t =>
-                $(java::block_comment(&f.comment))
+                $(doc(&f.comment, f.deprecated.as_ref()))
                 $(for a in ann join ($['\r']) => $a)
                 $(if is_override => @Override)
                 public $(f.field_type()) get$name() {
@@ -579,3 +840,29 @@ fn code(codes: &[Spanned<RpCode>]) -> impl FormatInto<Java> + '_ {
         }
     })
 }
+
+/// Documentation comment for an item, with an `@Deprecated` annotation if it is deprecated.
+fn doc<'f>(
+    comment: &'f [String],
+    deprecated: Option<&'f RpDeprecated>,
+) -> impl FormatInto<Java> + 'f {
+    from_fn(move |t| {
+        let mut lines = comment.to_vec();
+
+        if let Some(deprecated) = deprecated {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+
+            match deprecated.message() {
+                Some(message) => lines.push(format!("@deprecated {}", message)),
+                None => lines.push(String::from("@deprecated")),
+            }
+        }
+
+        quote_in! {*t =>
+            $(java::block_comment(lines))
+            $(if deprecated.is_some() => @Deprecated)
+        }
+    })
+}
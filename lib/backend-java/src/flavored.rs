@@ -9,7 +9,7 @@ use naming::Naming;
 use reproto_core::errors::Result;
 use reproto_core::{
     CoreFlavor, Diagnostics, Flavor, FlavorField, FlavorTranslator, PackageTranslator,
-    RpNumberKind, RpNumberType, RpStringType, Spanned, Translate, Translator,
+    RpNumberKind, RpNumberType, RpStringType, RpValidate, Spanned, Translate, Translator,
 };
 use std::collections::HashMap;
 use std::fmt;
@@ -18,11 +18,45 @@ use std::rc::Rc;
 use trans::Packages;
 
 #[derive(Debug, Clone)]
-pub(crate) struct JavaHttp {
-    pub(crate) request: java::Import,
-    pub(crate) response: java::Import,
-    pub(crate) path: RpPathSpec,
-    pub(crate) method: RpHttpMethod,
+pub(crate) struct JavaEndpoint {
+    pub(crate) endpoint: RpEndpoint,
+    pub(crate) http1: Option<RpEndpointHttp1>,
+}
+
+impl JavaEndpoint {
+    /// Test if the endpoint neither streams requests nor responses.
+    pub(crate) fn is_unary(&self) -> bool {
+        let request = self.arguments.iter().all(|a| !a.channel.is_streaming());
+        let response = self.response.iter().all(|r| !r.is_streaming());
+        request && response
+    }
+
+    /// The type of the response, boxed so that it can be used as a type argument.
+    ///
+    /// Endpoints without a response use `Void`.
+    pub(crate) fn response_ty(&self) -> impl FormatInto<Java> + '_ {
+        quote_fn! {
+            $(match &self.response {
+                Some(response) => $(response.ty().clone().into_boxed()),
+                None => Void,
+            })
+        }
+    }
+
+    /// The declared arguments of the endpoint.
+    pub(crate) fn arguments(&self) -> impl FormatInto<Java> + '_ {
+        quote_fn! {
+            $(for a in &self.arguments join (, ) => final $(a.channel.ty()) $(a.safe_ident()))
+        }
+    }
+}
+
+impl Deref for JavaEndpoint {
+    type Target = RpEndpoint;
+
+    fn deref(&self) -> &Self::Target {
+        &self.endpoint
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -142,7 +176,7 @@ impl Type {
     }
 
     /// Convert the type into a boxed type.
-    fn into_boxed(self) -> Self {
+    pub(crate) fn into_boxed(self) -> Self {
         match self {
             Self::Primitive { primitive } => Self::Boxed { primitive },
             other => other,
@@ -264,6 +298,19 @@ impl Field {
     }
 }
 
+impl Field {
+    /// Build the field holding the value of a newtype wrapper.
+    pub(crate) fn newtype(ty: Type, validate: Option<RpValidate>) -> Self {
+        let mut inner = RpField::new("value", ty);
+        inner.validate = validate;
+
+        Field {
+            optional: Rc::new(java::import("java.util", "Optional")),
+            inner,
+        }
+    }
+}
+
 impl FlavorField for Field {
     fn is_discriminating(&self) -> bool {
         self.inner.is_discriminating()
@@ -285,7 +332,7 @@ impl Flavor for JavaFlavor {
     type Type = Type;
     type Name = Name;
     type Field = Field;
-    type Endpoint = RpEndpoint;
+    type Endpoint = JavaEndpoint;
     type Package = RpPackage;
     type EnumType = Type;
 }
@@ -321,8 +368,6 @@ impl FlavorTranslator for JavaFlavorTranslator {
     type Source = CoreFlavor;
     type Target = JavaFlavor;
 
-    reproto_core::translator_defaults!(Self, endpoint);
-
     fn translate_field<T>(
         &self,
         translator: &T,
@@ -412,6 +457,21 @@ impl FlavorTranslator for JavaFlavorTranslator {
         })
     }
 
+    fn translate_endpoint<T>(
+        &self,
+        translator: &T,
+        diag: &mut Diagnostics,
+        endpoint: RpEndpoint<CoreFlavor>,
+    ) -> Result<JavaEndpoint>
+    where
+        T: Translator<Source = CoreFlavor, Target = JavaFlavor>,
+    {
+        let endpoint = endpoint.translate(diag, translator)?;
+        let http1 = RpEndpointHttp1::from_endpoint(&endpoint);
+
+        Ok(JavaEndpoint { endpoint, http1 })
+    }
+
     fn translate_package(&self, source: RpVersionedPackage) -> Result<RpPackage> {
         self.packages.translate_package(source)
     }
@@ -96,12 +96,13 @@ impl Lang for JavaLang {
 pub enum Module {
     Jackson,
     Lombok,
-    //Grpc,
+    Grpc,
     Builder,
     ConstructorProperties,
     Mutable,
+    Newtype,
     Nullable,
-    //OkHttp(module::OkHttpConfig),
+    OkHttp(module::OkHttpConfig),
 }
 
 impl TryFromToml for Module {
@@ -109,12 +110,13 @@ impl TryFromToml for Module {
         let result = match id {
             "jackson" => Self::Jackson,
             "lombok" => Self::Lombok,
-            //"grpc" => Grpc,
+            "grpc" => Self::Grpc,
             "builder" => Self::Builder,
             "constructor_properties" => Self::ConstructorProperties,
             "mutable" => Self::Mutable,
+            "newtype" => Self::Newtype,
             "nullable" => Self::Nullable,
-            //"okhttp" => OkHttp(module::OkHttpConfig::default()),*/
+            "okhttp" => Self::OkHttp(module::OkHttpConfig::default()),
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -125,12 +127,13 @@ impl TryFromToml for Module {
         let result = match id {
             "jackson" => Self::Jackson,
             "lombok" => Self::Lombok,
-            //"grpc" => Grpc,
+            "grpc" => Self::Grpc,
             "builder" => Self::Builder,
             "constructor_properties" => Self::ConstructorProperties,
             "mutable" => Self::Mutable,
+            "newtype" => Self::Newtype,
             "nullable" => Self::Nullable,
-            //"okhttp" => OkHttp(value.try_into()?),
+            "okhttp" => Self::OkHttp(value.try_into()?),
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -145,15 +148,13 @@ fn setup_options(modules: Vec<Module>) -> Result<Options> {
         match module {
             Module::Jackson => module::Jackson.initialize(&mut options),
             Module::Lombok => module::Lombok.initialize(&mut options),
-            //Grpc => module::Grpc.initialize(c),
+            Module::Grpc => module::Grpc.initialize(&mut options),
             Module::Builder => module::Builder.initialize(&mut options),
             Module::ConstructorProperties => module::ConstructorProperties.initialize(&mut options),
             Module::Mutable => module::Mutable.initialize(&mut options),
+            Module::Newtype => module::Newtype.initialize(&mut options),
             Module::Nullable => module::Nullable.initialize(&mut options),
-            //OkHttp(config) => {
-            //let serialization = c.options.get_serialization()?;
-            //module::OkHttp::new(config).initialize(c, serialization);
-            //}
+            Module::OkHttp(config) => module::OkHttp::new(config).initialize(&mut options),
         };
     }
 
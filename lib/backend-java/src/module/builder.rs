@@ -43,7 +43,7 @@ impl codegen::class::Codegen for Builder {
 
                 public $(e.ident) build() {
                     $(for f in e.fields join ($['\r']) {
-                        $(if f.is_required() {
+                        $(if f.is_required() && f.default.is_none() {
                             final $(&f.ty) $(f.safe_ident()) = this.$(f.safe_ident())
                                 .orElseThrow(() -> new $(&*self.runtime_exception)($[str]($[const](&f.ident): missing required value)));
                         })
@@ -51,10 +51,10 @@ impl codegen::class::Codegen for Builder {
 
                     return new $(e.ident)(
                         $(for f in e.fields join (,$['\r']) {
-                            $(if f.is_optional() {
-                                this.$(f.safe_ident())
-                            } else {
-                                $(f.safe_ident())
+                            $(match &f.default {
+                                _ if f.is_optional() => this.$(f.safe_ident()),
+                                Some(_) => this.$(f.safe_ident()).orElse(null),
+                                None => $(f.safe_ident()),
                             })
                         })
                     );
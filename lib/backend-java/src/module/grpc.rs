@@ -0,0 +1,397 @@
+//! Module that generates gRPC stubs and services.
+//!
+//! Messages are serialized with Jackson instead of protobuf.
+
+use crate::codegen;
+use crate::flavored::*;
+use crate::Options;
+use genco::prelude::*;
+use naming::Naming;
+use reproto_core::errors::Result;
+use std::rc::Rc;
+
+pub struct Module;
+
+impl Module {
+    pub fn initialize(self, options: &mut Options) {
+        let generator = Rc::new(Generator::new());
+        options.gen.service.push(generator);
+        options.grpc = true;
+    }
+}
+
+/// Check that the endpoint can be called over gRPC, where the request is the only message sent.
+///
+/// Arguments other than the request would otherwise be silently dropped.
+pub(crate) fn check(e: &JavaEndpoint) -> Result<()> {
+    let request = e.request.as_ref().map(|r| r.ident.as_str());
+
+    let unsupported = e
+        .arguments
+        .iter()
+        .filter(|a| Some(a.ident.as_str()) != request)
+        .map(|a| a.ident.as_str())
+        .collect::<Vec<_>>();
+
+    if !unsupported.is_empty() {
+        return Err(format!(
+            "grpc: endpoint `{}` has arguments which are not part of the request: {}",
+            e.ident,
+            unsupported.join(", ")
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub struct Generator {
+    to_upper_snake: naming::ToUpperSnake,
+
+    io_exception: java::Import,
+    input_stream: java::Import,
+    byte_array_input_stream: java::Import,
+
+    method_descriptor: java::Import,
+    channel: java::Import,
+    call_options: java::Import,
+    status: java::Import,
+    bindable_service: java::Import,
+    server_service_definition: java::Import,
+    abstract_stub: java::Import,
+    client_calls: java::Import,
+    server_calls: java::Import,
+    stream_observer: java::Import,
+
+    object_mapper: java::Import,
+    type_reference: java::Import,
+}
+
+impl Generator {
+    fn new() -> Self {
+        Self {
+            to_upper_snake: naming::to_upper_snake(),
+
+            io_exception: java::import("java.io", "IOException"),
+            input_stream: java::import("java.io", "InputStream"),
+            byte_array_input_stream: java::import("java.io", "ByteArrayInputStream"),
+
+            method_descriptor: java::import("io.grpc", "MethodDescriptor"),
+            channel: java::import("io.grpc", "Channel"),
+            call_options: java::import("io.grpc", "CallOptions"),
+            status: java::import("io.grpc", "Status"),
+            bindable_service: java::import("io.grpc", "BindableService"),
+            server_service_definition: java::import("io.grpc", "ServerServiceDefinition"),
+            abstract_stub: java::import("io.grpc.stub", "AbstractStub"),
+            client_calls: java::import("io.grpc.stub", "ClientCalls"),
+            server_calls: java::import("io.grpc.stub", "ServerCalls"),
+            stream_observer: java::import("io.grpc.stub", "StreamObserver"),
+
+            object_mapper: java::import("com.fasterxml.jackson.databind", "ObjectMapper"),
+            type_reference: java::import("com.fasterxml.jackson.core.type", "TypeReference"),
+        }
+    }
+}
+
+/// The shape of a single gRPC call.
+#[derive(Clone, Copy)]
+enum Shape {
+    Unary,
+    ServerStreaming,
+    ClientStreaming,
+    BidiStreaming,
+}
+
+impl Shape {
+    fn new(e: &JavaEndpoint) -> Self {
+        let request = e.request.iter().any(|r| r.channel.is_streaming());
+        let response = e.response.iter().any(|r| r.is_streaming());
+
+        match (request, response) {
+            (false, false) => Shape::Unary,
+            (false, true) => Shape::ServerStreaming,
+            (true, false) => Shape::ClientStreaming,
+            (true, true) => Shape::BidiStreaming,
+        }
+    }
+
+    /// The `MethodDescriptor.MethodType` of the call.
+    fn method_type(self) -> &'static str {
+        match self {
+            Shape::Unary => "UNARY",
+            Shape::ServerStreaming => "SERVER_STREAMING",
+            Shape::ClientStreaming => "CLIENT_STREAMING",
+            Shape::BidiStreaming => "BIDI_STREAMING",
+        }
+    }
+
+    /// Suffix of the `ClientCalls` and `ServerCalls` helpers for the call.
+    fn calls(self) -> &'static str {
+        match self {
+            Shape::Unary => "UnaryCall",
+            Shape::ServerStreaming => "ServerStreamingCall",
+            Shape::ClientStreaming => "ClientStreamingCall",
+            Shape::BidiStreaming => "BidiStreamingCall",
+        }
+    }
+
+    /// If the client streams requests, in which case the call returns a request observer.
+    fn is_client_streaming(self) -> bool {
+        matches!(self, Shape::ClientStreaming | Shape::BidiStreaming)
+    }
+}
+
+/// A single endpoint, as seen by gRPC.
+struct Method<'a> {
+    e: &'a JavaEndpoint,
+    shape: Shape,
+    /// Name of the constant holding the method descriptor.
+    descriptor: String,
+    request: java::Tokens,
+    response: java::Tokens,
+}
+
+impl Generator {
+    fn method<'a>(&self, e: &'a JavaEndpoint) -> Method<'a> {
+        let request = match &e.request {
+            Some(request) => quote!($(request.channel.ty().clone().into_boxed())),
+            None => quote!(Void),
+        };
+
+        let response = match &e.response {
+            Some(response) => quote!($(response.ty().clone().into_boxed())),
+            None => quote!(Void),
+        };
+
+        Method {
+            e,
+            shape: Shape::new(e),
+            descriptor: format!("{}_METHOD", self.to_upper_snake.convert(e.ident())),
+            request,
+            response,
+        }
+    }
+
+    /// Build the method descriptor for an endpoint.
+    fn descriptor<'a>(&'a self, m: &'a Method<'a>) -> impl FormatInto<Java> + 'a {
+        let Method {
+            e,
+            shape,
+            descriptor,
+            request,
+            response,
+        } = m;
+
+        quote_fn! {
+            public static final $(&self.method_descriptor)<$request, $response> $descriptor =
+                $(&self.method_descriptor).<$request, $response>newBuilder()
+                    .setType($(&self.method_descriptor).MethodType.$(shape.method_type()))
+                    .setFullMethodName($(&self.method_descriptor).generateFullMethodName(SERVICE_NAME, $(quoted(e.name()))))
+                    .setRequestMarshaller(new JsonMarshaller<$request>(new $(&self.type_reference)<$request>() {}))
+                    .setResponseMarshaller(new JsonMarshaller<$response>(new $(&self.type_reference)<$response>() {}))
+                    .build();
+        }
+    }
+
+    /// Build the client method for an endpoint.
+    fn client_method<'a>(&'a self, m: &'a Method<'a>) -> impl FormatInto<Java> + 'a {
+        let Method {
+            e,
+            shape,
+            descriptor,
+            request,
+            response,
+        } = m;
+
+        let call = quote!(getChannel().newCall($descriptor, getCallOptions()));
+
+        quote_fn! {
+            $(java::block_comment(&e.comment))
+            $(if shape.is_client_streaming() {
+                public $(&self.stream_observer)<$request> $(e.safe_ident())(final $(&self.stream_observer)<$response> responseObserver) {
+                    return $(&self.client_calls).async$(shape.calls())($call, responseObserver);
+                }
+            } else {
+                public void $(e.safe_ident())(final $request request, final $(&self.stream_observer)<$response> responseObserver) {
+                    $(&self.client_calls).async$(shape.calls())($call, request, responseObserver);
+                }
+            })
+        }
+    }
+
+    /// Build the default, unimplemented, server method for an endpoint.
+    fn server_method<'a>(&'a self, m: &'a Method<'a>) -> impl FormatInto<Java> + 'a {
+        let Method {
+            e,
+            shape,
+            descriptor,
+            request,
+            response,
+        } = m;
+
+        quote_fn! {
+            $(java::block_comment(&e.comment))
+            $(if shape.is_client_streaming() {
+                public $(&self.stream_observer)<$request> $(e.safe_ident())(final $(&self.stream_observer)<$response> responseObserver) {
+                    return $(&self.server_calls).asyncUnimplementedStreamingCall($descriptor, responseObserver);
+                }
+            } else {
+                public void $(e.safe_ident())(final $request request, final $(&self.stream_observer)<$response> responseObserver) {
+                    $(&self.server_calls).asyncUnimplementedUnaryCall($descriptor, responseObserver);
+                }
+            })
+        }
+    }
+}
+
+impl codegen::service::Codegen for Generator {
+    fn generate(&self, args: codegen::service::Args<'_>) {
+        let codegen::service::Args { body, inner, .. } = args;
+
+        let service_name = match body.name.package.parts().next() {
+            Some(_) => format!("{}.{}", body.name.package.join("."), body.name.name),
+            None => body.name.name.to_string(),
+        };
+
+        let methods = body
+            .endpoints
+            .iter()
+            .map(|e| self.method(e))
+            .collect::<Vec<_>>();
+
+        inner.push(quote! {
+            public static class Grpc {
+                public static final String SERVICE_NAME = $(quoted(service_name));
+
+                private static final $(&self.object_mapper) MAPPER = new $(&self.object_mapper)().findAndRegisterModules();
+
+                $(for m in &methods join ($['\n']) => $(self.descriptor(m)))
+
+                public static class Stub extends $(&self.abstract_stub)<Stub> {
+                    public Stub(final $(&self.channel) channel) {
+                        super(channel);
+                    }
+
+                    private Stub(final $(&self.channel) channel, final $(&self.call_options) callOptions) {
+                        super(channel, callOptions);
+                    }
+
+                    @Override
+                    protected Stub build(final $(&self.channel) channel, final $(&self.call_options) callOptions) {
+                        return new Stub(channel, callOptions);
+                    }
+
+                    $(for m in &methods join ($['\n']) => $(self.client_method(m)))
+                }
+
+                public static abstract class ImplBase implements $(&self.bindable_service) {
+                    $(for m in &methods join ($['\n']) => $(self.server_method(m)))
+
+                    @Override
+                    public final $(&self.server_service_definition) bindService() {
+                        return $(&self.server_service_definition).builder(SERVICE_NAME)
+                            $(for m in &methods join ($['\r']) {
+                                .addMethod($(&m.descriptor), $(&self.server_calls).async$(m.shape.calls())(this::$(m.e.safe_ident())))
+                            })
+                            .build();
+                    }
+                }
+
+                private static class JsonMarshaller<T> implements $(&self.method_descriptor).Marshaller<T> {
+                    private final $(&self.type_reference)<T> type;
+
+                    private JsonMarshaller(final $(&self.type_reference)<T> type) {
+                        this.type = type;
+                    }
+
+                    @Override
+                    public $(&self.input_stream) stream(final T value) {
+                        try {
+                            return new $(&self.byte_array_input_stream)(MAPPER.writeValueAsBytes(value));
+                        } catch (final $(&self.io_exception) e) {
+                            throw $(&self.status).INTERNAL.withDescription("failed to serialize message").withCause(e).asRuntimeException();
+                        }
+                    }
+
+                    @Override
+                    public T parse(final $(&self.input_stream) stream) {
+                        try {
+                            return MAPPER.readValue(stream, this.type);
+                        } catch (final $(&self.io_exception) e) {
+                            throw $(&self.status).INTERNAL.withDescription("failed to parse message").withCause(e).asRuntimeException();
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Module;
+    use manifest::Manifest;
+    use reproto_core::errors::Result;
+    use reproto_core::{
+        CapturingFilesystem, EmptyResolver, Filesystem, Reported, RpPackage, RpVersionedPackage,
+        Source,
+    };
+    use std::any::Any;
+    use trans::Session;
+
+    fn compile(spec: &str) -> Result<()> {
+        let package = RpVersionedPackage::new(RpPackage::parse("test"), None);
+        let source = Source::bytes("test", spec.as_bytes().to_vec());
+
+        let mut reporter: Vec<Reported> = Vec::new();
+        let mut resolver = EmptyResolver;
+        let mut session = Session::new(None, &mut reporter, &mut resolver)?;
+        session.import_source(source, Some(package))?;
+
+        let modules: Vec<Box<dyn Any>> = vec![Box::new(Module::Jackson), Box::new(Module::Grpc)];
+
+        let manifest = Manifest {
+            modules: Some(modules),
+            ..Manifest::default()
+        };
+
+        let fs = CapturingFilesystem::new();
+        crate::compile(fs.filesystem().open_root(None)?.as_ref(), session, manifest)
+    }
+
+    #[test]
+    fn test_arguments_outside_of_request() {
+        let result = compile(
+            r#"
+            type Entry {}
+
+            service MyService {
+              unary(request: Entry) -> Entry;
+              get(id: u32, name: string) -> Entry;
+            }
+            "#,
+        );
+
+        let e = result.expect_err("arguments outside of the request should be rejected");
+
+        assert_eq!(
+            "grpc: endpoint `get` has arguments which are not part of the request: name",
+            e.message()
+        );
+    }
+
+    #[test]
+    fn test_request_only() -> Result<()> {
+        compile(
+            r#"
+            type Entry {}
+
+            service MyService {
+              unknown();
+              unary(request: Entry) -> Entry;
+              streaming(request: stream Entry) -> stream Entry;
+            }
+            "#,
+        )
+    }
+}
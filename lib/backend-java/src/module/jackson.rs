@@ -21,6 +21,7 @@ impl Module {
         options.gen.enum_ty.push(generator.clone());
         options.gen.interface.push(generator.clone());
         options.gen.interface_sub_type.push(generator.clone());
+        options.gen.newtype.push(generator.clone());
     }
 }
 
@@ -304,6 +305,18 @@ impl codegen::enum_ty::Codegen for Generator {
     }
 }
 
+impl codegen::newtype::Codegen for Generator {
+    fn generate(&self, args: codegen::newtype::Args<'_>) {
+        args.constructor.push(quote! {
+            @$(&self.json_creator)(mode = $(&self.json_creator).Mode.DELEGATING)
+        });
+
+        args.getter.push(quote! {
+            @$(&self.json_value)
+        });
+    }
+}
+
 impl codegen::interface::Codegen for Generator {
     fn generate(&self, args: codegen::interface::Args<'_>) {
         match args.sub_type_strategy {
@@ -1,13 +1,20 @@
 mod builder;
 mod constructor_properties;
+pub(crate) mod grpc;
 mod jackson;
 mod lombok;
 mod mutable;
+mod newtype;
 mod nullable;
+mod okhttp;
 
 pub use self::builder::Module as Builder;
 pub use self::constructor_properties::Module as ConstructorProperties;
+pub use self::grpc::Module as Grpc;
 pub use self::jackson::Module as Jackson;
 pub use self::lombok::Module as Lombok;
 pub use self::mutable::Module as Mutable;
+pub use self::newtype::Module as Newtype;
 pub use self::nullable::Module as Nullable;
+pub use self::okhttp::Config as OkHttpConfig;
+pub use self::okhttp::Module as OkHttp;
@@ -0,0 +1,9 @@
+use crate::Options;
+
+pub struct Module;
+
+impl Module {
+    pub fn initialize(self, options: &mut Options) {
+        options.newtype = true;
+    }
+}
@@ -0,0 +1,271 @@
+//! Module that generates OkHttp clients for services.
+
+use crate::codegen;
+use crate::flavored::*;
+use crate::Options;
+use genco::prelude::*;
+use serde::Deserialize;
+use std::rc::Rc;
+
+/// The HTTP protocol version that the default client is restricted to.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum Version {
+    #[serde(rename = "1")]
+    Http1,
+    #[serde(rename = "2")]
+    Http2,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// Protocol version to use, otherwise the default protocols of OkHttp are used.
+    version: Option<Version>,
+}
+
+pub struct Module {
+    config: Config,
+}
+
+impl Module {
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    pub fn initialize(self, options: &mut Options) {
+        let generator = Rc::new(Generator::new(self.config));
+        options.gen.service.push(generator);
+    }
+}
+
+pub struct Generator {
+    config: Config,
+
+    io_exception: java::Import,
+    arrays: java::Import,
+    optional: java::Import,
+    completable_future: java::Import,
+
+    ok_http_client: java::Import,
+    http_url: java::Import,
+    media_type: java::Import,
+    protocol: java::Import,
+    request: java::Import,
+    request_body: java::Import,
+    response: java::Import,
+    response_body: java::Import,
+    call: java::Import,
+    callback: java::Import,
+
+    object_mapper: java::Import,
+    type_reference: java::Import,
+}
+
+impl Generator {
+    fn new(config: Config) -> Self {
+        Self {
+            config,
+
+            io_exception: java::import("java.io", "IOException"),
+            arrays: java::import("java.util", "Arrays"),
+            optional: java::import("java.util", "Optional"),
+            completable_future: java::import("java.util.concurrent", "CompletableFuture"),
+
+            ok_http_client: java::import("okhttp3", "OkHttpClient"),
+            http_url: java::import("okhttp3", "HttpUrl"),
+            media_type: java::import("okhttp3", "MediaType"),
+            protocol: java::import("okhttp3", "Protocol"),
+            request: java::import("okhttp3", "Request"),
+            request_body: java::import("okhttp3", "RequestBody"),
+            response: java::import("okhttp3", "Response"),
+            response_body: java::import("okhttp3", "ResponseBody"),
+            call: java::import("okhttp3", "Call"),
+            callback: java::import("okhttp3", "Callback"),
+
+            object_mapper: java::import("com.fasterxml.jackson.databind", "ObjectMapper"),
+            type_reference: java::import("com.fasterxml.jackson.core.type", "TypeReference"),
+        }
+    }
+
+    /// Build the default client, restricted to the configured protocol version.
+    fn default_client(&self) -> java::Tokens {
+        let protocols = match self.config.version {
+            Some(Version::Http1) => quote!($(&self.protocol).HTTP_1_1),
+            Some(Version::Http2) => quote!($(&self.protocol).HTTP_2, $(&self.protocol).HTTP_1_1),
+            None => return quote!(new $(&self.ok_http_client)()),
+        };
+
+        quote! {
+            new $(&self.ok_http_client).Builder()
+                .protocols($(&self.arrays).asList($protocols))
+                .build()
+        }
+    }
+
+    /// Build the segments of the request path.
+    fn path_segments<'a>(&'a self, path: &'a RpPathSpec) -> impl FormatInto<Java> + 'a {
+        quote_fn! {
+            $(for step in &path.steps join ($['\r']) {
+                .addPathSegment($(if step.parts.is_empty() {
+                    ""
+                } else {
+                    $(for part in &step.parts join ( + ) {
+                        $(match part {
+                            RpPathPart::Variable(arg) => String.valueOf($(arg.safe_ident())),
+                            RpPathPart::Segment(s) => $(quoted(s.as_str())),
+                        })
+                    })
+                }))
+            })
+        }
+    }
+
+    /// Build the implementation of a single endpoint.
+    fn endpoint<'a>(&'a self, e: &'a JavaEndpoint) -> impl FormatInto<Java> + 'a {
+        quote_fn! {
+            @Override
+            public $(&self.completable_future)<$(e.response_ty())> $(e.safe_ident())($(e.arguments())) {
+                $(match &e.http1 {
+                    Some(http) => {
+                        final $(&self.http_url) url_ = this.url.newBuilder()
+                            $(self.path_segments(&http.path))
+                            .build();
+
+                        $(match (&e.request, &http.request) {
+                            (Some(request), Some(_)) => {
+                                final $(&self.request_body) body_;
+
+                                try {
+                                    body_ = $(&self.request_body).create(JSON, this.mapper.writeValueAsBytes($(request.safe_ident())));
+                                } catch (final $(&self.io_exception) e_) {
+                                    return failed(e_);
+                                }
+                            }
+                            _ => {
+                                final $(&self.request_body) body_ = null;
+                            }
+                        })
+
+                        final $(&self.request) req_ = new $(&self.request).Builder()
+                            .url(url_)
+                            .method($(quoted(http.method.as_str())), body_)
+                            .build();
+
+                        $(match &http.response {
+                            Some(ty) => $(match e.http.accept {
+                                RpAccept::Json => {
+                                    return call(req_, res_ -> this.mapper.readValue(res_.byteStream(), new $(&self.type_reference)<$(ty.clone().into_boxed())>() {}));
+                                }
+                                RpAccept::Text => {
+                                    return call(req_, res_ -> res_.string());
+                                }
+                            }),
+                            None => {
+                                return call(req_, res_ -> null);
+                            }
+                        })
+                    }
+                    None => {
+                        return failed(new UnsupportedOperationException($(quoted(format!("not supported over HTTP: {}", e.ident())))));
+                    }
+                })
+            }
+        }
+    }
+}
+
+impl codegen::service::Codegen for Generator {
+    fn generate(&self, args: codegen::service::Args<'_>) {
+        let codegen::service::Args { ident, body, inner } = args;
+
+        let endpoints = body.endpoints.iter().filter(|e| e.is_unary());
+
+        let url = match &body.http.url {
+            Some(url) => {
+                quote!($(&self.optional).of($(&self.http_url).get($(quoted(url.as_str())))))
+            }
+            None => quote!($(&self.optional).empty()),
+        };
+
+        inner.push(quote! {
+            public static class OkHttp implements $ident {
+                private static final $(&self.media_type) JSON = $(&self.media_type).get("application/json; charset=utf-8");
+
+                private final $(&self.ok_http_client) client;
+                private final $(&self.http_url) url;
+                private final $(&self.object_mapper) mapper;
+
+                public OkHttp(final $(&self.ok_http_client) client, final $(&self.http_url) url, final $(&self.object_mapper) mapper) {
+                    this.client = client;
+                    this.url = url;
+                    this.mapper = mapper;
+                }
+
+                $(for e in endpoints join ($['\n']) => $(self.endpoint(e)))
+
+                private <T> $(&self.completable_future)<T> call(final $(&self.request) request, final Decoder<T> decoder) {
+                    final $(&self.completable_future)<T> future = new $(&self.completable_future)<T>();
+
+                    this.client.newCall(request).enqueue(new $(&self.callback)() {
+                        @Override
+                        public void onFailure(final $(&self.call) call, final $(&self.io_exception) e) {
+                            future.completeExceptionally(e);
+                        }
+
+                        @Override
+                        public void onResponse(final $(&self.call) call, final $(&self.response) response) {
+                            try (final $(&self.response_body) body = response.body()) {
+                                if (!response.isSuccessful()) {
+                                    throw new $(&self.io_exception)("bad response: " + response.code());
+                                }
+
+                                future.complete(decoder.decode(body));
+                            } catch (final Exception e) {
+                                future.completeExceptionally(e);
+                            }
+                        }
+                    });
+
+                    return future;
+                }
+
+                private static <T> $(&self.completable_future)<T> failed(final Throwable e) {
+                    final $(&self.completable_future)<T> future = new $(&self.completable_future)<T>();
+                    future.completeExceptionally(e);
+                    return future;
+                }
+
+                private interface Decoder<T> {
+                    T decode(final $(&self.response_body) body) throws $(&self.io_exception);
+                }
+
+                public static class Builder {
+                    private $(&self.optional)<$(&self.ok_http_client)> client = $(&self.optional).empty();
+                    private $(&self.optional)<$(&self.http_url)> url = $url;
+                    private $(&self.optional)<$(&self.object_mapper)> mapper = $(&self.optional).empty();
+
+                    public Builder client(final $(&self.ok_http_client) client) {
+                        this.client = $(&self.optional).of(client);
+                        return this;
+                    }
+
+                    public Builder url(final $(&self.http_url) url) {
+                        this.url = $(&self.optional).of(url);
+                        return this;
+                    }
+
+                    public Builder mapper(final $(&self.object_mapper) mapper) {
+                        this.mapper = $(&self.optional).of(mapper);
+                        return this;
+                    }
+
+                    public OkHttp build() {
+                        final $(&self.ok_http_client) client = this.client.orElseGet(() -> $(self.default_client()));
+                        final $(&self.http_url) url = this.url.orElseThrow(() -> new IllegalStateException("url: is a required field"));
+                        final $(&self.object_mapper) mapper = this.mapper.orElseGet(() -> new $(&self.object_mapper)());
+                        return new OkHttp(client, url, mapper);
+                    }
+                }
+            }
+        });
+    }
+}
@@ -19,6 +19,10 @@ pub struct Options {
     pub(crate) build_equals: bool,
     /// Build a Object#toString() implementation.
     pub(crate) build_to_string: bool,
+    /// Generate aliases as newtype wrappers?
+    pub(crate) newtype: bool,
+    /// Are services exposed over gRPC?
+    pub(crate) grpc: bool,
     /// Generators used.
     pub(crate) gen: codegen::Generators,
 }
@@ -34,6 +38,8 @@ impl Options {
             build_hash_code: true,
             build_equals: true,
             build_to_string: true,
+            newtype: false,
+            grpc: false,
             gen: codegen::Generators::default(),
         }
     }
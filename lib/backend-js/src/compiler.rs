@@ -1,3 +1,4 @@
+use crate::codegen::{ServiceAdded, ServiceCodegen};
 use crate::flavored::*;
 use crate::utils::{is_defined, is_not_defined};
 use crate::{FileSpec, Options, EXT};
@@ -7,7 +8,7 @@ use genco::tokens::FormatInto;
 use naming::Naming;
 use relative_path::RelativePathBuf;
 use reproto_core::errors::Result;
-use reproto_core::{Handle, Span, Spanned};
+use reproto_core::{Handle, RpValidate, Span, Spanned};
 use std::rc::Rc;
 use trans::Translated;
 
@@ -17,12 +18,14 @@ pub(crate) struct Compiler<'a> {
     to_lower_snake: naming::ToLowerSnake,
     values: Tokens<JavaScript>,
     enum_name: Tokens<JavaScript>,
+    newtype: bool,
+    service_generators: Vec<Box<dyn ServiceCodegen>>,
 }
 
 impl<'a> Compiler<'a> {
     pub(crate) fn new(
         env: &'a Translated<JavaScriptFlavor>,
-        _: Options,
+        options: Options,
         handle: &'a dyn Handle,
     ) -> Self {
         Self {
@@ -31,6 +34,8 @@ impl<'a> Compiler<'a> {
             to_lower_snake: naming::to_lower_snake(),
             values: quote!(values),
             enum_name: quote!(name),
+            newtype: options.newtype,
+            service_generators: options.service_generators,
         }
     }
 
@@ -187,10 +192,22 @@ impl<'a> Compiler<'a> {
 
                                 if ($(is_defined(var_name))) {
                                     $(ref t => field.ty.decode(t, quote!($var_name)))
+                                    $(ref t => self.validate(t, field, var_name))
                                 } else {
                                     $var_name = null;
                                 }
                             }
+                        } else if let Some(default) = &field.default {
+                            quote_in! { *o =>
+                                let $var_name = data[$var];
+
+                                if ($(is_not_defined(var_name))) {
+                                    $var_name = $(self.literal(default));
+                                }
+
+                                $(ref t => field.ty.decode(t, quote!($var_name)))
+                                $(ref t => self.validate(t, field, var_name))
+                            }
                         } else {
                             quote_in! { *o =>
                                 let $var_name = data[$var];
@@ -200,6 +217,7 @@ impl<'a> Compiler<'a> {
                                 }
 
                                 $(ref t => field.ty.decode(t, quote!($var_name)))
+                                $(ref t => self.validate(t, field, var_name))
                             }
                         }
                     })
@@ -210,6 +228,75 @@ impl<'a> Compiler<'a> {
         }
     }
 
+    /// Build a literal value.
+    fn literal(&self, literal: &RpLiteral) -> js::Tokens {
+        match literal {
+            RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+            RpLiteral::Number(number) => quote!($(number.to_string())),
+            RpLiteral::Boolean(boolean) => quote!($(boolean.to_string())),
+        }
+    }
+
+    /// Build checks for the validation rules of the given field.
+    fn validate(&self, out: &mut js::Tokens, field: &RpField, var: &Rc<String>) {
+        let validate = match &field.validate {
+            Some(validate) => validate,
+            None => return,
+        };
+
+        match validate {
+            RpValidate::Number(validate) => {
+                if let Some(min) = &validate.min {
+                    quote_in! { *out =>
+                        $['\n']
+                        if ($var < $(min.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be greater than or equal to {}", field.ident, min))));
+                        }
+                    }
+                }
+
+                if let Some(max) = &validate.max {
+                    quote_in! { *out =>
+                        $['\n']
+                        if ($var > $(max.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be less than or equal to {}", field.ident, max))));
+                        }
+                    }
+                }
+            }
+            RpValidate::String(validate) => {
+                if let Some(pattern) = &validate.pattern {
+                    let pattern = pattern.to_string();
+
+                    quote_in! { *out =>
+                        $['\n']
+                        if (!new RegExp($(quoted(format!("^(?:{})$", pattern)))).test($var)) {
+                            throw new Error($(quoted(format!("{}: must match pattern `{}`", field.ident, pattern))));
+                        }
+                    }
+                }
+
+                if let Some(min_length) = validate.min_length {
+                    quote_in! { *out =>
+                        $['\n']
+                        if (Array.from($var).length < $(min_length.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be at least {} characters long", field.ident, min_length))));
+                        }
+                    }
+                }
+
+                if let Some(max_length) = validate.max_length {
+                    quote_in! { *out =>
+                        $['\n']
+                        if (Array.from($var).length > $(max_length.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be at most {} characters long", field.ident, max_length))));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     fn field_by_name<'o>(
         _i: usize,
         field: &'o Spanned<RpField>,
@@ -486,4 +573,58 @@ impl<'a> PackageProcessor<'a, JavaScriptFlavor> for Compiler<'a> {
             }
         }
     }
+
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody) -> Result<()> {
+        quote_in! { out.0 =>
+            export const $(&body.name) = $(self.literal(&body.value));
+        }
+
+        Ok(())
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody) -> Result<()> {
+        if !self.newtype {
+            return Ok(());
+        }
+
+        let mut field = RpField::new("value", body.ty.clone());
+        field.validate = body.validate.clone();
+        let field = &Spanned::new(field, Span::empty());
+        let var_name = &Rc::new(String::from("v_value"));
+        let access = &quote!(this.value);
+
+        quote_in! { out.0 =>
+            export class $(&body.name) {
+                $(ref o => self.build_constructor(o, [field]))
+
+                static decode(data) {
+                    let $var_name = data;
+
+                    $(ref t => field.ty.decode(t, quote!($var_name)))
+                    $(ref t => self.validate(t, field, var_name))
+
+                    return new $(&body.name)($var_name);
+                }
+
+                encode() {
+                    $(ref o => self.throw_if_null(o, access, field))
+
+                    return $(field.ty.encode(access.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_service(&self, out: &mut Self::Out, body: &RpServiceBody) -> Result<()> {
+        for g in &self.service_generators {
+            g.generate(ServiceAdded {
+                body,
+                type_body: &mut out.0,
+            })?;
+        }
+
+        Ok(())
+    }
 }
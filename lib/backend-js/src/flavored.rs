@@ -9,9 +9,24 @@ use reproto_core::{
     self, CoreFlavor, Diagnostics, Flavor, FlavorTranslator, PackageTranslator, RpNumberType,
     RpStringType, Spanned, Translate, Translator,
 };
+use std::ops::Deref;
 use std::rc::Rc;
 use trans::Packages;
 
+#[derive(Debug, Clone)]
+pub(crate) struct JsEndpoint {
+    pub(crate) endpoint: RpEndpoint,
+    pub(crate) http1: Option<RpEndpointHttp1>,
+}
+
+impl Deref for JsEndpoint {
+    type Target = RpEndpoint;
+
+    fn deref(&self) -> &Self::Target {
+        &self.endpoint
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Type {
     Integer,
@@ -179,7 +194,7 @@ impl Flavor for JavaScriptFlavor {
     type Type = Type;
     type Name = Name;
     type Field = RpField;
-    type Endpoint = RpEndpoint;
+    type Endpoint = JsEndpoint;
     type Package = RpPackage;
     type EnumType = Type;
 }
@@ -199,7 +214,7 @@ impl FlavorTranslator for JavaScriptFlavorTranslator {
     type Source = CoreFlavor;
     type Target = JavaScriptFlavor;
 
-    reproto_core::translator_defaults!(Self, field, endpoint);
+    reproto_core::translator_defaults!(Self, field);
 
     fn translate_number(&self, _: RpNumberType) -> Result<Type> {
         Ok(Type::Integer)
@@ -262,6 +277,21 @@ impl FlavorTranslator for JavaScriptFlavorTranslator {
         })
     }
 
+    fn translate_endpoint<T>(
+        &self,
+        translator: &T,
+        diag: &mut Diagnostics,
+        endpoint: RpEndpoint<CoreFlavor>,
+    ) -> Result<JsEndpoint>
+    where
+        T: Translator<Source = CoreFlavor, Target = JavaScriptFlavor>,
+    {
+        let endpoint = endpoint.translate(diag, translator)?;
+        let http1 = RpEndpointHttp1::from_endpoint(&endpoint);
+
+        Ok(JsEndpoint { endpoint, http1 })
+    }
+
     fn translate_package(&self, source: RpVersionedPackage) -> Result<RpPackage> {
         Ok(self.packages.translate_package(source)?)
     }
@@ -1,8 +1,12 @@
+mod codegen;
 mod compiler;
 mod flavored;
+pub mod module;
 mod utils;
 
+use crate::codegen::ServiceCodegen;
 use crate::compiler::Compiler;
+use backend::Initializer;
 use genco::prelude::*;
 use manifest::{Lang, Manifest, NoModule, TryFromToml};
 use reproto_core::errors::Result;
@@ -98,21 +102,43 @@ impl Lang for JsLang {
 }
 
 #[derive(Debug)]
-pub enum JsModule {}
+pub(crate) enum JsModule {
+    Fetch(module::FetchConfig),
+    Newtype,
+}
 
 impl TryFromToml for JsModule {
     fn try_from_string(path: &Path, id: &str, value: String) -> Result<Self> {
-        NoModule::illegal(path, id, value)
+        use self::JsModule::*;
+
+        let result = match id {
+            "fetch" => Fetch(module::FetchConfig::default()),
+            "newtype" => Newtype,
+            _ => return NoModule::illegal(path, id, value),
+        };
+
+        Ok(result)
     }
 
     fn try_from_value(path: &Path, id: &str, value: toml::Value) -> Result<Self> {
-        NoModule::illegal(path, id, value)
+        use self::JsModule::*;
+
+        let result = match id {
+            "fetch" => Fetch(value.try_into()?),
+            "newtype" => Newtype,
+            _ => return NoModule::illegal(path, id, value),
+        };
+
+        Ok(result)
     }
 }
 
 pub struct Options {
     pub build_getters: bool,
     pub build_constructor: bool,
+    /// Generate aliases as wrapper classes.
+    pub newtype: bool,
+    pub(crate) service_generators: Vec<Box<dyn ServiceCodegen>>,
 }
 
 impl Options {
@@ -120,10 +146,29 @@ impl Options {
         Options {
             build_getters: false,
             build_constructor: true,
+            newtype: false,
+            service_generators: Vec::new(),
         }
     }
 }
 
+pub(crate) fn setup_options(modules: Vec<JsModule>) -> Result<Options> {
+    use self::JsModule::*;
+
+    let mut options = Options::new();
+
+    for module in modules {
+        let initializer: Box<dyn Initializer<Options = Options>> = match module {
+            Fetch(config) => Box::new(module::Fetch::new(config)),
+            Newtype => Box::new(module::Newtype::new()),
+        };
+
+        initializer.initialize(&mut options)?;
+    }
+
+    Ok(options)
+}
+
 pub struct FileSpec(pub Tokens<JavaScript>);
 
 impl Default for FileSpec {
@@ -135,10 +180,12 @@ impl Default for FileSpec {
 fn compile(handle: &dyn Handle, env: Session<CoreFlavor>, manifest: Manifest) -> Result<()> {
     let packages = env.packages()?;
 
-    let env = env.translate(flavored::JavaScriptFlavorTranslator::new(packages))?;
+    let modules: Vec<JsModule> = manifest::checked_modules(manifest.modules)?;
+    let options = setup_options(modules)?;
 
-    let _modules: Vec<JsModule> = manifest::checked_modules(manifest.modules)?;
-    let options = Options::new();
+    let env = env
+        .with_newtypes(options.newtype)
+        .translate(flavored::JavaScriptFlavorTranslator::new(packages))?;
 
     Compiler::new(&env, options, handle).compile()
 }
@@ -0,0 +1,139 @@
+//! Module that generates fetch-based clients for services.
+
+use crate::codegen::{ServiceAdded, ServiceCodegen};
+use crate::flavored::*;
+use crate::Options;
+use backend::Initializer;
+use genco::prelude::*;
+use reproto_core::errors::Result;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {}
+
+pub(crate) struct Module {
+    #[allow(dead_code)]
+    config: Config,
+}
+
+impl Module {
+    pub(crate) fn new(config: Config) -> Module {
+        Module { config }
+    }
+}
+
+struct FetchServiceCodegen;
+
+impl FetchServiceCodegen {
+    /// Build the url of an HTTP endpoint, relative to the base url of the client.
+    fn url(&self, path: &RpPathSpec) -> js::Tokens {
+        let mut parts = vec![quote!(this.url)];
+        let mut segment = String::new();
+
+        for step in &path.steps {
+            segment.push('/');
+
+            for part in &step.parts {
+                match part {
+                    RpPathPart::Segment(s) => segment.push_str(s),
+                    RpPathPart::Variable(arg) => {
+                        parts.push(quote!($(quoted(std::mem::take(&mut segment)))));
+                        parts.push(quote!(encodeURIComponent(String($(arg.safe_ident())))));
+                    }
+                }
+            }
+        }
+
+        if !segment.is_empty() {
+            parts.push(quote!($(quoted(segment))));
+        }
+
+        quote!($(for p in parts join ( + ) => $p))
+    }
+
+    /// Build the client method calling a single HTTP endpoint.
+    fn endpoint(&self, t: &mut js::Tokens, e: &JsEndpoint, http: &RpEndpointHttp1) {
+        let body = e
+            .request
+            .as_ref()
+            .map(|req| req.channel.ty().encode(quote!($(req.safe_ident()))));
+
+        quote_in! { *t =>
+            async $(e.safe_ident())($(for a in &e.arguments join (, ) => $(a.safe_ident()))) {
+                const url_ = $(self.url(&http.path));
+
+                const res_ = await fetch(url_, {
+                    method: $(quoted(http.method.as_str())),
+                    $(if let Some(body) = body {
+                        headers: {"Content-Type": "application/json"},
+                        body: JSON.stringify($body),
+                    })
+                });
+
+                if (!res_.ok) {
+                    throw new Error("request failed: " + res_.status + " " + res_.statusText);
+                }
+                $(if let Some(ty) = &http.response =>
+                    $(match e.http.accept {
+                        RpAccept::Json => {
+                            $['\n']
+                            let data_ = await res_.json();
+                            $['\n']
+                            $(ref t => ty.decode(t, quote!(data_)))
+                            $['\n']
+                            return data_;
+                        }
+                        RpAccept::Text => {
+                            $['\n']
+                            return await res_.text();
+                        }
+                    })
+                )
+            }
+        }
+    }
+}
+
+impl ServiceCodegen for FetchServiceCodegen {
+    fn generate(&self, ServiceAdded { body, type_body }: ServiceAdded) -> Result<()> {
+        let endpoints = body
+            .endpoints
+            .iter()
+            .flat_map(|e| e.http1.as_ref().map(|http| (e, http)))
+            .collect::<Vec<_>>();
+
+        quote_in! { *type_body =>
+            export class $(&body.name)_Fetch {
+                constructor(url) {
+                    if (url === undefined) {
+                        $(if let Some(url) = &body.http.url {
+                            url = $(quoted(url.as_str()));
+                        } else {
+                            throw new Error("missing `url` argument");
+                        })
+                    }
+
+                    this.url = url;
+                }
+
+                $(for (e, http) in endpoints join ($['\n']) {
+                    $(ref t => self.endpoint(t, e, http))
+                })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Initializer for Module {
+    type Options = Options;
+
+    fn initialize(&self, options: &mut Options) -> Result<()> {
+        options
+            .service_generators
+            .push(Box::new(FetchServiceCodegen));
+
+        Ok(())
+    }
+}
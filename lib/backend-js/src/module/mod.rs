@@ -0,0 +1,5 @@
+mod fetch;
+mod newtype;
+
+pub(crate) use self::fetch::{Config as FetchConfig, Module as Fetch};
+pub(crate) use self::newtype::Module as Newtype;
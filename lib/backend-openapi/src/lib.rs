@@ -22,7 +22,8 @@ use manifest::{checked_modules, Lang, Manifest, NoModule, TryFromToml};
 use reproto_core::errors::Result;
 use reproto_core::flavored::*;
 use reproto_core::{
-    CoreFlavor, Handle, RelativePath, RelativePathBuf, RpHttpMethod, RpNumberKind, Spanned,
+    CoreFlavor, Handle, RelativePath, RelativePathBuf, RpHttpMethod, RpNumberKind, RpValidate,
+    Spanned,
 };
 use std::any::Any;
 use std::cell::RefCell;
@@ -685,6 +686,10 @@ impl<'builder> SpecBuilder<'builder> {
                 let ref_ = self.name_to_ref(&self.any_type)?;
                 spec::Schema::from(Ref(format!("#/components/schemas/{}", ref_)))
             }
+            // NB: generic declarations are instantiated before they reach backends.
+            RpType::Generic { .. } | RpType::Parameter { .. } => {
+                return Err(format!("`{}` has not been instantiated", ty).into());
+            }
         };
 
         Ok(out)
@@ -700,7 +705,8 @@ impl<'builder> SpecBuilder<'builder> {
         for field in fields {
             let mut schema = self.type_to_schema(queue, &field.ty)?;
 
-            if field.is_required() {
+            // NB: fields with a default value may be absent.
+            if field.is_required() && field.default.is_none() {
                 object.required.push(field.safe_ident());
             }
 
@@ -712,6 +718,13 @@ impl<'builder> SpecBuilder<'builder> {
                 schema.description = Some(field.comment.join("\n"));
             }
 
+            if let Some(RpValidate::Number(validate)) = &field.validate {
+                schema.minimum = validate.min.as_ref().map(Bound::from);
+                schema.maximum = validate.max.as_ref().map(Bound::from);
+            }
+
+            schema.default = field.default.as_ref().map(DefaultValue::from);
+
             object.properties.insert(field.safe_ident(), schema);
 
             // reference to external type, so add to queue.
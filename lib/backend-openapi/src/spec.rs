@@ -1,5 +1,5 @@
 use linked_hash_map::LinkedHashMap;
-use reproto_core::Version;
+use reproto_core::{RpLiteral, RpNumber, Version};
 use serde::Serialize;
 use std::collections::BTreeMap;
 
@@ -218,6 +218,44 @@ impl<'a> Enum<'a> {
     }
 }
 
+/// A numeric bound, like `minimum` or `maximum`.
+///
+/// Whole numbers are kept as integers so that they are not rendered with a decimal point.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged)]
+pub enum Bound {
+    Integer(i64),
+    Float(f64),
+}
+
+impl<'a> From<&'a RpNumber> for Bound {
+    fn from(number: &'a RpNumber) -> Self {
+        match number.to_i64() {
+            Some(n) if number.decimal == 0 => Bound::Integer(n),
+            _ => Bound::Float(number.to_f64().unwrap_or_default()),
+        }
+    }
+}
+
+/// Default value of a property.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged)]
+pub enum DefaultValue<'a> {
+    String(&'a str),
+    Number(Bound),
+    Boolean(bool),
+}
+
+impl<'a> From<&'a RpLiteral> for DefaultValue<'a> {
+    fn from(default: &'a RpLiteral) -> Self {
+        match default {
+            RpLiteral::String(string) => DefaultValue::String(string),
+            RpLiteral::Number(number) => DefaultValue::Number(Bound::from(number)),
+            RpLiteral::Boolean(boolean) => DefaultValue::Boolean(*boolean),
+        }
+    }
+}
+
 #[derive(Default, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Info<'a> {
@@ -302,6 +340,18 @@ pub struct Schema<'a> {
     #[serde(rename = "enum", skip_serializing_if = "Enum::is_empty")]
     pub enum_: Enum<'a>,
 
+    /// Inclusive lower bound of numeric values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Bound>,
+
+    /// Inclusive upper bound of numeric values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Bound>,
+
+    /// Value used when the property is absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<DefaultValue<'a>>,
+
     /// `oneOf` field
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub one_of: Vec<Schema<'a>>,
@@ -2,13 +2,13 @@
 
 use crate::codegen::{ServiceAdded, ServiceCodegen};
 use crate::flavored::*;
-use crate::utils::BlockComment;
+use crate::utils::{BlockComment, Warn};
 use crate::{Options, EXT, INIT_PY};
 use backend::PackageProcessor;
 use genco::prelude::*;
 use naming::{self, Naming};
 use reproto_core::errors::Result;
-use reproto_core::{Handle, RelativePathBuf, Spanned};
+use reproto_core::{Handle, RelativePathBuf, RpValidate, Span, Spanned};
 use std::collections::BTreeMap;
 use std::slice;
 use trans::{self, Translated};
@@ -18,6 +18,8 @@ pub(crate) struct Compiler<'a> {
     variant_field: Spanned<RpField>,
     to_lower_snake: naming::ToLowerSnake,
     enum_enum: python::Import,
+    re: python::ImportModule,
+    newtype: bool,
     service_generators: Vec<Box<dyn ServiceCodegen>>,
     handle: &'a dyn Handle,
 }
@@ -34,6 +36,8 @@ impl<'a> Compiler<'a> {
             variant_field,
             to_lower_snake: naming::to_lower_snake(),
             enum_enum: python::import("enum", "Enum").qualified(),
+            re: python::import_module("re"),
+            newtype: options.newtype,
             service_generators: options.service_generators,
             handle,
         }
@@ -190,6 +194,14 @@ impl<'a> Compiler<'a> {
                                             $d
                                     })
                             }
+                        } else if let Some(default) = &field.default {
+                            quote_in! { *t =>
+                                $n = data.get($var, $(self.literal(&field.ty, default)))
+
+                                $(if let Some(d) = field.ty.decode(n.clone(), 0) {
+                                    $d
+                                })
+                            }
                         } else {
                             quote_in! { *t =>
                                 $n = data[$(variable_fn(i, field))]
@@ -208,12 +220,91 @@ impl<'a> Compiler<'a> {
         }
     }
 
-    fn build_constructor(&self, t: &mut python::Tokens, fields: &[Spanned<RpField>]) {
+    /// Build a literal of the given type.
+    fn literal(&self, ty: &Type, literal: &RpLiteral) -> python::Tokens {
+        match literal {
+            RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+            // NB: whole numbers need a decimal point to be decoded as floats.
+            RpLiteral::Number(number) if matches!(ty, Type::Float) && number.decimal == 0 => {
+                quote!($(format!("{}.0", number)))
+            }
+            RpLiteral::Number(number) => quote!($(number.to_string())),
+            RpLiteral::Boolean(true) => quote!(True),
+            RpLiteral::Boolean(false) => quote!(False),
+        }
+    }
+
+    /// Build checks for the validation rules of the given field, if it has any.
+    fn validate(&self, field: &RpField, var: &str) -> Option<python::Tokens> {
+        let mut checks = Vec::new();
+
+        match field.validate.as_ref()? {
+            RpValidate::Number(validate) => {
+                if let Some(min) = &validate.min {
+                    checks.push(quote! {
+                        if $var < $(min.to_string()):
+                            raise Exception($(quoted(format!("{}: must be greater than or equal to {}", field.ident, min))))
+                    });
+                }
+
+                if let Some(max) = &validate.max {
+                    checks.push(quote! {
+                        if $var > $(max.to_string()):
+                            raise Exception($(quoted(format!("{}: must be less than or equal to {}", field.ident, max))))
+                    });
+                }
+            }
+            RpValidate::String(validate) => {
+                if let Some(pattern) = &validate.pattern {
+                    let pattern = pattern.to_string();
+
+                    checks.push(quote! {
+                        if not $(&self.re).match($(quoted(format!("(?:{})\\Z", pattern))), $var):
+                            raise Exception($(quoted(format!("{}: must match pattern `{}`", field.ident, pattern))))
+                    });
+                }
+
+                if let Some(min_length) = validate.min_length {
+                    checks.push(quote! {
+                        if len($var) < $(min_length.to_string()):
+                            raise Exception($(quoted(format!("{}: must be at least {} characters long", field.ident, min_length))))
+                    });
+                }
+
+                if let Some(max_length) = validate.max_length {
+                    checks.push(quote! {
+                        if len($var) > $(max_length.to_string()):
+                            raise Exception($(quoted(format!("{}: must be at most {} characters long", field.ident, max_length))))
+                    });
+                }
+            }
+        }
+
+        Some(quote!($(for c in checks join ($['\n']) => $c)))
+    }
+
+    fn build_constructor(
+        &self,
+        t: &mut python::Tokens,
+        ident: &str,
+        deprecated: Option<&RpDeprecated>,
+        fields: &[Spanned<RpField>],
+    ) {
+        let validate = fields
+            .iter()
+            .filter_map(|f| self.validate(f, f.safe_ident()).map(|v| (f, v)));
+
         quote_in! { *t =>
             def __init__(self$(for f in fields => , $(f.safe_ident()))):
+                $(Warn { what: ident, deprecated })
                 $(if fields.is_empty() {
                     pass
                 } else {
+                    $(for (f, v) in validate join ($['\n']) {
+                        if $(f.safe_ident()) is not None:
+                            $v
+                    })
+
                     $(for f in fields join ($['\r']) {
                         self.__$(&f.ident) = $(f.safe_ident())
                     })
@@ -239,6 +330,7 @@ impl<'a> Compiler<'a> {
 
                         @$name.setter
                         def $name(self, $var):
+                            $(Warn { what: &field.ident, deprecated: field.deprecated.as_ref() })
                             self.__$(&field.ident) = $var
                     }
                 })
@@ -293,7 +385,7 @@ impl<'a> PackageProcessor<'a, PythonFlavor> for Compiler<'a> {
     fn process_tuple(&self, out: &mut Self::Out, body: &RpTupleBody) -> Result<()> {
         quote_in! { *out =>
             class $(&body.name):
-                $(ref t => self.build_constructor(t, &body.fields))
+                $(ref t => self.build_constructor(t, &body.ident, body.deprecated.as_ref(), &body.fields))
 
                 $(ref t => self.build_accessors(t, &body.fields))
 
@@ -314,7 +406,7 @@ impl<'a> PackageProcessor<'a, PythonFlavor> for Compiler<'a> {
     fn process_enum(&self, out: &mut Self::Out, body: &RpEnumBody) -> Result<()> {
         quote_in! { *out =>
             class $(&body.name):
-                $(ref t => self.build_constructor(t, slice::from_ref(&self.variant_field)))
+                $(ref t => self.build_constructor(t, &body.ident, None, slice::from_ref(&self.variant_field)))
 
                 $(ref t => self.build_accessors(t, slice::from_ref(&self.variant_field)))
 
@@ -354,7 +446,7 @@ impl<'a> PackageProcessor<'a, PythonFlavor> for Compiler<'a> {
     fn process_type(&self, out: &mut Self::Out, body: &RpTypeBody) -> Result<()> {
         quote_in! { *out =>
             class $(&body.name):
-                $(ref t => self.build_constructor(t, &body.fields))
+                $(ref t => self.build_constructor(t, &body.ident, body.deprecated.as_ref(), &body.fields))
 
                 $(ref t => self.build_accessors(t, &body.fields))
 
@@ -403,7 +495,7 @@ impl<'a> PackageProcessor<'a, PythonFlavor> for Compiler<'a> {
                         class $(&sub_type.name)($(&body.name)):
                             TYPE = $(quoted(sub_type.name()))
 
-                            $(ref t => self.build_constructor(t, &fields))
+                            $(ref t => self.build_constructor(t, &sub_type.ident, sub_type.deprecated.as_ref(), &fields))
 
                             $(ref t => self.build_accessors(t, &fields))
 
@@ -499,6 +591,55 @@ impl<'a> PackageProcessor<'a, PythonFlavor> for Compiler<'a> {
         }
     }
 
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody) -> Result<()> {
+        quote_in! { *out =>
+            $(&body.name) = $(self.literal(&body.ty, &body.value))
+            $(BlockComment(&body.comment))
+        }
+
+        Ok(())
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody) -> Result<()> {
+        if !self.newtype {
+            return Ok(());
+        }
+
+        let mut field = RpField::new("value", body.ty.clone());
+        field.validate = body.validate.clone();
+        let fields = &[Spanned::new(field, Span::empty())];
+        let field = &fields[0];
+        let n = "f_value";
+
+        quote_in! { *out =>
+            class $(&body.name):
+                $(BlockComment(&body.comment))
+
+                $(ref t => self.build_constructor(t, &body.ident, body.deprecated.as_ref(), fields))
+
+                $(ref t => self.build_accessors(t, fields))
+
+                @staticmethod
+                def decode(data):
+                    $n = data
+
+                    $(if let Some(d) = field.ty.decode(n, 0) {
+                        $d
+                    })
+                    return $(&body.name)($n)
+
+                def encode(self):
+                    if self.$(field.safe_ident()) is None:
+                        raise Exception("missing required field: value")
+
+                    return $(field.ty.encode(quote!(self.$(field.safe_ident()))))
+
+                $(ref t => self.repr_method(t, &body.name, fields))
+        }
+
+        Ok(())
+    }
+
     fn process_service(&self, out: &mut Self::Out, body: &RpServiceBody) -> Result<()> {
         for g in &self.service_generators {
             g.generate(ServiceAdded {
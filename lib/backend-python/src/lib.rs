@@ -75,7 +75,9 @@ impl Lang for PythonLang {
 #[derive(Debug)]
 pub(crate) enum PythonModule {
     Requests(module::RequestsConfig),
+    Flask(module::FlaskConfig),
     Python2(module::Python2Config),
+    Newtype,
 }
 
 impl TryFromToml for PythonModule {
@@ -84,7 +86,9 @@ impl TryFromToml for PythonModule {
 
         let result = match id {
             "requests" => Requests(module::RequestsConfig::default()),
+            "flask" => Flask(module::FlaskConfig::default()),
             "python2" => Python2(module::Python2Config::default()),
+            "newtype" => Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -96,7 +100,9 @@ impl TryFromToml for PythonModule {
 
         let result = match id {
             "requests" => Requests(value.try_into()?),
+            "flask" => Flask(value.try_into()?),
             "python2" => Python2(value.try_into()?),
+            "newtype" => Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -107,6 +113,8 @@ impl TryFromToml for PythonModule {
 pub(crate) struct Options {
     pub(crate) service_generators: Vec<Box<dyn ServiceCodegen>>,
     pub(crate) version_helper: Rc<dyn VersionHelper>,
+    /// Generate aliases as wrapper classes.
+    pub(crate) newtype: bool,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -123,6 +131,7 @@ impl Options {
         Options {
             service_generators: Vec::new(),
             version_helper: Rc::new(Python3VersionHelper {}),
+            newtype: false,
         }
     }
 }
@@ -135,7 +144,9 @@ pub(crate) fn setup_options(modules: Vec<PythonModule>) -> Result<Options> {
     for module in modules {
         let initializer: Box<dyn Initializer<Options = Options>> = match module {
             Requests(config) => Box::new(module::Requests::new(config)),
+            Flask(config) => Box::new(module::Flask::new(config)),
             Python2(config) => Box::new(module::Python2::new(config)),
+            Newtype => Box::new(module::Newtype::new()),
         };
 
         initializer.initialize(&mut options)?;
@@ -151,10 +162,13 @@ fn compile(handle: &dyn Handle, session: Session<CoreFlavor>, manifest: Manifest
     let packages = session.packages()?;
 
     let helper = options.version_helper.clone();
-    let session = session.translate(flavored::PythonFlavorTranslator::new(
-        packages,
-        helper.clone(),
-    ))?;
+    let session =
+        session
+            .with_newtypes(options.newtype)
+            .translate(flavored::PythonFlavorTranslator::new(
+                packages,
+                helper.clone(),
+            ))?;
 
     let variant_field = Spanned::new(
         RpField::new(
@@ -0,0 +1,269 @@
+//! Module that generates service classes and Flask blueprints routing requests to them.
+
+use crate::codegen::{ServiceAdded, ServiceCodegen};
+use crate::flavored::*;
+use crate::utils::BlockComment;
+use crate::Options;
+use backend::Initializer;
+use genco::prelude::*;
+use reproto_core::errors::Result;
+use serde::Deserialize;
+
+/// Identifiers used by the generated views, which arguments must not shadow.
+const RESERVED: &[&str] = &["blueprint", "body", "flask", "json", "result", "service"];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {}
+
+pub(crate) struct Module {
+    #[allow(dead_code)]
+    config: Config,
+}
+
+impl Module {
+    pub(crate) fn new(config: Config) -> Module {
+        Module { config }
+    }
+}
+
+struct FlaskServiceCodegen {
+    flask: python::ImportModule,
+    json: python::ImportModule,
+}
+
+impl FlaskServiceCodegen {
+    pub fn new() -> FlaskServiceCodegen {
+        Self {
+            flask: python::import_module("flask"),
+            json: python::import_module("json"),
+        }
+    }
+
+    /// Name of the local variable for an argument.
+    ///
+    /// Names which are used by the generated views are suffixed with an underscore.
+    fn var(&self, arg: &RpEndpointArgument) -> String {
+        let var = arg.safe_ident();
+
+        if RESERVED.contains(&var) {
+            return format!("{}_", var);
+        }
+
+        var.to_string()
+    }
+
+    /// Build the Flask rule corresponding to the path of an endpoint.
+    fn rule(&self, path: &RpPathSpec) -> String {
+        let mut rule = String::new();
+
+        for step in &path.steps {
+            rule.push('/');
+
+            for part in &step.parts {
+                match part {
+                    RpPathPart::Segment(s) => rule.push_str(s),
+                    RpPathPart::Variable(arg) => {
+                        rule.push('<');
+                        rule.push_str(&self.var(arg));
+                        rule.push('>');
+                    }
+                }
+            }
+        }
+
+        if rule.is_empty() {
+            rule.push('/');
+        }
+
+        rule
+    }
+
+    /// Decode a path variable, which is captured as a string.
+    fn path_decode(&self, arg: &RpEndpointArgument) -> Option<Tokens<Python>> {
+        let var = &self.var(arg);
+
+        match arg.channel.ty() {
+            Type::Integer => Some(quote!($var = int($var))),
+            Type::Float => Some(quote!($var = float($var))),
+            Type::Boolean => Some(quote! {
+                if $var not in ("true", "false"):
+                    raise Exception("not a boolean")
+
+                $var = $var == "true"
+            }),
+            ty @ Type::Local { .. } | ty @ Type::Name { .. } => ty.decode(var, 0),
+            _ => None,
+        }
+    }
+
+    /// Build the view function handling a single endpoint.
+    fn view(&self, t: &mut Tokens<Python>, e: &RpEndpoint, http: &RpEndpointHttp1) {
+        let variables = http
+            .path
+            .steps
+            .iter()
+            .flat_map(|s| &s.parts)
+            .flat_map(|p| match p {
+                RpPathPart::Variable(arg) => Some(arg),
+                RpPathPart::Segment(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut decode = Vec::new();
+
+        for arg in &variables {
+            decode.extend(self.path_decode(arg));
+        }
+
+        if let Some(req) = &e.request {
+            let var = &self.var(req);
+
+            decode.push(quote! {
+                $var = $(&self.flask).request.get_json(force=True)
+                $(if let Some(d) = req.channel.ty().decode(var, 0) {
+                    $d
+                })
+            });
+        }
+
+        let call =
+            quote!(service.$(e.safe_ident())($(for a in &e.arguments join (, ) => $(self.var(a)))));
+
+        quote_in! { *t =>
+            def $(e.safe_ident())_view($(for a in &variables join (, ) => $(self.var(a)))):
+                $(if !decode.is_empty() {
+                    try:
+                        $(for d in decode join ($['\n']) => $d)
+                    except Exception:
+                        $(&self.flask).abort(400)
+                    $['\n']
+                })
+                $(match (&http.response, &e.http.accept) {
+                    (Some(ty), RpAccept::Json) => {
+                        result = $call
+                        return $(&self.flask).Response($(&self.json).dumps($(ty.encode(quote!(result)))), mimetype="application/json")
+                    }
+                    (Some(_), RpAccept::Text) => {
+                        result = $call
+                        return $(&self.flask).Response(result, mimetype="text/plain")
+                    }
+                    (None, _) => {
+                        $call
+                        return ""
+                    }
+                })
+        }
+    }
+}
+
+impl ServiceCodegen for FlaskServiceCodegen {
+    fn generate(
+        &self,
+        ServiceAdded {
+            body, type_body, ..
+        }: ServiceAdded,
+    ) -> Result<()> {
+        let endpoints = body
+            .endpoints
+            .iter()
+            .flat_map(|e| RpEndpointHttp1::from_endpoint(e).map(|http| (e, http)))
+            .collect::<Vec<_>>();
+
+        quote_in! { *type_body =>
+            class $(&body.name):
+                $(BlockComment(&body.comment))
+                $(if endpoints.is_empty() {
+                    pass
+                })
+                $(for (e, _) in &endpoints join ($['\n']) {
+                    def $(e.safe_ident())(self$(for a in &e.arguments => , $(a.safe_ident()))):
+                        $(BlockComment(&e.comment))
+                        raise NotImplementedError()
+                })
+
+            def $(&body.name)_Flask(service, name=$(quoted(body.ident.as_str())), import_name=__name__, **kw):
+                blueprint = $(&self.flask).Blueprint(name, import_name, **kw)
+
+                $(for (e, http) in &endpoints join ($['\n']) {
+                    $(ref t => self.view(t, e, http))
+
+                    blueprint.add_url_rule($(quoted(self.rule(&http.path))), $(quoted(e.safe_ident())), $(e.safe_ident())_view, methods=[$(quoted(http.method.as_str()))])
+                })
+
+                return blueprint
+        }
+
+        Ok(())
+    }
+}
+
+impl Initializer for Module {
+    type Options = Options;
+
+    fn initialize(&self, options: &mut Options) -> Result<()> {
+        options
+            .service_generators
+            .push(Box::new(FlaskServiceCodegen::new()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PythonModule;
+    use manifest::Manifest;
+    use reproto_core::errors::Result;
+    use reproto_core::{
+        CapturingFilesystem, EmptyResolver, Filesystem, RelativePath, Reported, RpPackage,
+        RpVersionedPackage, Source,
+    };
+    use std::any::Any;
+    use trans::Session;
+
+    const SPEC: &str = r#"
+type Entry {
+  value?: string;
+}
+
+service MyService {
+  #[http(path = "/get/{blueprint}/{result}/{json}")]
+  get(blueprint: string, result: string, json: string) -> Entry;
+
+  #[http(path = "/post/{flask}", method = "POST")]
+  post(body: Entry, flask: string) -> Entry;
+}
+"#;
+
+    #[test]
+    fn test_reserved_arguments() -> Result<()> {
+        let package = RpVersionedPackage::new(RpPackage::parse("test"), None);
+        let source = Source::bytes("test", SPEC.as_bytes().to_vec());
+
+        let mut reporter: Vec<Reported> = Vec::new();
+        let mut resolver = EmptyResolver;
+        let mut session = Session::new(None, &mut reporter, &mut resolver)?;
+        session.import_source(source, Some(package))?;
+
+        let modules: Vec<Box<dyn Any>> = vec![Box::new(PythonModule::Flask(Default::default()))];
+
+        let manifest = Manifest {
+            modules: Some(modules),
+            ..Manifest::default()
+        };
+
+        let fs = CapturingFilesystem::new();
+        crate::compile(fs.filesystem().open_root(None)?.as_ref(), session, manifest)?;
+
+        let files = fs.files().borrow();
+        let output = String::from_utf8(files[RelativePath::new("test.py")].clone()).unwrap();
+
+        assert!(output.contains("def get_view(blueprint_, result_, json_):"));
+        assert!(output.contains("result = service.get(blueprint_, result_, json_)"));
+        assert!(output.contains(r#""/get/<blueprint_>/<result_>/<json_>""#));
+        assert!(output.contains("def post_view(flask_):"));
+        assert!(output.contains("body_ = flask.request.get_json(force=True)"));
+        assert!(output.contains("result = service.post(body_, flask_)"));
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
+mod flask;
+mod newtype;
 mod python2;
 mod requests;
 
+pub(crate) use self::flask::{Config as FlaskConfig, Module as Flask};
+pub(crate) use self::newtype::Module as Newtype;
 pub(crate) use self::python2::{Config as Python2Config, Module as Python2};
 pub(crate) use self::requests::{Config as RequestsConfig, Module as Requests};
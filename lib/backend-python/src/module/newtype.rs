@@ -0,0 +1,22 @@
+//! Module that generates aliases as wrapper classes.
+
+use crate::Options;
+use backend::Initializer;
+use reproto_core::errors::Result;
+
+pub(crate) struct Module;
+
+impl Module {
+    pub(crate) fn new() -> Module {
+        Module
+    }
+}
+
+impl Initializer for Module {
+    type Options = Options;
+
+    fn initialize(&self, options: &mut Options) -> Result<()> {
+        options.newtype = true;
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::codegen::{ServiceAdded, ServiceCodegen};
 use crate::flavored::*;
-use crate::utils::BlockComment;
+use crate::utils::{BlockComment, Warn};
 use crate::Options;
 use backend::Initializer;
 use genco::prelude::*;
@@ -79,6 +79,7 @@ impl ServiceCodegen for RequestsServiceCodegen {
                         quote_in! { *t =>
                             def $(e.safe_ident())(self, $(for a in &e.arguments => $(a.safe_ident()))):
                                 $(BlockComment(&e.comment))
+                                $(Warn { what: e.ident(), deprecated: e.deprecated.as_ref() })
                                 $(if let Some(ref http_path) = e.http.path {
                                     path = list()
 
@@ -1,4 +1,6 @@
+use crate::flavored::RpDeprecated;
 use genco::lang::Python;
+use genco::prelude::*;
 use genco::tokens::{FormatInto, ItemStr};
 use genco::Tokens;
 use std::fmt;
@@ -29,6 +31,38 @@ where
     }
 }
 
+/// Issues a `DeprecationWarning` when executed, if the item is deprecated.
+pub struct Warn<'a> {
+    /// Description of the deprecated item.
+    pub what: &'a str,
+    pub deprecated: Option<&'a RpDeprecated>,
+}
+
+impl<'a> FormatInto<Python> for Warn<'a> {
+    fn format_into(self, out: &mut Tokens<Python>) {
+        let deprecated = match self.deprecated {
+            Some(deprecated) => deprecated,
+            None => return,
+        };
+
+        let mut message = format!("{} is deprecated", self.what);
+
+        if let Some(since) = &deprecated.since {
+            message.push_str(&format!(" since {}", since));
+        }
+
+        if let Some(note) = &deprecated.note {
+            message.push_str(&format!(": {}", note));
+        }
+
+        let warnings = python::import_module("warnings");
+
+        quote_in! { *out =>
+            $warnings.warn($(quoted(message)), DeprecationWarning, stacklevel=2)
+        }
+    }
+}
+
 /// Utilities for handling differences between python versions.
 pub trait VersionHelper: fmt::Debug {
     /// Check if the given variable is a string.
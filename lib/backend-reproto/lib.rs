@@ -4,7 +4,7 @@ use genco::tokens::{FormatInto, ItemStr};
 use manifest::{Lang, Manifest, NoModule, TryFromToml};
 use reproto_core::errors::Result;
 use reproto_core::flavored::*;
-use reproto_core::{CoreFlavor, Handle, RelativePathBuf, Spanned, DEFAULT_TAG};
+use reproto_core::{CoreFlavor, Handle, RelativePathBuf, RpValidate, Spanned, DEFAULT_TAG};
 use std::any::Any;
 use std::fmt::Write as _;
 use std::path::Path;
@@ -180,6 +180,8 @@ pub fn format(out: &mut Tokens<Reproto>, decl: &RpDecl) {
         RpDecl::Tuple(tuple) => format_tuple(out, tuple),
         RpDecl::Enum(en) => format_enum(out, en),
         RpDecl::Service(service) => format_service(out, service),
+        RpDecl::Const(body) => format_const(out, body),
+        RpDecl::Alias(body) => format_alias(out, body),
     }
 }
 
@@ -206,6 +208,7 @@ fn format_interface(out: &mut Tokens<Reproto>, body: &RpInterfaceBody) {
         .chain(body.decls.iter().map(Interior::Decl));
 
     quote_in! { *out =>
+        $(Comments(&body.comment))
         $(match &body.sub_type_strategy {
             RpSubTypeStrategy::Tagged { tag, .. } if tag != DEFAULT_TAG => {
                 #[type_info(strategy = "tagged", tag = $(quoted(tag.as_str())))]
@@ -215,7 +218,6 @@ fn format_interface(out: &mut Tokens<Reproto>, body: &RpInterfaceBody) {
             }
             _ => {}
         })
-        $(Comments(&body.comment))
         interface $(body.ident.as_str()) {
             $(for i in interior join ($['\n']) => $i)
         }
@@ -251,11 +253,16 @@ fn format_enum(out: &mut Tokens<Reproto>, body: &RpEnumBody) {
 fn format_service(out: &mut Tokens<Reproto>, body: &RpServiceBody) {
     quote_in! { *out =>
         $(Comments(&body.comment))
+        $(if let Some(url) = &body.http.url {
+            #[http(url = $(quoted(url.as_str())))]
+        })
         service $(body.ident.as_str()) {
             $(for e in &body.endpoints join ($['\n']) =>
-                $(Comments(&e.comment))
                 $(ref out => format_endpoint(out, e))
             )
+            $(for d in &body.decls join ($['\n']) =>
+                $(ref out => format(out, d))
+            )
         }
     }
 
@@ -263,11 +270,73 @@ fn format_service(out: &mut Tokens<Reproto>, body: &RpServiceBody) {
 
     fn format_endpoint(out: &mut Tokens<Reproto>, e: &RpEndpoint) {
         quote_in! { *out =>
+            $(Comments(&e.comment))
+            $(ref out => format_http(out, &e.http))
             $(e.ident.as_str())($(for a in &e.arguments join (, ) =>
-                $(a.ident.as_str()): $(a.channel.to_string())
-            ))
+                $(a.ident()): $(a.channel.to_string())
+            ))$(if let Some(response) = &e.response {
+                $[' ']-> $(response.to_string())
+            });
         }
     }
+
+    fn format_http(out: &mut Tokens<Reproto>, http: &RpEndpointHttp) {
+        let mut values = Vec::<Tokens<Reproto>>::new();
+
+        if let Some(path) = &http.path {
+            let mut spec = String::new();
+
+            for step in &path.steps {
+                spec.push('/');
+
+                for part in &step.parts {
+                    match part {
+                        RpPathPart::Segment(segment) => spec.push_str(segment),
+                        RpPathPart::Variable(var) => {
+                            spec.push('{');
+                            spec.push_str(var.ident());
+                            spec.push('}');
+                        }
+                    }
+                }
+            }
+
+            values.push(quote!(path = $(quoted(spec))));
+        }
+
+        if let Some(method) = &http.method {
+            values.push(quote!(method = $(quoted(method.as_str()))));
+        }
+
+        if let RpAccept::Text = http.accept {
+            values.push(quote!(accept = "text/plain"));
+        }
+
+        if values.is_empty() {
+            return;
+        }
+
+        quote_in! { *out =>
+            #[http($(for v in values join (, ) => $v))]
+        }
+    }
+}
+
+fn format_const(out: &mut Tokens<Reproto>, body: &RpConstBody) {
+    quote_in! { *out =>
+        $(Comments(&body.comment))
+        const $(body.ident.as_str()): $(body.ty.to_string()) = $(body.value.to_string());
+    }
+}
+
+fn format_alias(out: &mut Tokens<Reproto>, body: &RpAliasBody) {
+    quote_in! { *out =>
+        $(Comments(&body.comment))
+        $(if let Some(validate) = &body.validate {
+            $(ref out => format_validate(out, validate))
+        })
+        type $(body.ident.as_str()) = $(body.ty.to_string());
+    }
 }
 
 fn format_field(out: &mut Tokens<Reproto>, field: &RpField) {
@@ -280,16 +349,58 @@ fn format_field(out: &mut Tokens<Reproto>, field: &RpField) {
 
     quote_in! { *out =>
         $(Comments(&field.comment))
+        $(if let Some(validate) = &field.validate {
+            $(ref out => format_validate(out, validate))
+        })
         $(if field.is_optional() {
             $(field_name)?: $(&field.ty.to_string())
         } else {
             $(field_name): $(field.ty.to_string())
         })$(if let Some(ref field_as) = field.field_as {
             $[' ']as $(quoted(field_as.as_str()))
+        })$(if let Some(ref default) = field.default {
+            $[' ']= $(default.to_string())
         })
     }
 }
 
+fn format_validate(out: &mut Tokens<Reproto>, validate: &RpValidate) {
+    let mut values = Vec::<Tokens<Reproto>>::new();
+
+    match validate {
+        RpValidate::Number(number) => {
+            if let Some(min) = &number.min {
+                values.push(quote!(min = $(min.to_string())));
+            }
+
+            if let Some(max) = &number.max {
+                values.push(quote!(max = $(max.to_string())));
+            }
+        }
+        RpValidate::String(string) => {
+            if let Some(pattern) = &string.pattern {
+                values.push(quote!(pattern = $(quoted(pattern.to_string()))));
+            }
+
+            if let Some(min_length) = string.min_length {
+                values.push(quote!(min_length = $(min_length.to_string())));
+            }
+
+            if let Some(max_length) = string.max_length {
+                values.push(quote!(max_length = $(max_length.to_string())));
+            }
+        }
+    }
+
+    if values.is_empty() {
+        return;
+    }
+
+    quote_in! { *out =>
+        #[validate($(for v in values join (, ) => $v))]
+    }
+}
+
 fn format_variant(out: &mut Tokens<Reproto>, variant: RpVariantRef<'_>) {
     quote_in! { *out =>
         $(Comments(variant.comment))
@@ -303,3 +414,148 @@ fn format_variant(out: &mut Tokens<Reproto>, variant: RpVariantRef<'_>) {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{format, Reproto};
+    use genco::fmt;
+    use genco::prelude::*;
+    use reproto_core::errors::Result;
+    use reproto_core::{
+        CoreFlavor, EmptyResolver, Reported, RpPackage, RpVersionedPackage, Source,
+    };
+    use trans::Session;
+
+    const DOCUMENT: &str = r##"
+openapi: 3.0.0
+info:
+  title: pets
+paths:
+  /pets/{petId}:
+    get:
+      operationId: getPet
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+    put:
+      operationId: putPet
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: string
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/Pet"
+      responses:
+        "204":
+          description: updated
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: "#/components/schemas/Pet"
+components:
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: "#/components/schemas/Dog"
+      discriminator:
+        propertyName: kind
+        mapping:
+          dog: "#/components/schemas/Dog"
+    Dog:
+      type: object
+      required: [kind, name]
+      properties:
+        kind:
+          type: string
+        name:
+          type: string
+          minLength: 1
+          pattern: "[a-z]+"
+        age:
+          type: integer
+          format: int32
+          minimum: 0
+        size:
+          type: string
+          enum: [small, large]
+"##;
+
+    /// Translate the session and format it as a reproto specification.
+    fn emit(session: Session<'_, CoreFlavor>) -> Result<String> {
+        let env = session.translate_default()?;
+
+        let mut body = Tokens::<Reproto>::new();
+
+        for (_, file) in env.for_each_file() {
+            for decl in &file.decls {
+                format(&mut body, decl);
+                body.line();
+            }
+        }
+
+        let config =
+            fmt::Config::from_lang::<Reproto>().with_indentation(fmt::Indentation::Space(2));
+
+        let mut w = fmt::FmtWriter::new(String::new());
+        body.format_file(&mut w.as_formatter(&config), &())?;
+        Ok(w.into_inner())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let package = RpVersionedPackage::new(RpPackage::parse("test"), None);
+
+        let source = Source::bytes("openapi", DOCUMENT.as_bytes().to_vec());
+
+        let file = ast::File {
+            comment: vec![],
+            attributes: vec![],
+            uses: vec![],
+            decls: derive::open_api("Generated", &source)?,
+        };
+
+        let mut reporter: Vec<Reported> = Vec::new();
+        let mut resolver = EmptyResolver;
+        let mut session = Session::new(None, &mut reporter, &mut resolver)?;
+        session.import_file(file, Some(package.clone()))?;
+        let emitted = emit(session)?;
+
+        assert!(emitted.contains(r#"#[http(path = "/pets/{pet_id}", method = "PUT")]"#));
+        assert!(emitted.contains("put_pet(body: Pet, pet_id: string);"));
+        assert!(emitted.contains("list_pets() -> [Pet];"));
+        assert!(emitted.contains(r#"#[validate(pattern = "[a-z]+", min_length = 1)]"#));
+        assert!(emitted.contains("#[validate(min = 0)]"));
+
+        // parse the emitted specification back, which should result in the same specification.
+        let source = Source::bytes("emitted", emitted.as_bytes().to_vec());
+
+        let mut reporter: Vec<Reported> = Vec::new();
+        let mut resolver = EmptyResolver;
+        let mut session = Session::new(None, &mut reporter, &mut resolver)?;
+        session.import_source(source, Some(package))?;
+
+        assert_eq!(emitted, emit(session)?);
+        Ok(())
+    }
+}
@@ -1,13 +1,13 @@
 //! Backend for Rust
 
 use crate::flavored::*;
-use crate::utils::Comments;
+use crate::utils::{Comments, Deprecated};
 use crate::{Options, Root, Service, EXT, MOD, TYPE_SEP};
 use backend::PackageProcessor;
 use genco::prelude::*;
 use genco::tokens::FormatInto;
 use reproto_core::errors::Result;
-use reproto_core::{Handle, RelativePathBuf, Spanned};
+use reproto_core::{Handle, RelativePathBuf, RpNumber, RpValidate, Span, Spanned};
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::rc::Rc;
@@ -137,7 +137,7 @@ impl<'el> Compiler<'el> {
     }
 
     // Build the corresponding element out of a field declaration.
-    fn field_element(&self, out: &mut Tokens<Rust>, field: &RpField) {
+    fn field_element(&self, out: &mut Tokens<Rust>, name: &str, field: &RpField) {
         let ident = field.safe_ident().to_string();
 
         quote_in! { *out =>
@@ -147,19 +147,278 @@ impl<'el> Compiler<'el> {
             $(if field.name() != ident {
                 $(Rename(field.name()))
             })
+            $(ref out => self.default_attributes(out, name, field))
+            $(ref out => self.validate_attributes(out, name, field))
             pub $ident: $(ref out => self.write_type(out, field))
         }
     }
 
+    /// Attributes which causes the default value to be used when the field is absent.
+    fn default_attributes(&self, out: &mut Tokens<Rust>, name: &str, field: &RpField) {
+        if field.default.is_none() {
+            return;
+        }
+
+        let path = format!("{}::default_{}", name, field.ident);
+
+        quote_in! { *out =>
+            #[serde(default = $(quoted(path)))]
+        }
+    }
+
+    /// Build functions providing the default values of all fields which have them.
+    fn default_impl<'f, I>(&self, out: &mut Tokens<Rust>, name: &str, fields: I)
+    where
+        I: IntoIterator<Item = &'f Spanned<RpField>>,
+    {
+        let fields = fields
+            .into_iter()
+            .filter_map(|f| f.default.as_ref().map(|d| (f, d)))
+            .collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            return;
+        }
+
+        quote_in! { *out =>
+            impl $name {
+                $(for (field, default) in fields join ($['\n']) {
+                    fn default_$(&field.ident)() -> $(ref out => self.write_type(out, field)) {
+                        $(match default {
+                            RpLiteral::String(..) => $(self.literal(&field.ty, default)).to_string(),
+                            _ => $(self.literal(&field.ty, default)),
+                        })
+                    }
+                })
+            }
+        }
+    }
+
+    /// Build a literal of the given type.
+    fn literal(&self, ty: &Type, literal: &RpLiteral) -> Tokens<Rust> {
+        match literal {
+            RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+            RpLiteral::Number(number) => match ty {
+                // NB: whole numbers need a decimal point to be used as floats.
+                Type::Primitive(Primitive::F32) | Type::Primitive(Primitive::F64)
+                    if number.decimal == 0 =>
+                {
+                    quote!($(format!("{}.0", number)))
+                }
+                _ => quote!($(number.to_string())),
+            },
+            RpLiteral::Boolean(boolean) => quote!($(boolean.to_string())),
+        }
+    }
+
+    /// Attributes which causes the field to be validated when it is deserialized.
+    fn validate_attributes(&self, out: &mut Tokens<Rust>, name: &str, field: &RpField) {
+        if field.validate.is_none() {
+            return;
+        }
+
+        let path = format!("{}::deserialize_{}", name, field.ident);
+
+        quote_in! { *out =>
+            $(if field.is_optional() {
+                #[serde(default)]
+            })
+            #[serde(deserialize_with = $(quoted(path)))]
+        }
+    }
+
+    /// Build deserialize functions for all fields which have validation rules.
+    fn validate_impl<'f, I>(&self, out: &mut Tokens<Rust>, name: &str, fields: I)
+    where
+        I: IntoIterator<Item = &'f Spanned<RpField>>,
+    {
+        let fields = fields.into_iter().map(|f| (f.ident.as_str(), f));
+        self.labeled_validate_impl(out, name, fields)
+    }
+
+    /// Build deserialize functions for all fields which have validation rules, where errors refer
+    /// to each field by the given label.
+    fn labeled_validate_impl<'f, I>(&self, out: &mut Tokens<Rust>, name: &str, fields: I)
+    where
+        I: IntoIterator<Item = (&'f str, &'f Spanned<RpField>)>,
+    {
+        let fields = fields
+            .into_iter()
+            .filter_map(|(label, f)| f.validate.as_ref().map(|v| (label, f, v)))
+            .collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            return;
+        }
+
+        let deserialize = &rust::import("serde", "Deserialize");
+        let deserializer = &rust::import("serde", "Deserializer");
+
+        quote_in! { *out =>
+            impl $name {
+                $(for (label, field, validate) in fields join ($['\n']) {
+                    fn deserialize_$(&field.ident)<'de, D>(d: D) -> Result<$(ref out => self.write_type(out, field)), D::Error>
+                    where
+                        D: $deserializer<'de>,
+                    {
+                        let value: $(ref out => self.write_type(out, field)) = $deserialize::deserialize(d)?;
+
+                        $(if field.is_optional() {
+                            if let Some(value) = &value {
+                                $(ref out => match validate {
+                                    RpValidate::Number(..) => self.validate_checks(out, label, field, validate, quote!(*value)),
+                                    _ => self.validate_checks(out, label, field, validate, quote!(value)),
+                                })
+                            }
+                        } else {
+                            $(ref out => self.validate_checks(out, label, field, validate, quote!(value)))
+                        })
+
+                        Ok(value)
+                    }
+                })
+            }
+        }
+    }
+
+    /// `FromStr` and `Display` implementations for a newtype over a primitive, so that it can be
+    /// used in paths.
+    ///
+    /// Parsing goes through `Deserialize`, so that the value is validated.
+    fn str_impl(&self, out: &mut Tokens<Rust>, name: &str, ty: &Type) {
+        if !matches!(ty, Type::Primitive(..) | Type::String) {
+            return;
+        }
+
+        let from_str = &rust::import("std::str", "FromStr");
+        let display = &rust::import("std::fmt", "Display");
+        let formatter = &rust::import("std::fmt", "Formatter");
+        let fmt_result = &rust::import("std::fmt", "Result");
+        let deserialize = &rust::import("serde", "Deserialize");
+        let error = &rust::import("serde::de", "Error");
+        let into_deserializer = &rust::import("serde::de", "IntoDeserializer");
+        let value_error = &rust::import("serde::de::value", "Error");
+
+        quote_in! { *out =>
+            impl $from_str for $name {
+                type Err = $value_error;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    let value: $ty = s.parse().map_err(<Self::Err as $error>::custom)?;
+                    $deserialize::deserialize($into_deserializer::into_deserializer(value))
+                }
+            }
+
+            impl $display for $name {
+                fn fmt(&self, fmt: &mut $formatter) -> $fmt_result {
+                    $display::fmt(&self.0, fmt)
+                }
+            }
+        }
+    }
+
+    /// Checks for the validation rules of a single field.
+    fn validate_checks(
+        &self,
+        out: &mut Tokens<Rust>,
+        label: &str,
+        field: &RpField,
+        validate: &RpValidate,
+        value: Tokens<Rust>,
+    ) {
+        let error = &rust::import("serde::de", "Error");
+
+        let mut checks = Vec::new();
+
+        match validate {
+            RpValidate::Number(validate) => {
+                let float = matches!(
+                    field.ty.resolve(),
+                    Type::Primitive(Primitive::F32) | Type::Primitive(Primitive::F64)
+                );
+
+                // NB: whole numbers need a decimal point to be compared with floats.
+                let bound = |n: &RpNumber| {
+                    if float && n.decimal == 0 {
+                        format!("{}.0", n)
+                    } else {
+                        n.to_string()
+                    }
+                };
+
+                if let Some(min) = &validate.min {
+                    checks.push(quote! {
+                        if $(&value) < $(bound(min)) {
+                            return Err(<D::Error as $error>::custom($(quoted(format!("{}: must be greater than or equal to {}", label, min)))));
+                        }
+                    });
+                }
+
+                if let Some(max) = &validate.max {
+                    checks.push(quote! {
+                        if $(&value) > $(bound(max)) {
+                            return Err(<D::Error as $error>::custom($(quoted(format!("{}: must be less than or equal to {}", label, max)))));
+                        }
+                    });
+                }
+            }
+            RpValidate::String(validate) => {
+                if let Some(pattern) = &validate.pattern {
+                    let pattern = pattern.to_string();
+                    let regex = &rust::import("regex", "Regex");
+                    let once_lock = &rust::import("std::sync", "OnceLock");
+
+                    checks.push(quote! {
+                        static PATTERN: $once_lock<$regex> = $once_lock::new();
+
+                        let pattern = PATTERN.get_or_init(|| {
+                            $regex::new($(quoted(format!("^(?:{})$", pattern)))).expect("valid pattern")
+                        });
+
+                        if !pattern.is_match(&$(&value)) {
+                            return Err(<D::Error as $error>::custom($(quoted(format!("{}: must match pattern `{}`", label, pattern)))));
+                        }
+                    });
+                }
+
+                if let Some(min_length) = validate.min_length {
+                    checks.push(quote! {
+                        if $(&value).chars().count() < $(min_length.to_string()) {
+                            return Err(<D::Error as $error>::custom($(quoted(format!("{}: must be at least {} characters long", label, min_length)))));
+                        }
+                    });
+                }
+
+                if let Some(max_length) = validate.max_length {
+                    checks.push(quote! {
+                        if $(&value).chars().count() > $(max_length.to_string()) {
+                            return Err(<D::Error as $error>::custom($(quoted(format!("{}: must be at most {} characters long", label, max_length)))));
+                        }
+                    });
+                }
+            }
+        }
+
+        quote_in! { *out =>
+            $(for c in checks join ($['\n']) => $c)
+        }
+    }
+
     pub fn compile(&self) -> Result<()> {
         use genco::fmt;
 
-        let mut files = self.do_populate_files(|_, new, out| {
+        let mut deprecated = BTreeSet::new();
+
+        let mut files = self.do_populate_files(|decl, new, out| {
             // Add a line separating entries in each non-new file.
             if !new {
                 out.line();
             }
 
+            if has_deprecated(decl) {
+                deprecated.insert(decl.name().package.clone());
+            }
+
             Ok(())
         })?;
 
@@ -176,7 +435,16 @@ impl<'el> Compiler<'el> {
 
             log::debug!("+module: {}", full_path);
 
-            let mut w = fmt::IoWriter::new(handle.create(&full_path)?);
+            let mut f = handle.create(&full_path)?;
+
+            // NB: derived implementations refer to the deprecated items they are derived for,
+            // which can only be allowed for the whole module.
+            if deprecated.contains(&package) {
+                writeln!(f, "#![allow(deprecated)]")?;
+                writeln!(f)?;
+            }
+
+            let mut w = fmt::IoWriter::new(f);
             let config = rust::Config::default().with_default_import(rust::ImportMode::Qualified);
             let fmt = fmt::Config::from_lang::<Rust>().with_indentation(fmt::Indentation::Space(2));
 
@@ -265,9 +533,12 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
         quote_in! { *out =>
             $['\n']
             $(Comments(&body.comment))
+            $(Deprecated(body.deprecated.as_ref()))
             $attributes
             $(&self.derives)
-            pub struct $name($(for f in &body.fields join (, ) => pub $(ref out => self.write_type(out, f))));
+            pub struct $(&name)($(for f in &body.fields join (, ) => $(ref out => self.validate_attributes(out, &name, f)) pub $(ref out => self.write_type(out, f))));
+
+            $(ref out => self.validate_impl(out, &name, &body.fields))
         };
 
         Ok(())
@@ -290,10 +561,12 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
 
         quote_in! { *out =>
             $(Comments(&body.comment))
+            $(Deprecated(body.deprecated.as_ref()))
             $attributes
             pub enum $name {
                 $(for v in &body.variants join ($['\r']) =>
                     $(Comments(v.comment))
+                    $(Deprecated(v.deprecated))
                     $(match v.value {
                         RpVariantValue::String(string) if string != v.ident() => {
                             $(Rename(string))
@@ -313,9 +586,9 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
             }
 
             $(if let RpVariants::Number { variants } = &body.variants {
-                $(ref t => numeric_serialize(t, body, &name, variants))
+                $(ref t => numeric_serialize(t, body, name, variants))
 
-                $(ref t => numeric_deserialize(t, body, &name, variants))
+                $(ref t => numeric_deserialize(t, body, name, variants))
             })
         }
 
@@ -496,15 +769,20 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
         quote_in! { *out =>
             $['\n']
             $(Comments(&body.comment))
+            $(Deprecated(body.deprecated.as_ref()))
             $attributes
             $(&self.derives)
             pub struct $name {
                 $(for field in &body.fields join ($['\n']) =>
                     $(Comments(&field.comment))
-                    $(ref out => self.field_element(out, field)),
+                    $(Deprecated(field.deprecated.as_ref()))
+                    $(ref out => self.field_element(out, name, field)),
                 )
             }
 
+            $(ref out => self.validate_impl(out, name, &body.fields))
+            $(ref out => self.default_impl(out, name, &body.fields))
+
             $(if backend::code_contains!(body.codes, RpContext::Rust) {
                 impl $name {
                     $(ref out => backend::code_in!(out, &body.codes, RpContext::Rust))
@@ -520,6 +798,7 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
 
         quote_in! { *out =>
             $(Comments(&body.comment))
+            $(Deprecated(body.deprecated.as_ref()))
             $(attributes)
             $(&self.derives)
             $(match &body.sub_type_strategy {
@@ -530,6 +809,11 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
                 $(for s in &body.sub_types join ($['\n']) => $(ref out =>
                     let (sub_name, _) = self.convert_type_name(&s.name);
 
+                    if s.deprecated.is_some() {
+                        out.push();
+                        out.append(Deprecated(s.deprecated.as_ref()));
+                    }
+
                     if let Some(name) = &s.sub_type_name {
                         if name.as_str() != s.ident.as_str() {
                             out.push();
@@ -553,14 +837,19 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
 
                 quote_in! { *out =>
                     $(Comments(&s.comment))
+                    $(Deprecated(s.deprecated.as_ref()))
                     $(&self.derives)
                     $attributes
-                    pub struct $sub_name {
+                    pub struct $(&sub_name) {
                         $(for field in body.fields.iter().chain(&s.fields) join ($['\n']) =>
                             $(Comments(&field.comment))
-                            $(ref out => self.field_element(out, field)),
+                            $(Deprecated(field.deprecated.as_ref()))
+                            $(ref out => self.field_element(out, &sub_name, field)),
                         )
                     }
+
+                    $(ref out => self.validate_impl(out, &sub_name, body.fields.iter().chain(&s.fields)))
+                    $(ref out => self.default_impl(out, &sub_name, body.fields.iter().chain(&s.fields)))
                 }
             ))
         };
@@ -568,6 +857,62 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
         Ok(())
     }
 
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody) -> Result<()> {
+        let (name, _) = self.convert_type_name(&body.name);
+
+        // NB: string constants can't be allocated, so they are string slices instead.
+        let ty = match body.ty {
+            Type::String => Type::StaticStr,
+            ref ty => ty.clone(),
+        };
+
+        quote_in! { *out =>
+            $['\n']
+            $(Comments(&body.comment))
+            $(Deprecated(body.deprecated.as_ref()))
+            #[allow(non_upper_case_globals)]
+            pub const $(&*name): $(&ty) = $(self.literal(&body.ty, &body.value));
+        };
+
+        Ok(())
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody) -> Result<()> {
+        let (name, attributes) = self.convert_type_name(&body.name);
+
+        if !self.options.newtype {
+            quote_in! { *out =>
+                $['\n']
+                $(Comments(&body.comment))
+                $(Deprecated(body.deprecated.as_ref()))
+                $attributes
+                pub type $(&*name) = $(&body.ty);
+            };
+
+            return Ok(());
+        }
+
+        let mut field = RpField::new("value", body.ty.clone());
+        field.validate = body.validate.clone();
+        let fields = [Spanned::new(field, Span::empty())];
+
+        quote_in! { *out =>
+            $['\n']
+            $(Comments(&body.comment))
+            $(Deprecated(body.deprecated.as_ref()))
+            $attributes
+            $(&self.derives)
+            #[serde(transparent)]
+            pub struct $(&*name)($(ref out => self.validate_attributes(out, &name, &fields[0])) pub $(&body.ty));
+
+            $(ref out => self.labeled_validate_impl(out, &name, [(name.as_str(), &fields[0])]))
+
+            $(ref out => self.str_impl(out, &name, &body.ty))
+        };
+
+        Ok(())
+    }
+
     fn process_service(&self, out: &mut Self::Out, body: &RpServiceBody) -> Result<()> {
         let (name, attributes) = self.convert_type_name(&body.name);
 
@@ -583,3 +928,27 @@ impl<'el> PackageProcessor<'el, RustFlavor> for Compiler<'el> {
         Ok(())
     }
 }
+
+/// Test if a declaration, or any of its members, is deprecated.
+fn has_deprecated(decl: &RpDecl) -> bool {
+    let fields = |fields: &[Spanned<RpField>]| fields.iter().any(|f| f.deprecated.is_some());
+
+    if decl.deprecated().is_some() {
+        return true;
+    }
+
+    match decl {
+        RpDecl::Type(body) => fields(&body.fields),
+        RpDecl::Tuple(body) => fields(&body.fields),
+        RpDecl::Interface(body) => {
+            fields(&body.fields)
+                || body
+                    .sub_types
+                    .iter()
+                    .any(|s| s.deprecated.is_some() || fields(&s.fields))
+        }
+        RpDecl::Enum(body) => body.variants.iter().any(|v| v.deprecated.is_some()),
+        RpDecl::Service(body) => body.endpoints.iter().any(|e| e.deprecated.is_some()),
+        RpDecl::Const(..) | RpDecl::Alias(..) => false,
+    }
+}
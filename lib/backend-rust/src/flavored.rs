@@ -49,6 +49,8 @@ pub(crate) enum Type {
     Generic(Rc<rust::Import>, Box<Type>),
     /// `&'static str`.
     StaticStr,
+    /// A transparent alias, with the type that it aliases.
+    Alias(Box<Type>, Box<Type>),
 }
 
 impl From<rust::Import> for Type {
@@ -69,6 +71,14 @@ impl Type {
     pub(crate) fn generic(base: impl Into<rust::Import>, a: impl Into<Type>) -> Self {
         Self::Generic(Rc::new(base.into()), Box::new(a.into()))
     }
+
+    /// The type that this type aliases, if it is an alias.
+    pub(crate) fn resolve(&self) -> &Type {
+        match self {
+            Type::Alias(_, ty) => ty.resolve(),
+            ty => ty,
+        }
+    }
 }
 
 impl<'a> tokens::FormatInto<Rust> for &'a Type {
@@ -111,6 +121,9 @@ impl<'a> tokens::FormatInto<Rust> for &'a Type {
             Type::Generic(base, a) => {
                 quote_in!(*t => $(&**base)<$(&**a)>);
             }
+            Type::Alias(name, _) => {
+                (&**name).format_into(t);
+            }
         }
     }
 }
@@ -217,6 +230,10 @@ impl FlavorTranslator for RustFlavorTranslator {
         Ok(Type::local(ident))
     }
 
+    fn translate_alias(&self, name: Type, ty: Type) -> Result<Type> {
+        Ok(Type::Alias(Box::new(name), Box::new(ty)))
+    }
+
     fn translate_endpoint<T>(
         &self,
         translator: &T,
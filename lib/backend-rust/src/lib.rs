@@ -94,6 +94,8 @@ impl Lang for RustLang {
 pub(crate) enum Module {
     Chrono,
     Reqwest,
+    Hyper,
+    Newtype,
 }
 
 impl TryFromToml for Module {
@@ -101,6 +103,8 @@ impl TryFromToml for Module {
         let result = match id {
             "chrono" => Module::Chrono,
             "reqwest" => Module::Reqwest,
+            "hyper" => Module::Hyper,
+            "newtype" => Module::Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -111,6 +115,8 @@ impl TryFromToml for Module {
         let result = match id {
             "chrono" => Module::Chrono,
             "reqwest" => Module::Reqwest,
+            "hyper" => Module::Hyper,
+            "newtype" => Module::Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -120,6 +126,8 @@ impl TryFromToml for Module {
 
 pub(crate) struct Options {
     pub(crate) datetime: Option<Type>,
+    /// Generate aliases as newtype wrappers.
+    pub(crate) newtype: bool,
     pub(crate) root: Vec<Box<dyn RootCodegen>>,
     pub(crate) service: Vec<Box<dyn ServiceCodegen>>,
     pub(crate) packages: Rc<Packages>,
@@ -129,6 +137,19 @@ pub(crate) struct Root<'a> {
     files: &'a mut BTreeMap<RpPackage, rust::Tokens>,
 }
 
+impl Root<'_> {
+    /// Append the given tokens to a file, which might be shared with other modules.
+    pub(crate) fn append(&mut self, package: &RpPackage, tokens: rust::Tokens) {
+        let file = self.files.entry(package.clone()).or_default();
+
+        if !file.is_empty() {
+            file.line();
+        }
+
+        file.append(tokens);
+    }
+}
+
 pub(crate) trait RootCodegen {
     /// Generate root code.
     fn generate(&self, root: Root) -> Result<()>;
@@ -149,6 +170,7 @@ pub(crate) trait ServiceCodegen {
 fn options(modules: Vec<Module>, packages: Rc<Packages>) -> Result<Options> {
     let mut options = Options {
         datetime: None,
+        newtype: false,
         root: Vec::new(),
         service: Vec::new(),
         packages,
@@ -160,6 +182,8 @@ fn options(modules: Vec<Module>, packages: Rc<Packages>) -> Result<Options> {
         match m {
             Module::Chrono => module::chrono::initialize(&mut options)?,
             Module::Reqwest => module::reqwest::initialize(&mut options)?,
+            Module::Hyper => module::hyper::initialize(&mut options)?,
+            Module::Newtype => module::newtype::initialize(&mut options)?,
         }
     }
 
@@ -171,10 +195,13 @@ fn compile(handle: &dyn Handle, session: Session<CoreFlavor>, manifest: Manifest
     let packages = session.packages()?;
     let options = options(modules, packages.clone())?;
 
-    let session = session.translate(flavored::RustFlavorTranslator::new(
-        packages.clone(),
-        options.datetime.clone(),
-    ))?;
+    let session =
+        session
+            .with_newtypes(options.newtype)
+            .translate(flavored::RustFlavorTranslator::new(
+                packages.clone(),
+                options.datetime.clone(),
+            ))?;
 
     Compiler::new(&session, options, handle).compile()
 }
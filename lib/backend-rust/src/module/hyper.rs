@@ -0,0 +1,360 @@
+//! Hyper module for Rust.
+//!
+//! Generates a trait for each service, and a function which routes hyper requests to it.
+
+use crate::flavored::*;
+use crate::utils::{Comments, Deprecated};
+use crate::{Options, Root, RootCodegen, Service, ServiceCodegen, SCOPE_SEP};
+use genco::prelude::*;
+use genco::tokens::{FormatInto, ItemStr, Tokens};
+use reproto_core::errors::Result;
+use std::rc::Rc;
+
+pub(crate) fn initialize(options: &mut Options) -> Result<()> {
+    let utils_package = options.packages.new("reproto")?;
+
+    let imported_utils_package = Rc::new(format!("crate::{}", utils_package.join(SCOPE_SEP)));
+    let server_error = Type::from(rust::import(imported_utils_package.clone(), "ServerError"));
+    let path_decode = Type::from(rust::import(imported_utils_package.clone(), "PathDecode"));
+    let json_body = Type::from(rust::import(imported_utils_package.clone(), "json_body"));
+    let json_response = Type::from(rust::import(
+        imported_utils_package.clone(),
+        "json_response",
+    ));
+
+    options.service.push(Box::new(HyperService::new(
+        server_error,
+        path_decode,
+        json_body,
+        json_response,
+    )));
+
+    options.root.push(Box::new(HyperUtils::new(utils_package)));
+
+    Ok(())
+}
+
+struct HyperUtils {
+    utils_package: RpPackage,
+}
+
+impl HyperUtils {
+    pub fn new(utils_package: RpPackage) -> Self {
+        Self { utils_package }
+    }
+
+    fn reproto(&self) -> Result<rust::Tokens> {
+        let mut t = rust::Tokens::new();
+
+        let result = &rust::import("std::result", "Result");
+        let display = &rust::import("std::fmt", "Display");
+        let fmt = &rust::import("std::fmt", "Formatter");
+        let fmt_result = &rust::import("std::fmt", "Result");
+        let from_str = &rust::import("std::str", "FromStr");
+        let status_code = &rust::import("hyper", "StatusCode");
+        let request = &rust::import("hyper", "Request");
+        let response = &rust::import("hyper", "Response");
+        let body = &rust::import("hyper", "Body");
+        let to_bytes = &rust::import("hyper::body", "to_bytes");
+        let content_type = &rust::import("hyper::header", "CONTENT_TYPE");
+        let serialize = &rust::import("serde", "Serialize");
+        let deserialize_owned = &rust::import("serde::de", "DeserializeOwned");
+        let decode = &rust::import("percent_encoding", "percent_decode_str");
+
+        // error sent as a response.
+        quote_in! { t =>
+            #[derive(Debug)]
+            pub struct ServerError {
+                status: $status_code,
+                message: String,
+            }
+
+            impl ServerError {
+                pub fn new(status: $status_code, message: impl $display) -> Self {
+                    Self {
+                        status,
+                        message: message.to_string(),
+                    }
+                }
+
+                pub fn bad_request(message: impl $display) -> Self {
+                    Self::new($status_code::BAD_REQUEST, message)
+                }
+
+                pub fn not_found(message: impl $display) -> Self {
+                    Self::new($status_code::NOT_FOUND, message)
+                }
+
+                pub fn internal(message: impl $display) -> Self {
+                    Self::new($status_code::INTERNAL_SERVER_ERROR, message)
+                }
+
+                pub fn status(&self) -> $status_code {
+                    self.status
+                }
+
+                pub fn into_response(self) -> $response<$body> {
+                    let mut res = $response::new($body::from(self.message));
+                    *res.status_mut() = self.status;
+                    res
+                }
+            }
+
+            impl $display for ServerError {
+                fn fmt(&self, fmt: &mut $fmt) -> $fmt_result {
+                    write!(fmt, "{}: {}", self.status, self.message)
+                }
+            }
+        };
+
+        t.line();
+
+        quote_in! { t =>
+            pub struct PathDecode<T>(pub T);
+
+            impl<T> $from_str for PathDecode<T>
+            where
+                T: $from_str
+            {
+                type Err = ServerError;
+
+                fn from_str(s: &str) -> $result<Self, Self::Err> {
+                    let decoded = $decode(s).decode_utf8().map_err(ServerError::bad_request)?;
+
+                    match decoded.parse() {
+                        Ok(value) => Ok(PathDecode(value)),
+                        Err(_) => Err(ServerError::bad_request(format!("bad path segment: {}", s))),
+                    }
+                }
+            }
+        };
+
+        t.line();
+
+        quote_in! { t =>
+            pub async fn json_body<T>(req: $request<$body>) -> $result<T, ServerError>
+            where
+                T: $deserialize_owned
+            {
+                let body = $to_bytes(req.into_body()).await.map_err(ServerError::bad_request)?;
+                serde_json::from_slice(&body).map_err(ServerError::bad_request)
+            }
+
+            pub fn json_response<T>(value: &T) -> $result<$response<$body>, ServerError>
+            where
+                T: $serialize
+            {
+                let body = serde_json::to_vec(value).map_err(ServerError::internal)?;
+                let mut res = $response::new($body::from(body));
+                res.headers_mut().insert($content_type, "application/json".parse().unwrap());
+                Ok(res)
+            }
+        };
+
+        Ok(t)
+    }
+}
+
+impl RootCodegen for HyperUtils {
+    fn generate(&self, mut root: Root) -> Result<()> {
+        root.append(&self.utils_package, self.reproto()?);
+        Ok(())
+    }
+}
+
+struct HyperService {
+    server_error: Type,
+    path_decode: Type,
+    json_body: Type,
+    json_response: Type,
+}
+
+impl HyperService {
+    pub fn new(
+        server_error: Type,
+        path_decode: Type,
+        json_body: Type,
+        json_response: Type,
+    ) -> Self {
+        Self {
+            server_error,
+            path_decode,
+            json_body,
+            json_response,
+        }
+    }
+}
+
+impl ServiceCodegen for HyperService {
+    fn generate(&self, service: Service) -> Result<()> {
+        let Service {
+            body,
+            container,
+            name,
+            attributes,
+            ..
+        } = service;
+
+        let result = &rust::import("std::result", "Result");
+        let future = &rust::import("std::future", "Future");
+        let request = &rust::import("hyper", "Request");
+        let response = &rust::import("hyper", "Response");
+        let hyper_body = &rust::import("hyper", "Body");
+
+        let endpoints = body
+            .endpoints
+            .iter()
+            .flat_map(|e| e.http1.as_ref().map(|http| (e, http)))
+            .collect::<Vec<_>>();
+
+        let mut routes = Vec::new();
+
+        for (e, http) in &endpoints {
+            routes.push(Route {
+                codegen: self,
+                e,
+                http,
+                pattern: pattern(&http.path)?,
+            });
+        }
+
+        let route = &ItemStr::from(format!("{}_route", name));
+
+        quote_in! { *container =>
+            $(Comments(&body.comment))
+            $(Deprecated(body.deprecated.as_ref()))
+            $attributes
+            pub trait $(&name): Send + Sync {
+                $(for (e, http) in &endpoints join ($['\n']) =>
+                    $(Comments(&e.comment))
+                    $(Deprecated(e.deprecated.as_ref()))
+                    fn $(e.safe_ident())(&self, $(for a in &e.arguments join (, ) => $(a.safe_ident()): $(a.channel.ty()))) -> impl $future<Output = $result<$(match &http.response {
+                        Some(res) => $res,
+                        None => $("()"),
+                    }), $(&self.server_error)>> + Send;
+                )
+            }
+
+            $(Comments([format!("Route a request to an implementation of `{}`.", name)]))
+            #[allow(deprecated, non_snake_case)]
+            pub async fn $route<S_>(service: &S_, req: $request<$hyper_body>) -> $response<$hyper_body>
+            where
+                S_: $(&name),
+            {
+                return match route(service, req).await {
+                    Ok(res) => res,
+                    Err(e) => e.into_response(),
+                };
+
+                async fn route<S_>(service_: &S_, req_: $request<$hyper_body>) -> $result<$response<$hyper_body>, $(&self.server_error)>
+                where
+                    S_: $(&name),
+                {
+                    let method_ = req_.method().clone();
+                    let path_ = req_.uri().path().to_string();
+                    let segments_ = path_.strip_prefix('/').unwrap_or(&path_).split('/').collect::<Vec<_>>();
+
+                    match (method_.as_str(), &segments_[..]) {
+                        $(for r in routes join ($['\r']) => $r)
+                        _ => Err($(&self.server_error)::not_found(path_.as_str())),
+                    }
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// Build the slice pattern matching the segments of a path.
+fn pattern(path: &RpPathSpec) -> Result<Tokens<Rust>> {
+    let mut segments = Vec::new();
+
+    for step in &path.steps {
+        segments.push(match step.parts.as_slice() {
+            [] => quote!(""),
+            [RpPathPart::Segment(s)] => quote!($(quoted(s.as_str()))),
+            [RpPathPart::Variable(arg)] => quote!($(arg.safe_ident())),
+            _ => {
+                return Err(format!(
+                    "hyper: path segments which mix variables and text are not supported: {}",
+                    path
+                )
+                .into())
+            }
+        });
+    }
+
+    if segments.is_empty() {
+        segments.push(quote!(""));
+    }
+
+    Ok(quote!([$(for s in segments join (, ) => $s)]))
+}
+
+/// Build the match arm handling a single endpoint.
+struct Route<'el> {
+    codegen: &'el HyperService,
+    e: &'el RustEndpoint,
+    http: &'el RpEndpointHttp1,
+    pattern: Tokens<Rust>,
+}
+
+impl<'el> FormatInto<Rust> for Route<'el> {
+    fn format_into(self, t: &mut Tokens<Rust>) {
+        let Route {
+            codegen,
+            e,
+            http,
+            pattern,
+        } = self;
+
+        let response = &rust::import("hyper", "Response");
+        let hyper_body = &rust::import("hyper", "Body");
+        let content_type = &rust::import("hyper::header", "CONTENT_TYPE");
+
+        let variables = http
+            .path
+            .steps
+            .iter()
+            .flat_map(|s| &s.parts)
+            .flat_map(|p| match p {
+                RpPathPart::Variable(arg) => Some(arg),
+                RpPathPart::Segment(_) => None,
+            });
+
+        quote_in! { *t =>
+            ($(quoted(http.method.as_str())), $pattern) => {
+                $(for arg in variables join ($['\r']) {
+                    let $(arg.safe_ident()) = $(arg.safe_ident()).parse::<$(&codegen.path_decode)<$(arg.channel.ty())>>()?.0;
+                })
+                $(if let Some(req) = &e.request {
+                    let $(req.safe_ident()) = $(&codegen.json_body)(req_).await?;
+                })
+                $(match (&http.response, &e.http.accept) {
+                    (Some(_), RpAccept::Json) => {
+                        let res_ = $(ref t => call(t, e));
+                        $(&codegen.json_response)(&res_)
+                    }
+                    (Some(_), RpAccept::Text) => {
+                        let res_ = $(ref t => call(t, e));
+                        let mut res = $response::new($hyper_body::from(res_));
+                        res.headers_mut().insert($content_type, "text/plain; charset=utf-8".parse().unwrap());
+                        Ok(res)
+                    }
+                    (None, _) => {
+                        $(ref t => call(t, e));
+                        Ok($response::new($hyper_body::empty()))
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Call the service method of an endpoint.
+fn call(t: &mut Tokens<Rust>, e: &RustEndpoint) {
+    quote_in! { *t =>
+        service_.$(e.safe_ident())($(for a in &e.arguments join (, ) => $(a.safe_ident()))).await?
+    }
+}
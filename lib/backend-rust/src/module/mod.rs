@@ -1,2 +1,4 @@
 pub(crate) mod chrono;
+pub(crate) mod hyper;
+pub(crate) mod newtype;
 pub(crate) mod reqwest;
@@ -0,0 +1,9 @@
+//! Newtype module for Rust.
+
+use crate::Options;
+use reproto_core::errors::Result;
+
+pub(crate) fn initialize(options: &mut Options) -> Result<()> {
+    options.newtype = true;
+    Ok(())
+}
@@ -1,7 +1,7 @@
 //! gRPC module for Rust.
 
 use crate::flavored::*;
-use crate::utils::Comments;
+use crate::utils::{Comments, Deprecated};
 use crate::{Options, Root, RootCodegen, Service, ServiceCodegen, SCOPE_SEP};
 use genco::prelude::*;
 use genco::tokens::{FormatInto, ItemStr, Tokens};
@@ -115,9 +115,8 @@ impl ReqwestUtils {
 }
 
 impl RootCodegen for ReqwestUtils {
-    fn generate(&self, root: Root) -> Result<()> {
-        let Root { files, .. } = root;
-        files.insert(self.utils_package.clone(), self.reproto()?);
+    fn generate(&self, mut root: Root) -> Result<()> {
+        root.append(&self.utils_package, self.reproto()?);
         Ok(())
     }
 }
@@ -176,6 +175,7 @@ impl ServiceCodegen for ReqwestService {
 
                         quote_in! { *tokens =>
                             $(Comments(&e.comment))
+                            $(Deprecated(e.deprecated.as_ref()))
                             $(Endpoint {
                                 result: &self.result,
                                 path_encode: &self.path_encode,
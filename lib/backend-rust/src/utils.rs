@@ -1,4 +1,6 @@
+use crate::flavored::RpDeprecated;
 use genco::lang::Rust;
+use genco::prelude::*;
 use genco::tokens::{FormatInto, ItemStr, Tokens};
 
 /// Documentation comments.
@@ -29,3 +31,31 @@ where
         }
     }
 }
+
+/// A `#[deprecated]` attribute, if the item is deprecated.
+pub struct Deprecated<'a>(pub Option<&'a RpDeprecated>);
+
+impl<'a> FormatInto<Rust> for Deprecated<'a> {
+    fn format_into(self, tokens: &mut Tokens<Rust>) {
+        let deprecated = match self.0 {
+            Some(deprecated) => deprecated,
+            None => return,
+        };
+
+        let mut args = Vec::new();
+
+        if let Some(since) = &deprecated.since {
+            args.push(quote!(since = $(quoted(since.to_string()))));
+        }
+
+        if let Some(note) = &deprecated.note {
+            args.push(quote!(note = $(quoted(note))));
+        }
+
+        if args.is_empty() {
+            quote_in!(*tokens => #[deprecated]);
+        } else {
+            quote_in!(*tokens => #[deprecated($(for a in args join (, ) => $a))]);
+        }
+    }
+}
@@ -91,6 +91,20 @@ decl_codegen! {
         pub body: &'a RpInterfaceBody,
     }
 
+    /// Event emitted when a newtype wrapper of an alias has been added.
+    alias_added<'a> {
+        pub container: &'a mut Vec<swift::Tokens>,
+        pub name: &'a Name,
+        pub field: &'a Spanned<Field>,
+    }
+
+    /// Event emitted when a service has been added.
+    service_added<'a> {
+        pub container: &'a mut Vec<swift::Tokens>,
+        pub name: &'a Name,
+        pub body: &'a RpServiceBody,
+    }
+
     /// Event emitted when an interface model has been added.
     package_added<'a> {
         pub files: &'a mut Vec<(RpPackage, swift::Tokens)>,
@@ -1,12 +1,12 @@
 //! Backend for Swift
 
 use crate::flavored::*;
-use crate::{Options, EXT};
+use crate::{module, Options, EXT};
 use backend::PackageProcessor;
 use genco::prelude::*;
 use genco::tokens::{FormatInto, ItemStr};
 use reproto_core::errors::Result;
-use reproto_core::{Handle, Spanned};
+use reproto_core::{Handle, Span, Spanned};
 use trans::{self, Packages, Translated};
 
 /// Documentation comments.
@@ -27,6 +27,29 @@ where
     }
 }
 
+/// An `@available(*, deprecated)` attribute, if the item is deprecated.
+pub(crate) struct Available<'a>(pub(crate) Option<&'a RpDeprecated>);
+
+impl<'a> FormatInto<Swift> for Available<'a> {
+    fn format_into(self, t: &mut swift::Tokens) {
+        let deprecated = match self.0 {
+            Some(deprecated) => deprecated,
+            None => return,
+        };
+
+        t.push();
+
+        match deprecated.message() {
+            Some(message) => {
+                quote_in!(*t => @available(*, deprecated, message: $(quoted(message))));
+            }
+            None => {
+                quote_in!(*t => @available(*, deprecated));
+            }
+        }
+    }
+}
+
 pub(crate) struct Compiler<'a> {
     pub(crate) env: &'a Translated<SwiftFlavor>,
     opt: Options,
@@ -48,6 +71,7 @@ impl<'a> Compiler<'a> {
         t: &mut swift::Tokens,
         name: &Name,
         comment: &[String],
+        deprecated: Option<&RpDeprecated>,
         fields: &[Spanned<Field>],
         extends: bool,
     ) -> Result<()> {
@@ -62,9 +86,11 @@ impl<'a> Compiler<'a> {
 
         quote_in! { *t =>
             $(Comments(comment))
+            $(Available(deprecated))
             public struct $(&name.name)$extends {
                 $(for field in fields join ($['\r']) {
                     $(Comments(&field.comment))
+                    $(Available(field.deprecated.as_ref()))
                     let $(field.safe_ident()): $(field.field_type())
                 })
 
@@ -81,9 +107,10 @@ impl<'a> Compiler<'a> {
         t: &mut swift::Tokens,
         name: &Name,
         comment: &[String],
+        deprecated: Option<&RpDeprecated>,
         fields: &[Spanned<Field>],
     ) -> Result<()> {
-        self.model_struct(t, name, comment, fields, true)?;
+        self.model_struct(t, name, comment, deprecated, fields, true)?;
 
         let mut container = Vec::new();
         self.opt.gen.type_added(&mut container, name, fields);
@@ -158,7 +185,13 @@ impl<'a> PackageProcessor<'a, SwiftFlavor> for Compiler<'a> {
     }
 
     fn process_type(&self, out: &mut Self::Out, body: &RpTypeBody) -> Result<()> {
-        self.model_type(out, &body.name, &body.comment, &body.fields)?;
+        self.model_type(
+            out,
+            &body.name,
+            &body.comment,
+            body.deprecated.as_ref(),
+            &body.fields,
+        )?;
 
         Ok(())
     }
@@ -174,6 +207,7 @@ impl<'a> PackageProcessor<'a, SwiftFlavor> for Compiler<'a> {
                 o,
                 &body.name,
                 &body.comment,
+                body.deprecated.as_ref(),
                 &body.fields,
                 false,
             )?)
@@ -189,9 +223,11 @@ impl<'a> PackageProcessor<'a, SwiftFlavor> for Compiler<'a> {
         self.opt.gen.enum_added(&mut containers, &body.name, body);
 
         quote_in! { *out =>
+            $(Available(body.deprecated.as_ref()))
             public enum $(&body.name.name) {
                 $(for v in &body.variants join ($['\r']) {
                     $(Comments(v.comment))
+                    $(Available(v.deprecated))
                     case $(v.ident())
                 })
             }
@@ -211,8 +247,10 @@ impl<'a> PackageProcessor<'a, SwiftFlavor> for Compiler<'a> {
 
         quote_in! { *out =>
             $(Comments(&body.comment))
+            $(Available(body.deprecated.as_ref()))
             public enum $(body.name.name.clone()) {
                 $(for sub_type in &body.sub_types join ($['\r']) {
+                    $(Available(sub_type.deprecated.as_ref()))
                     case $(&sub_type.ident)($(sub_type.name.name.clone()))
                 })
 
@@ -230,10 +268,79 @@ impl<'a> PackageProcessor<'a, SwiftFlavor> for Compiler<'a> {
                     .collect::<Vec<_>>();
 
                 o.line();
-                self.model_type(o, &sub_type.name, &sub_type.comment, &fields)?;
+                self.model_type(
+                    o,
+                    &sub_type.name,
+                    &sub_type.comment,
+                    sub_type.deprecated.as_ref(),
+                    &fields,
+                )?;
             })
         };
 
         Ok(())
     }
+
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody) -> Result<()> {
+        quote_in! { *out =>
+            $(Comments(&body.comment))
+            $(Available(body.deprecated.as_ref()))
+            public let $(&body.name): $(&body.ty) = $(literal(&body.value))
+        }
+
+        Ok(())
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody) -> Result<()> {
+        if !self.opt.newtype {
+            quote_in! { *out =>
+                $(Comments(&body.comment))
+                $(Available(body.deprecated.as_ref()))
+                public typealias $(&body.name.name) = $(&body.ty)
+            }
+
+            return Ok(());
+        }
+
+        let field = Spanned::new(
+            Field::newtype(body.ty.clone(), body.validate.clone()),
+            Span::empty(),
+        );
+
+        let mut containers = Vec::new();
+        self.opt
+            .gen
+            .alias_added(&mut containers, &body.name, &field);
+
+        quote_in! { *out =>
+            $(Comments(&body.comment))
+            $(Available(body.deprecated.as_ref()))
+            public struct $(&body.name.name) {
+                let $(field.safe_ident()): $(field.field_type())
+            }
+
+            $(for c in containers join ($['\n']) => $c)
+        }
+
+        Ok(())
+    }
+
+    fn process_service(&self, out: &mut Self::Out, body: &RpServiceBody) -> Result<()> {
+        if self.opt.grpc {
+            for e in &body.endpoints {
+                module::grpc::check(e)?;
+            }
+        }
+
+        let mut containers = Vec::new();
+        self.opt
+            .gen
+            .service_added(&mut containers, &body.name, body);
+
+        quote_in! { *out =>
+            $(for c in containers join ($['\n']) => $c)
+        }
+
+        Ok(())
+    }
 }
@@ -10,7 +10,7 @@ use naming::Naming;
 use reproto_core::errors::Result;
 use reproto_core::{
     CoreFlavor, Diagnostics, Flavor, FlavorField, FlavorTranslator, PackageTranslator,
-    RpNumberKind, RpNumberType, RpStringType, Spanned, Translate, Translator,
+    RpNumberKind, RpNumberType, RpStringType, RpValidate, Spanned, Translate, Translator,
 };
 use std::collections::HashMap;
 use std::fmt;
@@ -211,6 +211,102 @@ impl Field {
             })
         }
     }
+
+    /// The literal for the default value of the field, if it has one.
+    pub(crate) fn default_value(&self) -> Option<swift::Tokens> {
+        self.inner.default.as_ref().map(literal)
+    }
+
+    /// Build checks for the validation rules of the field against the given value.
+    ///
+    /// `error` builds the expression which is thrown when a check fails.
+    pub(crate) fn validate<'f, E>(
+        &'f self,
+        value: swift::Tokens,
+        error: E,
+    ) -> impl FormatInto<Swift> + 'f
+    where
+        E: 'f + Fn(String) -> swift::Tokens,
+    {
+        from_fn(move |t| {
+            let validate = match &self.inner.validate {
+                Some(validate) => validate,
+                None => return,
+            };
+
+            let mut checks = Vec::new();
+
+            match validate {
+                RpValidate::Number(validate) => {
+                    if let Some(min) = &validate.min {
+                        checks.push(quote! {
+                            if $(&value) < $(min.to_string()) {
+                                throw $(error(format!("{}: must be greater than or equal to {}", self.ident, min)))
+                            }
+                        });
+                    }
+
+                    if let Some(max) = &validate.max {
+                        checks.push(quote! {
+                            if $(&value) > $(max.to_string()) {
+                                throw $(error(format!("{}: must be less than or equal to {}", self.ident, max)))
+                            }
+                        });
+                    }
+                }
+                RpValidate::String(validate) => {
+                    if let Some(pattern) = &validate.pattern {
+                        let pattern = pattern.to_string();
+                        let ns_string = swift::import("Foundation", "NSString");
+
+                        checks.push(quote! {
+                            if $(&value).range(of: $(quoted(format!("^(?:{})\\z", pattern))), options: $ns_string.CompareOptions.regularExpression) == nil {
+                                throw $(error(format!("{}: must match pattern `{}`", self.ident, pattern)))
+                            }
+                        });
+                    }
+
+                    if let Some(min_length) = validate.min_length {
+                        checks.push(quote! {
+                            if $(&value).unicodeScalars.count < $(min_length.to_string()) {
+                                throw $(error(format!("{}: must be at least {} characters long", self.ident, min_length)))
+                            }
+                        });
+                    }
+
+                    if let Some(max_length) = validate.max_length {
+                        checks.push(quote! {
+                            if $(&value).unicodeScalars.count > $(max_length.to_string()) {
+                                throw $(error(format!("{}: must be at most {} characters long", self.ident, max_length)))
+                            }
+                        });
+                    }
+                }
+            }
+
+            quote_in! { *t =>
+                $(for c in checks join ($['\r']) => $c)
+            }
+        })
+    }
+}
+
+/// Build a literal value.
+pub(crate) fn literal(literal: &RpLiteral) -> swift::Tokens {
+    match literal {
+        RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+        RpLiteral::Number(number) => quote!($(number.to_string())),
+        RpLiteral::Boolean(boolean) => quote!($(boolean.to_string())),
+    }
+}
+
+impl Field {
+    /// Build the field holding the value of a newtype wrapper.
+    pub(crate) fn newtype(ty: Type, validate: Option<RpValidate>) -> Self {
+        let mut inner = RpField::new("value", ty);
+        inner.validate = validate;
+        Field { inner }
+    }
 }
 
 impl FlavorField for Field {
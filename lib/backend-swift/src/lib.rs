@@ -27,6 +27,10 @@ impl Lang for SwiftLang {
         Some(format!("// {}", input))
     }
 
+    fn endpoint_ident_naming(&self) -> Option<Box<dyn naming::Naming>> {
+        Some(Box::new(naming::to_lower_camel()))
+    }
+
     fn package_naming(&self) -> Option<Box<dyn naming::Naming>> {
         Some(Box::new(naming::to_upper_camel()))
     }
@@ -119,6 +123,7 @@ pub(crate) enum SwiftModule {
     Grpc,
     Simple,
     Codable,
+    Newtype,
 }
 
 impl TryFromToml for SwiftModule {
@@ -129,6 +134,7 @@ impl TryFromToml for SwiftModule {
             "grpc" => Grpc,
             "simple" => Simple,
             "codable" => Codable,
+            "newtype" => Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -142,6 +148,7 @@ impl TryFromToml for SwiftModule {
             "grpc" => Grpc,
             "simple" => Simple,
             "codable" => Codable,
+            "newtype" => Newtype,
             _ => return NoModule::illegal(path, id, value),
         };
 
@@ -154,6 +161,10 @@ pub(crate) struct Options {
     pub(crate) struct_model_extends: Vec<swift::Tokens>,
     /// The provided Any type that should be used in structs.
     pub(crate) any_type: Vec<(&'static str, Type)>,
+    /// Generate aliases as newtype wrappers.
+    pub(crate) newtype: bool,
+    /// Services are exposed over gRPC.
+    pub(crate) grpc: bool,
     pub(crate) gen: codegen::Generators,
 }
 
@@ -162,6 +173,8 @@ impl Options {
         Options {
             struct_model_extends: Vec::new(),
             any_type: Vec::new(),
+            newtype: false,
+            grpc: false,
             gen: codegen::Generators::default(),
         }
     }
@@ -170,6 +183,10 @@ impl Options {
 pub(crate) fn options(modules: Vec<SwiftModule>) -> Result<Options> {
     use self::SwiftModule::*;
 
+    if modules.iter().any(|m| matches!(m, Grpc)) && !modules.iter().any(|m| matches!(m, Codable)) {
+        return Err("grpc: the `codable` module is required to encode messages".into());
+    }
+
     let mut options = Options::new();
 
     for m in modules {
@@ -179,6 +196,7 @@ pub(crate) fn options(modules: Vec<SwiftModule>) -> Result<Options> {
             Grpc => Box::new(module::Grpc::new()),
             Simple => Box::new(module::Simple::new()),
             Codable => Box::new(module::Codable::new()),
+            Newtype => Box::new(module::Newtype::new()),
         };
 
         initializer.initialize(&mut options)?;
@@ -193,10 +211,13 @@ fn compile(handle: &dyn Handle, session: Session<CoreFlavor>, manifest: Manifest
 
     let packages = session.packages()?;
 
-    let session = session.translate(flavored::SwiftFlavorTranslator::new(
-        packages.clone(),
-        &options,
-    )?)?;
+    let session =
+        session
+            .with_newtypes(options.newtype)
+            .translate(flavored::SwiftFlavorTranslator::new(
+                packages.clone(),
+                &options,
+            )?)?;
 
     Compiler::new(&session, options, handle)?.compile(&packages)
 }
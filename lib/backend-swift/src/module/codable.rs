@@ -28,10 +28,12 @@ impl Initializer for Module {
     fn initialize(&self, opt: &mut Self::Options) -> Result<()> {
         let codegen = Rc::new(Codegen);
         opt.struct_model_extends.push(quote!(Codable));
+        opt.gen.type_added.push(codegen.clone());
         opt.gen.tuple_added.push(codegen.clone());
         opt.gen.struct_model_added.push(codegen.clone());
         opt.gen.enum_added.push(codegen.clone());
         opt.gen.interface_added.push(codegen.clone());
+        opt.gen.alias_added.push(codegen.clone());
         opt.gen.interface_model_added.push(codegen.clone());
         opt.gen.package_added.push(codegen.clone());
         opt.any_type.push(("codable", Type::local("AnyCodable")));
@@ -409,6 +411,12 @@ impl codegen::tuple_added::Codegen for Codegen {
                                 self.$(field.safe_ident()) = try values.decode($(&field.ty).self)
                             })
                         })
+
+                        $(for field in fields.iter().filter(|f| f.validate.is_some()) join ($['\r']) {
+                            $(validate(field, |m| quote! {
+                                DecodingError.dataCorruptedError(in: values, debugDescription: $(quoted(m)))
+                            }))
+                        })
                     }
                 }
             }
@@ -439,6 +447,98 @@ impl codegen::tuple_added::Codegen for Codegen {
     }
 }
 
+impl codegen::type_added::Codegen for Codegen {
+    fn generate(&self, e: codegen::type_added::Args<'_>) {
+        let codegen::type_added::Args {
+            container,
+            name,
+            fields,
+        } = e;
+
+        // NB: the synthesized decoder is only replaced if there is something to validate or default.
+        if fields
+            .iter()
+            .all(|f| f.validate.is_none() && f.default.is_none())
+        {
+            return;
+        }
+
+        container.push(quote! {
+            extension $name {
+                public init(from decoder: Decoder) throws {
+                    let values = try decoder.container(keyedBy: CodingKeys.self)
+
+                    $(for field in fields join ($['\r']) {
+                        $(match field.default_value() {
+                            _ if field.is_optional() => {
+                                self.$(field.safe_ident()) = try values.decodeIfPresent($(&field.ty).self, forKey: .$(field.safe_ident()))
+                            }
+                            Some(default) => {
+                                self.$(field.safe_ident()) = try values.decodeIfPresent($(&field.ty).self, forKey: .$(field.safe_ident())) ?? $default
+                            }
+                            None => {
+                                self.$(field.safe_ident()) = try values.decode($(&field.ty).self, forKey: .$(field.safe_ident()))
+                            }
+                        })
+                    })
+
+                    $(for field in fields.iter().filter(|f| f.validate.is_some()) join ($['\r']) {
+                        $(validate(field, |m| quote! {
+                            DecodingError.dataCorruptedError(forKey: .$(field.safe_ident()), in: values, debugDescription: $(quoted(m)))
+                        }))
+                    })
+                }
+            }
+        });
+    }
+}
+
+impl codegen::alias_added::Codegen for Codegen {
+    fn generate(&self, e: codegen::alias_added::Args<'_>) {
+        let codegen::alias_added::Args {
+            container,
+            name,
+            field,
+        } = e;
+
+        container.push(quote! {
+            extension $name: Decodable {
+                public init(from decoder: Decoder) throws {
+                    let values = try decoder.singleValueContainer()
+                    self.$(field.safe_ident()) = try values.decode($(&field.ty).self)
+
+                    $(validate(field, |m| quote! {
+                        DecodingError.dataCorruptedError(in: values, debugDescription: $(quoted(m)))
+                    }))
+                }
+            }
+
+            extension $name: Encodable {
+                public func encode(to encoder: Encoder) throws {
+                    var values = encoder.singleValueContainer()
+                    try values.encode(self.$(field.safe_ident()))
+                }
+            }
+        });
+    }
+}
+
+/// Validate a decoded field, which has been assigned to `self`.
+fn validate<'f, E>(field: &'f Field, error: E) -> impl FormatInto<Swift> + 'f
+where
+    E: 'f + Fn(String) -> swift::Tokens,
+{
+    quote_fn! {
+        $(if field.is_optional() {
+            if let value = self.$(field.safe_ident()) {
+                $(field.validate(quote!(value), error))
+            }
+        } else {
+            $(field.validate(quote!(self.$(field.safe_ident())), error))
+        })
+    }
+}
+
 impl codegen::enum_added::Codegen for Codegen {
     fn generate(&self, e: codegen::enum_added::Args<'_>) {
         let codegen::enum_added::Args {
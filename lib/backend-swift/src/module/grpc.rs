@@ -1,7 +1,19 @@
-//! gRPC module for Rust.
+//! gRPC module for Swift.
+//!
+//! Generates client and provider protocols for [grpc-swift], where messages are encoded as JSON
+//! through `Codable` instead of protobuf.
+//!
+//! [grpc-swift]: https://github.com/grpc/grpc-swift
 
+use crate::codegen;
+use crate::compiler::{Available, Comments};
+use crate::flavored::*;
 use crate::Options;
 use backend::Initializer;
+use genco::prelude::*;
+use naming::Naming;
+use reproto_core::errors::Result;
+use std::rc::Rc;
 
 pub(crate) struct Module {}
 
@@ -13,4 +25,473 @@ impl Module {
 
 impl Initializer for Module {
     type Options = Options;
+
+    fn initialize(&self, opt: &mut Self::Options) -> Result<()> {
+        let codegen = Rc::new(Codegen::new());
+        opt.gen.service_added.push(codegen.clone());
+        opt.gen.package_added.push(codegen.clone());
+        opt.grpc = true;
+        Ok(())
+    }
+}
+
+/// Check that the endpoint can be called over gRPC, where the request is the only message sent.
+///
+/// Arguments other than the request would otherwise be silently dropped.
+pub(crate) fn check(e: &RpEndpoint) -> Result<()> {
+    let request = e.request.as_ref().map(|r| r.ident.as_str());
+
+    let unsupported = e
+        .arguments
+        .iter()
+        .filter(|a| Some(a.ident.as_str()) != request)
+        .map(|a| a.ident.as_str())
+        .collect::<Vec<_>>();
+
+    if !unsupported.is_empty() {
+        return Err(format!(
+            "grpc: endpoint `{}` has arguments which are not part of the request: {}",
+            e.ident,
+            unsupported.join(", ")
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The shape of a single gRPC call.
+#[derive(Clone, Copy)]
+enum Shape {
+    Unary,
+    ServerStreaming,
+    ClientStreaming,
+    BidirectionalStreaming,
+}
+
+impl Shape {
+    fn new(e: &RpEndpoint) -> Self {
+        let request = e.request.iter().any(|r| r.channel.is_streaming());
+        let response = e.response.iter().any(|r| r.is_streaming());
+
+        match (request, response) {
+            (false, false) => Shape::Unary,
+            (false, true) => Shape::ServerStreaming,
+            (true, false) => Shape::ClientStreaming,
+            (true, true) => Shape::BidirectionalStreaming,
+        }
+    }
+
+    /// The kind of call, as used in the names of grpc-swift types and functions.
+    fn kind(self) -> &'static str {
+        match self {
+            Shape::Unary => "Unary",
+            Shape::ServerStreaming => "ServerStreaming",
+            Shape::ClientStreaming => "ClientStreaming",
+            Shape::BidirectionalStreaming => "BidirectionalStreaming",
+        }
+    }
+
+    /// If the server streams responses, in which case the client provides a response handler.
+    fn is_server_streaming(self) -> bool {
+        matches!(self, Shape::ServerStreaming | Shape::BidirectionalStreaming)
+    }
+
+    /// If the client streams requests, in which case no request is provided up front.
+    fn is_client_streaming(self) -> bool {
+        matches!(self, Shape::ClientStreaming | Shape::BidirectionalStreaming)
+    }
+}
+
+/// A single endpoint, as seen by gRPC.
+struct Method<'a> {
+    e: &'a RpEndpoint,
+    shape: Shape,
+    /// The payload type of requests.
+    request: swift::Tokens,
+    /// The payload type of responses.
+    response: swift::Tokens,
+}
+
+struct Codegen {
+    to_lower_snake: naming::ToLowerSnake,
+    grpc_client: swift::Import,
+    grpc_channel: swift::Import,
+    call_options: swift::Import,
+    call_handler_provider: swift::Import,
+    call_handler_context: swift::Import,
+    call_handler_factory: swift::Import,
+    grpc_call_handler: swift::Import,
+    grpc_status: swift::Import,
+    grpc_payload: swift::Import,
+    status_only_call_context: swift::Import,
+    unary_response_call_context: swift::Import,
+    streaming_response_call_context: swift::Import,
+    stream_event: swift::Import,
+    event_loop_future: swift::Import,
+    byte_buffer: swift::Import,
+    json_encoder: swift::Import,
+    json_decoder: swift::Import,
+    data: swift::Import,
+}
+
+impl Codegen {
+    fn new() -> Self {
+        Self {
+            to_lower_snake: naming::to_lower_snake(),
+            grpc_client: swift::import("GRPC", "GRPCClient"),
+            grpc_channel: swift::import("GRPC", "GRPCChannel"),
+            call_options: swift::import("GRPC", "CallOptions"),
+            call_handler_provider: swift::import("GRPC", "CallHandlerProvider"),
+            call_handler_context: swift::import("GRPC", "CallHandlerContext"),
+            call_handler_factory: swift::import("GRPC", "CallHandlerFactory"),
+            grpc_call_handler: swift::import("GRPC", "GRPCCallHandler"),
+            grpc_status: swift::import("GRPC", "GRPCStatus"),
+            grpc_payload: swift::import("GRPC", "GRPCPayload"),
+            status_only_call_context: swift::import("GRPC", "StatusOnlyCallContext"),
+            unary_response_call_context: swift::import("GRPC", "UnaryResponseCallContext"),
+            streaming_response_call_context: swift::import("GRPC", "StreamingResponseCallContext"),
+            stream_event: swift::import("GRPC", "StreamEvent"),
+            event_loop_future: swift::import("NIO", "EventLoopFuture"),
+            byte_buffer: swift::import("NIO", "ByteBuffer"),
+            json_encoder: swift::import("Foundation", "JSONEncoder"),
+            json_decoder: swift::import("Foundation", "JSONDecoder"),
+            data: swift::import("Foundation", "Data"),
+        }
+    }
+
+    fn utils_package(&self) -> RpPackage {
+        RpPackage::parse("reproto_grpc")
+    }
+
+    fn utils(&self) -> swift::Tokens {
+        quote! {
+            $(Comments(["Payload which encodes its value as JSON."]))
+            public struct JSONPayload<T: Codable>: $(&self.grpc_payload) {
+                public let value: T
+
+                public init(_ value: T) {
+                    self.value = value
+                }
+
+                public init(serializedByteBuffer: inout $(&self.byte_buffer)) throws {
+                    let bytes = serializedByteBuffer.readBytes(length: serializedByteBuffer.readableBytes) ?? []
+                    let decoder = $(&self.json_decoder)()
+                    decoder.dateDecodingStrategy = .iso8601
+                    self.value = try decoder.decode(T.self, from: $(&self.data)(bytes))
+                }
+
+                public func serialize(into buffer: inout $(&self.byte_buffer)) throws {
+                    let encoder = $(&self.json_encoder)()
+                    encoder.dateEncodingStrategy = .iso8601
+                    buffer.writeBytes(try encoder.encode(self.value))
+                }
+            }
+
+            $(Comments(["Message used for endpoints without a request or response."]))
+            public struct Empty: Codable {
+                public init() {
+                }
+            }
+        }
+    }
+
+    fn method<'a>(&self, e: &'a RpEndpoint) -> Method<'a> {
+        let request = match &e.request {
+            Some(request) => quote!(JSONPayload<$(request.channel.ty())>),
+            None => quote!(JSONPayload<Empty>),
+        };
+
+        let response = match &e.response {
+            Some(response) => quote!(JSONPayload<$(response.ty())>),
+            None => quote!(JSONPayload<Empty>),
+        };
+
+        Method {
+            e,
+            shape: Shape::new(e),
+            request,
+            response,
+        }
+    }
+
+    /// The arguments of the client function for an endpoint.
+    fn client_arguments<'a>(
+        &'a self,
+        m: &'a Method<'a>,
+        defaults: bool,
+    ) -> impl FormatInto<Swift> + 'a {
+        let Method {
+            e, shape, response, ..
+        } = m;
+
+        let mut args = Vec::<swift::Tokens>::new();
+
+        if !shape.is_client_streaming() {
+            args.push(match &e.request {
+                Some(request) => quote!(_ $(request.safe_ident()): $(request.channel.ty())),
+                None if defaults => quote!(_ request: Empty = Empty()),
+                None => quote!(_ request: Empty),
+            });
+        }
+
+        args.push(if defaults {
+            quote!(callOptions: $(&self.call_options)? = nil)
+        } else {
+            quote!(callOptions: $(&self.call_options)?)
+        });
+
+        if shape.is_server_streaming() {
+            args.push(quote!(handler: @escaping ($response) -> Void));
+        }
+
+        quote_fn!($(for a in args join (, ) => $a))
+    }
+
+    /// The return type of the client function for an endpoint.
+    fn client_return<'a>(&'a self, m: &'a Method<'a>) -> impl FormatInto<Swift> + 'a {
+        let Method {
+            shape,
+            request,
+            response,
+            ..
+        } = m;
+
+        quote_fn!($(shape.kind())Call<$request, $response>)
+    }
+
+    /// The implementation of the client function for an endpoint.
+    fn client_method<'a>(
+        &'a self,
+        path: &'a str,
+        m: &'a Method<'a>,
+    ) -> impl FormatInto<Swift> + 'a {
+        let Method { e, shape, .. } = m;
+
+        let mut args = vec![quote!(path: $(quoted(format!("/{}/{}", path, e.name()))))];
+
+        if !shape.is_client_streaming() {
+            args.push(match &e.request {
+                Some(request) => quote!(request: JSONPayload($(request.safe_ident()))),
+                None => quote!(request: JSONPayload(request)),
+            });
+        }
+
+        args.push(quote!(callOptions: callOptions ?? self.defaultCallOptions));
+
+        if shape.is_server_streaming() {
+            args.push(quote!(handler: handler));
+        }
+
+        quote_fn! {
+            $(Comments(&e.comment))
+            $(Available(e.deprecated.as_ref()))
+            public func $(e.safe_ident())($(self.client_arguments(m, true))) -> $(self.client_return(m)) {
+                return self.make$(shape.kind())Call($(for a in args join (, ) => $a))
+            }
+        }
+    }
+
+    /// The requirement in the provider protocol for an endpoint.
+    fn provider_method<'a>(&'a self, m: &'a Method<'a>) -> impl FormatInto<Swift> + 'a {
+        let Method {
+            e,
+            shape,
+            request,
+            response,
+        } = m;
+
+        let signature = match shape {
+            Shape::Unary => quote! {
+                (request: $request, context: $(&self.status_only_call_context)) -> $(&self.event_loop_future)<$response>
+            },
+            Shape::ServerStreaming => quote! {
+                (request: $request, context: $(&self.streaming_response_call_context)<$response>) -> $(&self.event_loop_future)<$(&self.grpc_status)>
+            },
+            Shape::ClientStreaming => quote! {
+                (context: $(&self.unary_response_call_context)<$response>) -> $(&self.event_loop_future)<($(&self.stream_event)<$request>) -> Void>
+            },
+            Shape::BidirectionalStreaming => quote! {
+                (context: $(&self.streaming_response_call_context)<$response>) -> $(&self.event_loop_future)<($(&self.stream_event)<$request>) -> Void>
+            },
+        };
+
+        quote_fn! {
+            $(Comments(&e.comment))
+            $(Available(e.deprecated.as_ref()))
+            func $(e.safe_ident())$signature
+        }
+    }
+
+    /// The case handling an endpoint when dispatching a method.
+    fn handle_method<'a>(&'a self, m: &'a Method<'a>) -> impl FormatInto<Swift> + 'a {
+        let Method { e, shape, .. } = m;
+
+        quote_fn! {
+            case $(quoted(e.name())):
+                return $(&self.call_handler_factory).make$(shape.kind())(callHandlerContext: callHandlerContext) { context in
+                    $(if shape.is_client_streaming() {
+                        return self.$(e.safe_ident())(context: context)
+                    } else {
+                        return { request in
+                            self.$(e.safe_ident())(request: request, context: context)
+                        }
+                    })
+                }
+        }
+    }
+}
+
+impl codegen::service_added::Codegen for Codegen {
+    fn generate(&self, e: codegen::service_added::Args<'_>) {
+        let codegen::service_added::Args {
+            container,
+            name,
+            body,
+        } = e;
+
+        // NB: packages are upper camel in Swift, so they are converted back to match the name of the
+        // service in other languages.
+        let path = body
+            .name
+            .package
+            .parts()
+            .map(|p| self.to_lower_snake.convert(p))
+            .chain(std::iter::once(body.ident.to_string()))
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let methods = body
+            .endpoints
+            .iter()
+            .map(|e| self.method(e))
+            .collect::<Vec<_>>();
+
+        let client_protocol = format!("{}ClientProtocol", name.name);
+        let client = format!("{}Client", name.name);
+        let provider = format!("{}Provider", name.name);
+
+        container.push(quote! {
+            $(Comments(&body.comment))
+            $(Available(body.deprecated.as_ref()))
+            public protocol $(&client_protocol): $(&self.grpc_client) {
+                $(for m in &methods join ($['\n']) {
+                    $(Available(m.e.deprecated.as_ref()))
+                    func $(m.e.safe_ident())($(self.client_arguments(m, false))) -> $(self.client_return(m))
+                })
+            }
+
+            extension $(&client_protocol) {
+                $(for m in &methods join ($['\n']) => $(self.client_method(&path, m)))
+            }
+
+            public final class $client: $(&client_protocol) {
+                public let channel: $(&self.grpc_channel)
+                public var defaultCallOptions: $(&self.call_options)
+
+                public init(channel: $(&self.grpc_channel), defaultCallOptions: $(&self.call_options) = $(&self.call_options)()) {
+                    self.channel = channel
+                    self.defaultCallOptions = defaultCallOptions
+                }
+            }
+
+            $(Comments(&body.comment))
+            $(Available(body.deprecated.as_ref()))
+            public protocol $(&provider): $(&self.call_handler_provider) {
+                $(for m in &methods join ($['\n']) => $(self.provider_method(m)))
+            }
+
+            extension $(&provider) {
+                public var serviceName: Substring {
+                    return $(quoted(path.as_str()))
+                }
+
+                public func handleMethod(_ methodName: Substring, callHandlerContext: $(&self.call_handler_context)) -> $(&self.grpc_call_handler)? {
+                    switch methodName {
+                    $(for m in &methods join ($['\r']) => $(self.handle_method(m)))
+                    default:
+                        return nil
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl codegen::package_added::Codegen for Codegen {
+    fn generate(&self, e: codegen::package_added::Args<'_>) {
+        e.files.push((self.utils_package(), self.utils()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SwiftModule;
+    use manifest::Manifest;
+    use reproto_core::errors::Result;
+    use reproto_core::{
+        CapturingFilesystem, EmptyResolver, Filesystem, Reported, RpPackage, RpVersionedPackage,
+        Source,
+    };
+    use std::any::Any;
+    use trans::Session;
+
+    fn compile(spec: &str) -> Result<()> {
+        let package = RpVersionedPackage::new(RpPackage::parse("test"), None);
+        let source = Source::bytes("test", spec.as_bytes().to_vec());
+
+        let mut reporter: Vec<Reported> = Vec::new();
+        let mut resolver = EmptyResolver;
+        let mut session = Session::new(None, &mut reporter, &mut resolver)?;
+        session.import_source(source, Some(package))?;
+
+        let modules: Vec<Box<dyn Any>> =
+            vec![Box::new(SwiftModule::Codable), Box::new(SwiftModule::Grpc)];
+
+        let manifest = Manifest {
+            modules: Some(modules),
+            ..Manifest::default()
+        };
+
+        let fs = CapturingFilesystem::new();
+        crate::compile(fs.filesystem().open_root(None)?.as_ref(), session, manifest)
+    }
+
+    #[test]
+    fn test_arguments_outside_of_request() {
+        let result = compile(
+            r#"
+            type Entry {}
+
+            service MyService {
+              unary(request: Entry) -> Entry;
+
+              #[http(path = "/{id}/{name}")]
+              get(id: u32, name: string) -> Entry;
+            }
+            "#,
+        );
+
+        let e = result.expect_err("arguments outside of the request should be rejected");
+
+        assert_eq!(
+            "grpc: endpoint `get` has arguments which are not part of the request: id, name",
+            e.message()
+        );
+    }
+
+    #[test]
+    fn test_request_only() -> Result<()> {
+        compile(
+            r#"
+            type Entry {}
+
+            service MyService {
+              unknown();
+              unary(request: Entry) -> Entry;
+              streaming(request: stream Entry) -> stream Entry;
+            }
+            "#,
+        )
+    }
 }
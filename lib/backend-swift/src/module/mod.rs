@@ -1,7 +1,9 @@
 mod codable;
-mod grpc;
+pub(crate) mod grpc;
+mod newtype;
 pub(crate) mod simple;
 
 pub(crate) use self::codable::Module as Codable;
 pub(crate) use self::grpc::Module as Grpc;
+pub(crate) use self::newtype::Module as Newtype;
 pub(crate) use self::simple::Module as Simple;
@@ -8,7 +8,7 @@ use backend::Initializer;
 use genco::prelude::*;
 use genco::tokens::ItemStr;
 use reproto_core::errors::Result;
-use reproto_core::Spanned;
+use reproto_core::{FlavorField, Spanned};
 use std::rc::Rc;
 
 static NUMERICS: [&str; 6] = ["Int", "UInt", "Int32", "Int64", "UInt32", "UInt64"];
@@ -32,6 +32,7 @@ impl Initializer for Module {
         opt.gen.tuple_added.push(codegen.clone());
         opt.gen.enum_added.push(codegen.clone());
         opt.gen.interface_added.push(codegen.clone());
+        opt.gen.alias_added.push(codegen.clone());
         opt.gen.package_added.push(codegen.clone());
         Ok(())
     }
@@ -76,19 +77,41 @@ impl Codegen {
 
         let (name, index) = index(field, ItemStr::from("json"));
 
+        let invalid = |m: String| quote!(SerializationError.invalid($(quoted(m))));
+
         quote_fn! {
-            $(if field.is_optional() {
-                var $ident: $(field.field_type()) = Optional.none
+            $(match field.default_value() {
+                _ if field.is_optional() => {
+                    var $ident: $(field.field_type()) = Optional.none
+
+                    if let value = $index {
+                        $ident = Optional.some($(field.ty.decode_value(name, quote!(value))))
+                    }
 
-                if let value = $index {
-                    $ident = Optional.some($(field.ty.decode_value(name, quote!(value))))
+                    $(if field.validate.is_some() {
+                        if let value = $ident {
+                            $(field.validate(quote!(value), invalid))
+                        }
+                    })
                 }
-            } else {
-                guard let $(&*f_ident) = $index else {
-                    throw SerializationError.missing($(quoted(name.clone())))
+                Some(default) => {
+                    var $ident: $(field.field_type()) = $default
+
+                    if let value = $index {
+                        $ident = $(field.ty.decode_value(name, quote!(value)))
+                    }
+
+                    $(field.validate(quote!($ident), invalid))
                 }
+                None => {
+                    guard let $(&*f_ident) = $index else {
+                        throw SerializationError.missing($(quoted(name.clone())))
+                    }
+
+                    let $ident = $(field.ty.decode_value(name, quote!($(&*f_ident))))
 
-                let $ident = $(field.ty.decode_value(name, quote!($(&*f_ident))))
+                    $(field.validate(quote!($ident), invalid))
+                }
             })
         }
     }
@@ -339,6 +362,35 @@ impl codegen::type_added::Codegen for Codegen {
     }
 }
 
+impl codegen::alias_added::Codegen for Codegen {
+    fn generate(&self, e: codegen::alias_added::Args<'_>) {
+        let codegen::alias_added::Args {
+            container,
+            name,
+            field,
+        } = e;
+
+        let ident = field.safe_ident();
+        let invalid = |m: String| quote!(SerializationError.invalid($(quoted(m))));
+
+        container.push(quote! {
+            public extension $name {
+                static func decode(json: Any) throws -> $name {
+                    let $ident = $(field.ty.decode_value(ItemStr::from(field.name()), quote!(json)))
+
+                    $(field.validate(quote!($ident), invalid))
+
+                    return $name($ident: $ident)
+                }
+
+                func encode() throws -> Any {
+                    return $(field.ty.encode_value(field.name(), quote!(self.$ident)))
+                }
+            }
+        });
+    }
+}
+
 impl codegen::tuple_added::Codegen for Codegen {
     fn generate(&self, e: codegen::tuple_added::Args<'_>) {
         let codegen::tuple_added::Args {
@@ -550,7 +602,7 @@ impl codegen::interface_added::Codegen for Codegen {
             name: &'f Name,
             body: &'f RpInterfaceBody,
         ) -> impl FormatInto<Swift> + 'f {
-            let optional = quoted_tags(body.fields.iter().filter(|f| f.is_optional()));
+            let optional = quoted_tags(body.fields.iter().filter(|f| !f.is_discriminating()));
 
             return quote_fn! {
                 static func decode(json: Any) throws -> $name {
@@ -558,12 +610,12 @@ impl codegen::interface_added::Codegen for Codegen {
 
                     let keys = Set(json.keys).subtracting($optional)
                     $(ref o => for sub_type in &body.sub_types {
-                        let tags = quoted_tags(sub_type.fields.iter().filter(|f| f.is_optional()));
+                        let tags = quoted_tags(sub_type.fields.iter().filter(|f| !f.is_discriminating()));
                         let req = quoted_tags(
                             body.fields
                                 .iter()
                                 .chain(sub_type.fields.iter())
-                                .filter(|f| f.is_required()),
+                                .filter(|f| f.is_discriminating()),
                         );
 
                         quote_in! { *o =>
@@ -0,0 +1,659 @@
+use crate::flavored::*;
+use crate::utils::{is_defined, is_not_defined};
+use crate::{FileSpec, Options, EXT};
+use backend::PackageProcessor;
+use genco::prelude::*;
+use genco::tokens::FormatInto;
+use relative_path::RelativePathBuf;
+use reproto_core::errors::Result;
+use reproto_core::{Handle, RpValidate, Span, Spanned};
+use std::rc::Rc;
+use trans::Translated;
+
+pub(crate) struct Compiler<'a> {
+    pub(crate) env: &'a Translated<TypeScriptFlavor>,
+    options: Options,
+    handle: &'a dyn Handle,
+}
+
+impl<'a> Compiler<'a> {
+    pub(crate) fn new(
+        env: &'a Translated<TypeScriptFlavor>,
+        options: Options,
+        handle: &'a dyn Handle,
+    ) -> Self {
+        Self {
+            env,
+            options,
+            handle,
+        }
+    }
+
+    pub(crate) fn compile(&self) -> Result<()> {
+        use genco::fmt;
+
+        let files = self.do_populate_files(|_, new, out| {
+            if !new {
+                out.0.line();
+            }
+
+            Ok(())
+        })?;
+
+        let handle = self.handle();
+
+        for (package, out) in files {
+            let full_path = self.setup_module_path(&package)?;
+
+            log::debug!("+module: {}", full_path);
+
+            let path = RelativePathBuf::from(format!("{}.{}", package.join("/"), EXT));
+
+            let mut w = fmt::IoWriter::new(handle.create(&full_path)?);
+            let mut config = js::Config::default();
+
+            if let Some(parent) = path.parent() {
+                config = config.with_module_path(parent.to_owned());
+            }
+
+            let fmt =
+                fmt::Config::from_lang::<JavaScript>().with_indentation(fmt::Indentation::Space(2));
+
+            out.0.format_file(&mut w.as_formatter(&fmt), &config)?;
+        }
+
+        Ok(())
+    }
+
+    /// The declared type of a field.
+    fn field_type(field: &RpField) -> js::Tokens {
+        if field.is_optional() {
+            quote!($(&field.ty) | null)
+        } else {
+            quote!($(&field.ty))
+        }
+    }
+
+    /// Build a function that throws an exception if the given value `toks` is None.
+    fn throw_if_null<T>(&self, out: &mut js::Tokens, toks: T, field: &Spanned<RpField>)
+    where
+        T: Copy + FormatInto<JavaScript>,
+    {
+        quote_in! { *out =>
+            if ($(is_not_defined(toks))) {
+                throw new Error($(quoted(format!("{}: is a required field", field.name()))));
+            }
+        }
+    }
+
+    fn encode_method<'el, I>(&self, out: &mut js::Tokens, fields: I, extra: Option<js::Tokens>)
+    where
+        I: IntoIterator<Item = &'el Spanned<RpField>>,
+    {
+        quote_in! { *out =>
+            encode(): {[key: string]: unknown} {
+                const data: {[key: string]: unknown} = {};
+
+                $(if let Some(extra) = extra {
+                    $extra
+                })
+
+                $(for field in fields join ($['\n']) {
+                    $(ref out => {
+                        let field_toks = quote!(this.$(field.safe_ident()));
+
+                        if field.is_optional() {
+                            quote_in! { *out =>
+                                if ($(is_defined(&field_toks))) {
+                                    data[$(quoted(field.name()))] = $(field.ty.encode(field_toks));
+                                }
+                            }
+                        } else {
+                            quote_in! { *out =>
+                                $(ref o => self.throw_if_null(o, &field_toks, field))
+
+                                data[$(quoted(field.name()))] = $(field.ty.encode(field_toks));
+                            }
+                        }
+                    })
+                })
+
+                return data;
+            }
+        }
+    }
+
+    fn encode_tuple_method<'el, I>(&self, out: &mut js::Tokens, fields: I)
+    where
+        I: IntoIterator<Item = &'el Spanned<RpField>> + Clone,
+    {
+        let mut values = Vec::new();
+
+        quote_in! { *out =>
+            encode(): [$(for field in fields.clone() join (, ) => $(field.ty.encoded()))] {
+                $(for field in fields join ($['\n']) {
+                    $(ref out => {
+                        let access = quote!(this.$(field.safe_ident()));
+                        self.throw_if_null(out, &access, field);
+                        values.push(field.ty.encode(access));
+                    })
+                })
+
+                return [$(for v in values join (, ) => $v)];
+            }
+        }
+    }
+
+    fn decode_method<'el, F, I, O>(&self, out: &mut js::Tokens, fields: I, name: &Name, var_fn: F)
+    where
+        F: Fn(usize, &'el Spanned<RpField>) -> O,
+        I: IntoIterator<Item = &'el Spanned<RpField>>,
+        O: FormatInto<JavaScript> + Copy,
+    {
+        let mut arguments = Vec::<Rc<String>>::new();
+
+        quote_in! { *out =>
+            static decode(data: any): $name {
+                $(for (i, field) in fields.into_iter().enumerate() join ($['\n']) {
+                    $(ref o {
+                        let var_name = &Rc::new(format!("v_{}", field.ident));
+                        arguments.push(var_name.clone());
+
+                        let var = var_fn(i, field);
+
+                        if field.is_optional() {
+                            quote_in! { *o =>
+                                let $var_name = data[$var];
+
+                                if ($(is_defined(var_name))) {
+                                    $(ref t => field.ty.decode(t, quote!($var_name)))
+                                    $(ref t => self.validate(t, field, var_name))
+                                } else {
+                                    $var_name = null;
+                                }
+                            }
+                        } else if let Some(default) = &field.default {
+                            quote_in! { *o =>
+                                let $var_name = data[$var];
+
+                                if ($(is_not_defined(var_name))) {
+                                    $var_name = $(self.literal(default));
+                                }
+
+                                $(ref t => field.ty.decode(t, quote!($var_name)))
+                                $(ref t => self.validate(t, field, var_name))
+                            }
+                        } else {
+                            quote_in! { *o =>
+                                let $var_name = data[$var];
+
+                                if ($(is_not_defined(var_name))) {
+                                    throw new Error($var + ": required field");
+                                }
+
+                                $(ref t => field.ty.decode(t, quote!($var_name)))
+                                $(ref t => self.validate(t, field, var_name))
+                            }
+                        }
+                    })
+                })
+
+                return new $name($(for a in arguments join (, ) => $a));
+            }
+        }
+    }
+
+    /// Build a literal value.
+    fn literal(&self, literal: &RpLiteral) -> js::Tokens {
+        match literal {
+            RpLiteral::String(string) => quote!($(quoted(string.as_str()))),
+            RpLiteral::Number(number) => quote!($(number.to_string())),
+            RpLiteral::Boolean(boolean) => quote!($(boolean.to_string())),
+        }
+    }
+
+    /// Build checks for the validation rules of the given field.
+    fn validate(&self, out: &mut js::Tokens, field: &RpField, var: &Rc<String>) {
+        let validate = match &field.validate {
+            Some(validate) => validate,
+            None => return,
+        };
+
+        match validate {
+            RpValidate::Number(validate) => {
+                if let Some(min) = &validate.min {
+                    quote_in! { *out =>
+                        $['\n']
+                        if ($var < $(min.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be greater than or equal to {}", field.ident, min))));
+                        }
+                    }
+                }
+
+                if let Some(max) = &validate.max {
+                    quote_in! { *out =>
+                        $['\n']
+                        if ($var > $(max.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be less than or equal to {}", field.ident, max))));
+                        }
+                    }
+                }
+            }
+            RpValidate::String(validate) => {
+                if let Some(pattern) = &validate.pattern {
+                    let pattern = pattern.to_string();
+
+                    quote_in! { *out =>
+                        $['\n']
+                        if (!new RegExp($(quoted(format!("^(?:{})$", pattern)))).test($var)) {
+                            throw new Error($(quoted(format!("{}: must match pattern `{}`", field.ident, pattern))));
+                        }
+                    }
+                }
+
+                if let Some(min_length) = validate.min_length {
+                    quote_in! { *out =>
+                        $['\n']
+                        if (Array.from($var).length < $(min_length.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be at least {} characters long", field.ident, min_length))));
+                        }
+                    }
+                }
+
+                if let Some(max_length) = validate.max_length {
+                    quote_in! { *out =>
+                        $['\n']
+                        if (Array.from($var).length > $(max_length.to_string())) {
+                            throw new Error($(quoted(format!("{}: must be at most {} characters long", field.ident, max_length))));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn field_by_name<'o>(
+        _i: usize,
+        field: &'o Spanned<RpField>,
+    ) -> impl FormatInto<JavaScript> + 'o + Copy {
+        quoted(field.name())
+    }
+
+    fn field_by_index(i: usize, _field: &Spanned<RpField>) -> impl FormatInto<JavaScript> + Copy {
+        display(i)
+    }
+
+    /// Build the field declarations and the constructor of a class.
+    fn build_fields<'el, I>(&self, out: &mut js::Tokens, fields: I)
+    where
+        I: IntoIterator<Item = &'el Spanned<RpField>>,
+    {
+        let mut declarations = Vec::new();
+        let mut arguments = Vec::new();
+        let mut assign = Vec::new();
+
+        for field in fields {
+            let ty = Self::field_type(field);
+            declarations.push(quote!($(field.safe_ident()): $(&ty);));
+            arguments.push(quote!($(field.safe_ident()): $ty));
+            assign.push(quote!(this.$(field.safe_ident()) = $(field.safe_ident());));
+        }
+
+        quote_in! { *out =>
+            $(if !declarations.is_empty() {
+                $(for d in declarations join ($['\r']) => $d)
+                $['\n']
+            })
+            constructor($(for a in arguments join (, ) => $a)) {
+                $['\r']$(for a in assign join ($['\r']) => $a)
+            }
+        }
+    }
+
+    /// Build the path of an HTTP endpoint, appended to the base url of the client.
+    fn build_path(&self, path: &RpPathSpec) -> js::Tokens {
+        let mut parts = vec![quote!(this.url)];
+        let mut segment = String::new();
+
+        for step in &path.steps {
+            segment.push('/');
+
+            for part in &step.parts {
+                match part {
+                    RpPathPart::Segment(s) => segment.push_str(s),
+                    RpPathPart::Variable(arg) => {
+                        parts.push(quote!($(quoted(std::mem::take(&mut segment)))));
+                        parts.push(quote!(encodeURIComponent(String($(arg.safe_ident())))));
+                    }
+                }
+            }
+        }
+
+        if !segment.is_empty() {
+            parts.push(quote!($(quoted(segment))));
+        }
+
+        quote!($(for p in parts join ( + ) => $p))
+    }
+
+    /// Build the client method calling a single HTTP endpoint.
+    fn endpoint_method(&self, out: &mut js::Tokens, e: &TsEndpoint, http: &RpEndpointHttp1) {
+        let response = match (&http.response, &e.http.accept) {
+            (Some(_), RpAccept::Text) => quote!(string),
+            (Some(ty), RpAccept::Json) => quote!($ty),
+            (None, _) => quote!(void),
+        };
+
+        let body = e.request.as_ref().map(|req| {
+            let ty = req.channel.ty();
+            ty.encode(quote!($(req.safe_ident())))
+        });
+
+        quote_in! { *out =>
+            async $(e.safe_ident())($(for a in &e.arguments join (, ) => $(a.safe_ident()): $(a.channel.ty()))): Promise<$response> {
+                const url_ = $(self.build_path(&http.path));
+
+                const res_ = await fetch(url_, {
+                    method: $(quoted(http.method.as_str())),
+                    $(if let Some(body) = body {
+                        headers: {"Content-Type": "application/json"},
+                        body: JSON.stringify($body),
+                    })
+                });
+
+                if (!res_.ok) {
+                    throw new Error("request failed: " + res_.status + " " + res_.statusText);
+                }
+                $(match (&http.response, &e.http.accept) {
+                    (Some(ty), RpAccept::Json) => {
+                        $['\n']
+                        let data_ = await res_.json();
+                        $['\n']
+                        $(ref t => ty.decode(t, quote!(data_)))
+                        $['\n']
+                        return data_;
+                    }
+                    (Some(_), RpAccept::Text) => {
+                        $['\n']
+                        return await res_.text();
+                    }
+                    (None, _) => {}
+                })
+            }
+        }
+    }
+}
+
+impl<'a> PackageProcessor<'a, TypeScriptFlavor> for Compiler<'a> {
+    type Out = FileSpec;
+    type DeclIter = trans::translated::DeclIter<'a, TypeScriptFlavor>;
+
+    fn ext(&self) -> &str {
+        EXT
+    }
+
+    fn decl_iter(&self) -> Self::DeclIter {
+        self.env.decl_iter()
+    }
+
+    fn handle(&self) -> &dyn Handle {
+        self.handle
+    }
+
+    fn process_tuple(&self, out: &mut Self::Out, body: &RpTupleBody) -> Result<()> {
+        quote_in! { out.0 =>
+            export class $(&body.name) {
+                $(ref o => self.build_fields(o, &body.fields))
+
+                $(ref o => self.decode_method(o, &body.fields, &body.name, Self::field_by_index))
+
+                $(ref o => self.encode_tuple_method(o, &body.fields))
+
+                $(if backend::code_contains!(&body.codes, RpContext::TypeScript) {
+                    $(ref o => backend::code_in!(o, &body.codes, RpContext::TypeScript))
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_enum(&self, out: &mut Self::Out, body: &RpEnumBody) -> Result<()> {
+        quote_in! { out.0 =>
+            export enum $(&body.name) {
+                $(for v in body.variants.iter() join ($['\r']) {
+                    $(v.ident()) = $(match v.value {
+                        RpVariantValue::String(string) => $(quoted(string)),
+                        RpVariantValue::Number(number) => $(display(number)),
+                    }),
+                })
+            }
+
+            export namespace $(&body.name) {
+                export function decode(data: any): $(&body.name) {
+                    switch (data) {
+                    $(for v in body.variants.iter() join ($['\r']) {
+                        case $(match v.value {
+                            RpVariantValue::String(string) => $(quoted(string)),
+                            RpVariantValue::Number(number) => $(display(number)),
+                        }):
+                            return $(&body.name).$(v.ident());
+                    })
+                    }
+
+                    throw new Error("no value matching: " + data);
+                }
+
+                $(if backend::code_contains!(&body.codes, RpContext::TypeScript) {
+                    $(ref o => backend::code_in!(o, &body.codes, RpContext::TypeScript))
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_type(&self, out: &mut Self::Out, body: &RpTypeBody) -> Result<()> {
+        quote_in! { out.0 =>
+            export class $(&body.name) {
+                $(ref o => self.build_fields(o, &body.fields))
+
+                $(ref o => self.decode_method(o, &body.fields, &body.name, Self::field_by_name))
+
+                $(ref o => self.encode_method(o, &body.fields, None))
+
+                $(if backend::code_contains!(&body.codes, RpContext::TypeScript) {
+                    $(ref o => backend::code_in!(o, &body.codes, RpContext::TypeScript))
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_interface(&self, out: &mut Self::Out, body: &RpInterfaceBody) -> Result<()> {
+        quote_in! { out.0 =>
+            export type $(&body.name) = $(if body.sub_types.is_empty() {
+                never
+            } else {
+                $(for sub_type in &body.sub_types join ( | ) => $(&sub_type.name))
+            });
+
+            export namespace $(&body.name) {
+                $(match &body.sub_type_strategy {
+                    RpSubTypeStrategy::Tagged { tag, .. } => {
+                        $(ref o => decode(o, body, tag.as_str()))
+                    }
+                    RpSubTypeStrategy::Untagged => {
+                        $(ref o => decode_untagged(o, body))
+                    }
+                })
+
+                $(if backend::code_contains!(&body.codes, RpContext::TypeScript) {
+                    $(ref o => backend::code_in!(o, &body.codes, RpContext::TypeScript))
+                })
+            }
+
+            $(for sub_type in &body.sub_types {
+                export class $(&sub_type.name) {
+                    $(if let RpSubTypeStrategy::Tagged { tag, .. } = &body.sub_type_strategy {
+                        readonly $(quoted(tag)): $(quoted(sub_type.name())) = $(quoted(sub_type.name()));
+                        $['\n']
+                    })
+                    $(ref o => self.build_fields(o, body.fields.iter().chain(sub_type.fields.iter())))
+
+                    $(ref o => {
+                        self.decode_method(
+                            o,
+                            body.fields.iter().chain(sub_type.fields.iter()),
+                            &sub_type.name,
+                            Self::field_by_name,
+                        )
+                    })
+
+                    $(match &body.sub_type_strategy {
+                        RpSubTypeStrategy::Tagged { tag, .. } => {
+                            $(ref o => {
+                                self.encode_method(
+                                    o,
+                                    body.fields.iter().chain(sub_type.fields.iter()),
+                                    Some(quote!(data[$(quoted(tag))] = $(quoted(sub_type.name()));))
+                                )
+                            })
+                        }
+                        RpSubTypeStrategy::Untagged => {
+                            $(ref o => {
+                                self.encode_method(o, body.fields.iter().chain(sub_type.fields.iter()), None)
+                            })
+                        }
+                    })
+
+                    $(if backend::code_contains!(&sub_type.codes, RpContext::TypeScript) {
+                        $(ref o => backend::code_in!(o, &sub_type.codes, RpContext::TypeScript))
+                    })
+                }
+            })
+        }
+
+        return Ok(());
+
+        fn decode(out: &mut js::Tokens, body: &RpInterfaceBody, tag: &str) {
+            quote_in! { *out =>
+                export function decode(data: any): $(&body.name) {
+                    const f_tag = data[$(quoted(tag))];
+
+                    if ($(is_not_defined("f_tag"))) {
+                        throw new Error($(quoted(format!("missing tag field: {}", tag))));
+                    }
+
+                    $(for sub_type in body.sub_types.iter() {
+                        if (f_tag === $(quoted(sub_type.name()))) {
+                            return $(&sub_type.name).decode(data);
+                        }
+                    })
+
+                    throw new Error("bad sub-type: " + f_tag);
+                }
+            }
+        }
+
+        fn decode_untagged(out: &mut js::Tokens, body: &RpInterfaceBody) {
+            quote_in! { *out =>
+                export function decode(data: any): $(&body.name) {
+                    const keys: {[key: string]: boolean} = {};
+
+                    for (const k in data) {
+                        keys[k] = true;
+                    }
+
+                    $(for sub_type in body.sub_types.iter() {
+                        if ($(for f in sub_type.discriminating_fields() join ( && ) => ($(quoted(f.name())) in keys))) {
+                            return $(&sub_type.name).decode(data);
+                        }
+                    })
+
+                    throw new Error("no legal field combinations found");
+                }
+            }
+        }
+    }
+
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody) -> Result<()> {
+        quote_in! { out.0 =>
+            export const $(&body.name): $(&body.ty) = $(self.literal(&body.value));
+        }
+
+        Ok(())
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody) -> Result<()> {
+        if !self.options.newtype {
+            quote_in! { out.0 =>
+                export type $(&body.name) = $(&body.ty);
+            }
+
+            return Ok(());
+        }
+
+        let mut field = RpField::new("value", body.ty.clone());
+        field.validate = body.validate.clone();
+        let field = &Spanned::new(field, Span::empty());
+        let var_name = &Rc::new(String::from("v_value"));
+        let access = &quote!(this.value);
+
+        quote_in! { out.0 =>
+            export class $(&body.name) {
+                $(ref o => self.build_fields(o, [field]))
+
+                static decode(data: any): $(&body.name) {
+                    let $var_name = data;
+
+                    $(ref t => field.ty.decode(t, quote!($var_name)))
+                    $(ref t => self.validate(t, field, var_name))
+
+                    return new $(&body.name)($var_name);
+                }
+
+                encode(): $(field.ty.encoded()) {
+                    $(ref o => self.throw_if_null(o, access, field))
+
+                    return $(field.ty.encode(access.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_service(&self, out: &mut Self::Out, body: &RpServiceBody) -> Result<()> {
+        let endpoints = body
+            .endpoints
+            .iter()
+            .flat_map(|e| e.http1.as_ref().map(|http| (e, http)))
+            .collect::<Vec<_>>();
+
+        quote_in! { out.0 =>
+            export class $(&body.name)_Fetch {
+                private readonly url: string;
+
+                constructor(url?: string) {
+                    if (url === undefined) {
+                        $(if let Some(url) = &body.http.url {
+                            url = $(quoted(url.as_str()));
+                        } else {
+                            throw new Error("missing `url` argument");
+                        })
+                    }
+
+                    this.url = url;
+                }
+
+                $(for (e, http) in endpoints join ($['\n']) {
+                    $(ref o => self.endpoint_method(o, e, http))
+                })
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,365 @@
+//! TypeScript flavor.
+
+use crate::TYPE_SEP;
+use backend::package_processor;
+use genco::prelude::*;
+use genco::tokens::{FormatInto, ItemStr};
+use reproto_core::errors::Result;
+use reproto_core::{
+    self, CoreFlavor, Diagnostics, Flavor, FlavorTranslator, PackageTranslator, RpNumberType,
+    RpStringType, Spanned, Translate, Translator,
+};
+use std::ops::Deref;
+use std::rc::Rc;
+use trans::Packages;
+
+#[derive(Debug, Clone)]
+pub(crate) struct TsEndpoint {
+    pub(crate) endpoint: RpEndpoint,
+    pub(crate) http1: Option<RpEndpointHttp1>,
+}
+
+impl Deref for TsEndpoint {
+    type Target = RpEndpoint;
+
+    fn deref(&self) -> &Self::Target {
+        &self.endpoint
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Type {
+    Integer,
+    Float,
+    String,
+    Bool,
+    Object,
+    Array { argument: Box<Type> },
+    Map { key: Box<Type>, value: Box<Type> },
+    Import { import: js::Import, is_enum: bool },
+    Local { ident: ItemStr, is_enum: bool },
+}
+
+impl FormatInto<JavaScript> for &Type {
+    fn format_into(self, t: &mut js::Tokens) {
+        match self {
+            Type::Integer | Type::Float => quote_in!(*t => number),
+            Type::String => quote_in!(*t => string),
+            Type::Bool => quote_in!(*t => boolean),
+            Type::Object => quote_in!(*t => unknown),
+            Type::Array { argument } => quote_in!(*t => $(&**argument)[]),
+            Type::Map { value, .. } => quote_in!(*t => {[key: string]: $(&**value)}),
+            Type::Import { import, .. } => quote_in!(*t => $import),
+            Type::Local { ident, .. } => quote_in!(*t => $ident),
+        }
+    }
+}
+
+impl Type {
+    /// The type of the encoded, JSON-compatible, representation of this type.
+    pub(crate) fn encoded(&self) -> js::Tokens {
+        match self {
+            Self::Array { argument } => quote!($(argument.encoded())[]),
+            Self::Map { value, .. } => quote!({[key: string]: $(value.encoded())}),
+            Self::Import { .. } | Self::Local { .. } => quote!(unknown),
+            other => quote!($other),
+        }
+    }
+
+    pub(crate) fn decode(&self, t: &mut js::Tokens, var: js::Tokens) {
+        self.decode_depth(t, &var, 0);
+    }
+
+    /// Build decode method which also performs type checking.
+    pub(crate) fn decode_depth<T>(&self, t: &mut js::Tokens, var: T, d: usize)
+    where
+        T: FormatInto<JavaScript> + Copy,
+    {
+        match self {
+            Self::Object => (),
+            Self::Integer => {
+                quote_in! { *t =>
+                    if (!Number.isInteger($var)) {
+                        throw Error("expected integer");
+                    }
+                }
+            }
+            Self::Float => {
+                quote_in! { *t =>
+                    if (!Number.isFinite($var)) {
+                        throw Error("expected float");
+                    }
+                }
+            }
+            Self::Bool => {
+                quote_in! { *t =>
+                    if (typeof $var !== "boolean") {
+                        throw Error("expected boolean");
+                    }
+                }
+            }
+            Self::String => {
+                quote_in! { *t =>
+                    if (typeof $var !== "string") {
+                        throw Error("expected string");
+                    }
+                }
+            }
+            Self::Array { argument } => {
+                let o = &format!("o{}", d);
+                let i = &format!("i{}", d);
+                let l = &format!("l{}", d);
+                let v = &format!("v{}", d);
+
+                quote_in! { *t =>
+                    if (!Array.isArray($var)) {
+                        throw Error("expected array");
+                    }
+
+                    let $o: $(self) = [];
+
+                    for (let $i = 0, $l = $var.length; $i < $l; $i++) {
+                        let $v = $var[$i];
+
+                        $(ref t => argument.decode_depth(t, v, d + 1))
+
+                        $o.push($v);
+                    }
+
+                    $var = $o;
+                }
+            }
+            Self::Map { value, .. } => {
+                let o = &format!("o{}", d);
+                let k = &format!("k{}", d);
+                let v = &format!("v{}", d);
+
+                // NB: keys in JSON objects are always strings, so they are not checked.
+                quote_in! { *t =>
+                    if (typeof $var !== "object" || $var === null) {
+                        throw Error("expected object");
+                    }
+
+                    let $o: $(self) = {};
+
+                    for (let [$k, $v] of Object.entries($var)) {
+                        $(ref t => value.decode_depth(t, v, d + 1))
+
+                        $o[$k] = $v;
+                    }
+
+                    $var = $o;
+                }
+            }
+            Self::Import { import, .. } => quote_in! { *t =>
+                $var = $import.decode($var);
+            },
+            Self::Local { ident, .. } => quote_in! { *t =>
+                $var = $ident.decode($var);
+            },
+        }
+    }
+
+    /// Build encode method.
+    pub(crate) fn encode(&self, var: js::Tokens) -> js::Tokens {
+        match self {
+            Self::String => quote!($var),
+            Self::Float => quote!($var),
+            Self::Integer => quote!($var),
+            Self::Bool => quote!($var),
+            Self::Object => quote!($var),
+            Self::Array { argument } => {
+                let v = argument.encode(quote!(v));
+                quote!($var.map((v) => $v))
+            }
+            Self::Map { value, .. } => {
+                let v = &value.encode(quote!(data[k]));
+
+                quote! {
+                    ((data: $(self)) => {
+                        const o: $(self.encoded()) = {};
+
+                        for (const k in data) {
+                            o[k] = $v;
+                        }
+
+                        return o;
+                    })($var)
+                }
+            }
+            // NB: enums are encoded as their values.
+            Self::Import { is_enum: true, .. } | Self::Local { is_enum: true, .. } => quote!($var),
+            Self::Import { .. } => quote!($var.encode()),
+            Self::Local { .. } => quote!($var.encode()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Name {
+    ident: ItemStr,
+    package: RpPackage,
+}
+
+impl FormatInto<JavaScript> for &Name {
+    fn format_into(self, tokens: &mut Tokens<JavaScript>) {
+        tokens.append(&self.ident);
+    }
+}
+
+impl package_processor::Name<TypeScriptFlavor> for Name {
+    fn package(&self) -> &RpPackage {
+        &self.package
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum TypeScriptFlavor {}
+
+impl Flavor for TypeScriptFlavor {
+    type Type = Type;
+    type Name = Name;
+    type Field = RpField;
+    type Endpoint = TsEndpoint;
+    type Package = RpPackage;
+    type EnumType = Type;
+}
+
+/// Responsible for translating RpType -> TypeScript type.
+pub(crate) struct TypeScriptFlavorTranslator {
+    packages: Rc<Packages>,
+}
+
+impl TypeScriptFlavorTranslator {
+    pub(crate) fn new(packages: Rc<Packages>) -> Self {
+        Self { packages }
+    }
+}
+
+impl FlavorTranslator for TypeScriptFlavorTranslator {
+    type Source = CoreFlavor;
+    type Target = TypeScriptFlavor;
+
+    reproto_core::translator_defaults!(Self, field);
+
+    fn translate_number(&self, _: RpNumberType) -> Result<Type> {
+        Ok(Type::Integer)
+    }
+
+    fn translate_float(&self) -> Result<Type> {
+        Ok(Type::Float)
+    }
+
+    fn translate_double(&self) -> Result<Type> {
+        Ok(Type::Float)
+    }
+
+    fn translate_boolean(&self) -> Result<Type> {
+        Ok(Type::Bool)
+    }
+
+    fn translate_string(&self, _: RpStringType) -> Result<Type> {
+        Ok(Type::String)
+    }
+
+    fn translate_datetime(&self) -> Result<Type> {
+        Ok(Type::String)
+    }
+
+    fn translate_array(&self, argument: Type) -> Result<Type> {
+        Ok(Type::Array {
+            argument: Box::new(argument),
+        })
+    }
+
+    fn translate_map(&self, key: Type, value: Type) -> Result<Type> {
+        Ok(Type::Map {
+            key: Box::new(key),
+            value: Box::new(value),
+        })
+    }
+
+    fn translate_any(&self) -> Result<Type> {
+        Ok(Type::Object)
+    }
+
+    fn translate_bytes(&self) -> Result<Type> {
+        Ok(Type::String)
+    }
+
+    fn translate_name(&self, _from: &RpPackage, reg: RpReg, name: Spanned<RpName>) -> Result<Type> {
+        let is_enum = matches!(reg, RpReg::Enum);
+        let ident = reg.ident(&name, |p| p.join(TYPE_SEP), |c| c.join(TYPE_SEP));
+
+        if let Some(used) = &name.prefix {
+            let module = js::Module::Path(format!("{}.js", name.package.join("/")).into());
+
+            return Ok(Type::Import {
+                import: js::import(module, ident).with_alias(used.to_string()),
+                is_enum,
+            });
+        }
+
+        Ok(Type::Local {
+            ident: ident.into(),
+            is_enum,
+        })
+    }
+
+    fn translate_endpoint<T>(
+        &self,
+        translator: &T,
+        diag: &mut Diagnostics,
+        endpoint: RpEndpoint<CoreFlavor>,
+    ) -> Result<TsEndpoint>
+    where
+        T: Translator<Source = CoreFlavor, Target = TypeScriptFlavor>,
+    {
+        let endpoint = endpoint.translate(diag, translator)?;
+        let http1 = RpEndpointHttp1::from_endpoint(&endpoint);
+
+        Ok(TsEndpoint { endpoint, http1 })
+    }
+
+    fn translate_package(&self, source: RpVersionedPackage) -> Result<RpPackage> {
+        self.packages.translate_package(source)
+    }
+
+    fn translate_local_name<T>(
+        &self,
+        _: &T,
+        _: &mut Diagnostics,
+        reg: RpReg,
+        name: Spanned<RpName<CoreFlavor>>,
+    ) -> Result<Name>
+    where
+        T: Translator<Source = Self::Source, Target = Self::Target>,
+    {
+        let (name, _) = Spanned::take_pair(name);
+
+        let ident = reg.ident(&name, |p| p.join(TYPE_SEP), |v| v.join(TYPE_SEP));
+        let package = self.translate_package(name.package)?;
+
+        Ok(Name {
+            ident: ident.into(),
+            package,
+        })
+    }
+
+    fn translate_enum_type<T>(
+        &self,
+        _: &T,
+        _: &mut Diagnostics,
+        enum_type: RpEnumType,
+    ) -> Result<Type>
+    where
+        T: Translator<Source = Self::Source, Target = Self::Target>,
+    {
+        match enum_type {
+            RpEnumType::String(string) => self.translate_string(string),
+            RpEnumType::Number(number) => self.translate_number(number),
+        }
+    }
+}
+
+reproto_core::decl_flavor!(pub(crate) TypeScriptFlavor);
@@ -0,0 +1,161 @@
+mod compiler;
+mod flavored;
+mod utils;
+
+use crate::compiler::Compiler;
+use genco::prelude::*;
+use manifest::{Lang, Manifest, NoModule, TryFromToml};
+use reproto_core::errors::Result;
+use reproto_core::{CoreFlavor, Handle};
+use std::any::Any;
+use std::path::Path;
+use trans::Session;
+
+const TYPE_SEP: &str = "_";
+const EXT: &str = "ts";
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TsLang;
+
+impl Lang for TsLang {
+    manifest::lang_base!(TsModule, compile);
+
+    fn comment(&self, input: &str) -> Option<String> {
+        Some(format!("// {}", input))
+    }
+
+    fn safe_packages(&self) -> bool {
+        // NB: TypeScript imports by string literals, no keyword escaping needed.
+        true
+    }
+
+    fn keywords(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("abstract", "_abstract"),
+            ("await", "_await"),
+            ("boolean", "_boolean"),
+            ("break", "_break"),
+            ("byte", "_byte"),
+            ("case", "_case"),
+            ("catch", "_catch"),
+            ("char", "_char"),
+            ("class", "_class"),
+            ("const", "_const"),
+            ("continue", "_continue"),
+            ("debugger", "_debugger"),
+            ("default", "_default"),
+            ("delete", "_delete"),
+            ("do", "_do"),
+            ("double", "_double"),
+            ("else", "_else"),
+            ("enum", "_enum"),
+            ("export", "_export"),
+            ("extends", "_extends"),
+            ("false", "_false"),
+            ("final", "_final"),
+            ("finally", "_finally"),
+            ("float", "_float"),
+            ("for", "_for"),
+            ("function", "_function"),
+            ("goto", "_goto"),
+            ("if", "_if"),
+            ("implements", "_implements"),
+            ("import", "_import"),
+            ("in", "_in"),
+            ("instanceof", "_instanceof"),
+            ("int", "_int"),
+            ("interface", "_interface"),
+            ("let", "_let"),
+            ("long", "_long"),
+            ("native", "_native"),
+            ("new", "_new"),
+            ("null", "_null"),
+            ("package", "_package"),
+            ("private", "_private"),
+            ("protected", "_protected"),
+            ("public", "_public"),
+            ("return", "_return"),
+            ("short", "_short"),
+            ("static", "_static"),
+            ("super", "_super"),
+            ("switch", "_switch"),
+            ("synchronized", "_synchronized"),
+            ("this", "_this"),
+            ("throw", "_throw"),
+            ("throws", "_throws"),
+            ("transient", "_transient"),
+            ("true", "_true"),
+            ("try", "_try"),
+            ("typeof", "_typeof"),
+            ("var", "_var"),
+            ("void", "_void"),
+            ("volatile", "_volatile"),
+            ("while", "_while"),
+            ("with", "_with"),
+            ("yield", "_yield"),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub enum TsModule {
+    Newtype,
+}
+
+impl TryFromToml for TsModule {
+    fn try_from_string(path: &Path, id: &str, value: String) -> Result<Self> {
+        let result = match id {
+            "newtype" => TsModule::Newtype,
+            _ => return NoModule::illegal(path, id, value),
+        };
+
+        Ok(result)
+    }
+
+    fn try_from_value(path: &Path, id: &str, value: toml::Value) -> Result<Self> {
+        let result = match id {
+            "newtype" => TsModule::Newtype,
+            _ => return NoModule::illegal(path, id, value),
+        };
+
+        Ok(result)
+    }
+}
+
+pub(crate) struct Options {
+    /// Generate aliases as wrapper classes.
+    pub(crate) newtype: bool,
+}
+
+fn options(modules: Vec<TsModule>) -> Options {
+    let mut options = Options { newtype: false };
+
+    for module in modules {
+        match module {
+            TsModule::Newtype => options.newtype = true,
+        }
+    }
+
+    options
+}
+
+pub struct FileSpec(pub Tokens<JavaScript>);
+
+impl Default for FileSpec {
+    fn default() -> Self {
+        FileSpec(Tokens::new())
+    }
+}
+
+fn compile(handle: &dyn Handle, env: Session<CoreFlavor>, manifest: Manifest) -> Result<()> {
+    let packages = env.packages()?;
+
+    let modules: Vec<TsModule> = manifest::checked_modules(manifest.modules)?;
+    let options = options(modules);
+
+    let env = env
+        .with_newtypes(options.newtype)
+        .translate(flavored::TypeScriptFlavorTranslator::new(packages))?;
+
+    Compiler::new(&env, options, handle).compile()
+}
@@ -1,7 +1,7 @@
 use reproto_core::errors::Result;
 use reproto_core::{
-    Flavor, Handle, RelativePath, RelativePathBuf, RpDecl, RpEnumBody, RpInterfaceBody, RpName,
-    RpPackage, RpServiceBody, RpTupleBody, RpTypeBody, Spanned,
+    Flavor, Handle, RelativePath, RelativePathBuf, RpAliasBody, RpConstBody, RpDecl, RpEnumBody,
+    RpInterfaceBody, RpName, RpPackage, RpServiceBody, RpTupleBody, RpTypeBody, Spanned,
 };
 use std::cmp;
 use std::collections::{btree_map, BTreeMap};
@@ -67,6 +67,14 @@ where
         self.default_process(out, &body.name)
     }
 
+    fn process_const(&self, out: &mut Self::Out, body: &RpConstBody<F>) -> Result<()> {
+        self.default_process(out, &body.name)
+    }
+
+    fn process_alias(&self, out: &mut Self::Out, body: &RpAliasBody<F>) -> Result<()> {
+        self.default_process(out, &body.name)
+    }
+
     fn populate_files(&self) -> Result<BTreeMap<F::Package, Self::Out>> {
         self.do_populate_files(|_, _, _| Ok(()))
     }
@@ -94,6 +102,8 @@ where
                 Tuple(ref b) => self.process_tuple(out, b)?,
                 Enum(ref b) => self.process_enum(out, b)?,
                 Service(ref b) => self.process_service(out, b)?,
+                Const(ref b) => self.process_const(out, b)?,
+                Alias(ref b) => self.process_alias(out, b)?,
             }
         }
 
@@ -16,6 +16,10 @@ pub enum SymbolKind {
     Enum,
     #[serde(rename = "service")]
     Service,
+    #[serde(rename = "const")]
+    Const,
+    #[serde(rename = "alias")]
+    Alias,
 }
 
 /// A single diagnostic emitted by the compiler.
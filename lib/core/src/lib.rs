@@ -20,14 +20,18 @@ mod options;
 pub mod regex;
 mod reporter;
 mod resolver;
+mod rp_alias;
 mod rp_channel;
 mod rp_code;
+mod rp_const;
 mod rp_decl;
+mod rp_deprecated;
 mod rp_endpoint;
 mod rp_enum;
 mod rp_field;
 mod rp_file;
 mod rp_interface;
+mod rp_literal;
 mod rp_name;
 mod rp_number;
 mod rp_package;
@@ -60,9 +64,12 @@ pub use self::option_entry::OptionEntry;
 pub use self::options::Options;
 pub use self::reporter::{Reported, Reporter};
 pub use self::resolver::{EmptyResolver, Resolved, ResolvedByPrefix, Resolver};
+pub use self::rp_alias::RpAliasBody;
 pub use self::rp_channel::RpChannel;
 pub use self::rp_code::{RpCode, RpContext};
+pub use self::rp_const::RpConstBody;
 pub use self::rp_decl::{RpDecl, RpNamed};
+pub use self::rp_deprecated::RpDeprecated;
 pub use self::rp_endpoint::{
     RpAccept, RpEndpoint, RpEndpointArgument, RpEndpointHttp, RpEndpointHttp1, RpHttpMethod,
 };
@@ -72,6 +79,7 @@ pub use self::rp_enum::{
 pub use self::rp_field::RpField;
 pub use self::rp_file::{RpEnabledFeature, RpFile};
 pub use self::rp_interface::{RpInterfaceBody, RpSubType, RpSubTypeStrategy, DEFAULT_TAG};
+pub use self::rp_literal::RpLiteral;
 pub use self::rp_name::RpName;
 pub use self::rp_number::RpNumber;
 pub use self::rp_package::RpPackage;
@@ -83,6 +91,7 @@ pub use self::rp_service::{RpServiceBody, RpServiceBodyHttp};
 pub use self::rp_tuple::RpTupleBody;
 pub use self::rp_type::{
     RpNumberKind, RpNumberType, RpNumberValidate, RpStringType, RpStringValidate, RpType,
+    RpValidate,
 };
 pub use self::rp_type_model::RpTypeBody;
 pub use self::rp_value::RpValue;
@@ -2,13 +2,15 @@
 macro_rules! decl_body {
     (pub struct $name:ident<$f:ident> { $($rest:tt)* }) => {
         #[derive(Debug, Clone, serde::Serialize)]
-        #[serde(bound = "F: serde::Serialize, F::Field: serde::Serialize, F::Endpoint: serde::Serialize, F::Package: serde::Serialize, F::Name: serde::Serialize, F::EnumType: serde::Serialize")]
+        #[serde(bound = "F: serde::Serialize, F::Field: serde::Serialize, F::Endpoint: serde::Serialize, F::Package: serde::Serialize, F::Name: serde::Serialize, F::EnumType: serde::Serialize, F::Type: serde::Serialize")]
         pub struct $name<$f> where $f: $crate::Flavor {
             pub name: $f::Name,
             pub ident: String,
             pub comment: Vec<String>,
             pub decls: Vec<$crate::RpDecl<$f>>,
             pub decl_idents: ::linked_hash_map::LinkedHashMap<String, usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub deprecated: Option<$crate::RpDeprecated>,
             $($rest)*
         }
     };
@@ -18,9 +20,12 @@ macro_rules! decl_body {
 macro_rules! decl_flavor {
     ($vis:vis $flavor:ident) => {
         $vis type RpAccept = $crate::RpAccept;
+        $vis type RpAliasBody<F = $flavor> = $crate::RpAliasBody<F>;
         $vis type RpCode = $crate::RpCode;
+        $vis type RpConstBody<F = $flavor> = $crate::RpConstBody<F>;
         $vis type RpContext = $crate::RpContext;
         $vis type RpDecl<F = $flavor> = $crate::RpDecl<F>;
+        $vis type RpDeprecated = $crate::RpDeprecated;
         $vis type RpEndpoint<F = $flavor> = $crate::RpEndpoint<F>;
         $vis type RpEndpointArgument<F = $flavor> = $crate::RpEndpointArgument<F>;
         $vis type RpEndpointHttp<F = $flavor> = $crate::RpEndpointHttp<F>;
@@ -28,6 +33,7 @@ macro_rules! decl_flavor {
         $vis type RpEnumBody<F = $flavor> = $crate::RpEnumBody<F>;
         $vis type RpField<F = $flavor> = $crate::RpField<F>;
         $vis type RpFile<F = $flavor> = $crate::RpFile<F>;
+        $vis type RpLiteral = $crate::RpLiteral;
         $vis type RpHttpMethod = $crate::RpHttpMethod;
         $vis type RpInterfaceBody<F = $flavor> = $crate::RpInterfaceBody<F>;
         $vis type RpPathPart<F = $flavor> = $crate::RpPathPart<F>;
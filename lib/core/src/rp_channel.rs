@@ -44,12 +44,13 @@ where
 impl<F> fmt::Display for RpChannel<F>
 where
     F: Flavor,
+    F::Type: fmt::Display,
 {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         if self.is_streaming() {
-            write!(fmt, "stream {:?}", self.ty())
+            write!(fmt, "stream {}", self.ty())
         } else {
-            write!(fmt, "{:?}", self.ty())
+            write!(fmt, "{}", self.ty())
         }
     }
 }
@@ -19,6 +19,7 @@ pub enum RpContext {
     Reproto {},
     Rust {},
     Swift {},
+    TypeScript {},
     Dart {},
 }
 
@@ -0,0 +1,39 @@
+//! Model for constants.
+
+use crate::errors::Result;
+use crate::{Diagnostics, RpLiteral, RpReg, Translate, Translator};
+
+decl_body!(
+    pub struct RpConstBody<F> {
+        #[serde(rename = "type")]
+        pub ty: F::Type,
+        pub value: RpLiteral,
+    }
+);
+
+impl<T> Translate<T> for RpConstBody<T::Source>
+where
+    T: Translator,
+{
+    type Out = RpConstBody<T::Target>;
+
+    /// Translate into different flavor.
+    fn translate(self, diag: &mut Diagnostics, translator: &T) -> Result<RpConstBody<T::Target>> {
+        translator.visit(diag, &self.name)?;
+
+        let name = translator.translate_local_name(diag, RpReg::Const, self.name)?;
+        let decls = self.decls.translate(diag, translator)?;
+        let ty = translator.translate_type(diag, self.ty)?;
+
+        Ok(RpConstBody {
+            name,
+            ident: self.ident,
+            comment: self.comment,
+            decls,
+            decl_idents: self.decl_idents,
+            deprecated: self.deprecated,
+            ty,
+            value: self.value,
+        })
+    }
+}
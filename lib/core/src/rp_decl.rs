@@ -2,8 +2,9 @@
 
 use crate::errors::Result;
 use crate::{
-    Diagnostics, Flavor, RpEnumBody, RpInterfaceBody, RpReg, RpServiceBody, RpSubType, RpTupleBody,
-    RpTypeBody, RpVariantRef, Span, Spanned, Translate, Translator,
+    Diagnostics, Flavor, RpAliasBody, RpConstBody, RpDeprecated, RpEnumBody, RpInterfaceBody,
+    RpReg, RpServiceBody, RpSubType, RpTupleBody, RpTypeBody, RpVariantRef, Span, Spanned,
+    Translate, Translator,
 };
 use serde::Serialize;
 use std::fmt;
@@ -20,6 +21,8 @@ where
     Enum(&'a Spanned<RpEnumBody<F>>),
     EnumVariant(RpVariantRef<'a, F>),
     Service(&'a Spanned<RpServiceBody<F>>),
+    Const(&'a Spanned<RpConstBody<F>>),
+    Alias(&'a Spanned<RpAliasBody<F>>),
 }
 
 impl<'a, F> RpNamed<'a, F>
@@ -31,13 +34,15 @@ where
         use self::RpNamed::*;
 
         match *self {
-            Type(ref body) => &body.name,
-            Tuple(ref tuple) => &tuple.name,
-            Interface(ref interface) => &interface.name,
-            SubType(ref sub_type) => &sub_type.name,
-            Enum(ref en) => &en.name,
-            EnumVariant(ref variant) => variant.name,
-            Service(ref service) => &service.name,
+            Type(body) => &body.name,
+            Tuple(tuple) => &tuple.name,
+            Interface(interface) => &interface.name,
+            SubType(sub_type) => &sub_type.name,
+            Enum(en) => &en.name,
+            EnumVariant(variant) => variant.name,
+            Service(service) => &service.name,
+            Const(body) => &body.name,
+            Alias(body) => &body.name,
         }
     }
 
@@ -46,13 +51,32 @@ where
         use self::RpNamed::*;
 
         match *self {
-            Type(ref body) => body.span(),
-            Tuple(ref tuple) => tuple.span(),
-            Interface(ref interface) => interface.span(),
-            SubType(ref sub_type) => sub_type.span(),
-            Enum(ref en) => en.span(),
-            EnumVariant(ref variant) => variant.span,
-            Service(ref service) => service.span(),
+            Type(body) => body.span(),
+            Tuple(tuple) => tuple.span(),
+            Interface(interface) => interface.span(),
+            SubType(sub_type) => sub_type.span(),
+            Enum(en) => en.span(),
+            EnumVariant(variant) => variant.span,
+            Service(service) => service.span(),
+            Const(body) => body.span(),
+            Alias(body) => body.span(),
+        }
+    }
+
+    /// Get the deprecation of the named element, if it is deprecated.
+    pub fn deprecated(&self) -> Option<&RpDeprecated> {
+        use self::RpNamed::*;
+
+        match *self {
+            Type(body) => body.deprecated.as_ref(),
+            Tuple(tuple) => tuple.deprecated.as_ref(),
+            Interface(interface) => interface.deprecated.as_ref(),
+            SubType(sub_type) => sub_type.deprecated.as_ref(),
+            Enum(en) => en.deprecated.as_ref(),
+            EnumVariant(variant) => variant.deprecated,
+            Service(service) => service.deprecated.as_ref(),
+            Const(body) => body.deprecated.as_ref(),
+            Alias(body) => body.deprecated.as_ref(),
         }
     }
 }
@@ -60,7 +84,7 @@ where
 #[derive(Debug, Clone, Serialize)]
 #[serde(
     bound = "F: Serialize, F::Field: Serialize, F::Endpoint: Serialize, F::Package: Serialize, \
-             F::Name: Serialize, F::EnumType: Serialize"
+             F::Name: Serialize, F::EnumType: Serialize, F::Type: Serialize"
 )]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum RpDecl<F>
@@ -72,6 +96,8 @@ where
     Interface(Spanned<RpInterfaceBody<F>>),
     Enum(Spanned<RpEnumBody<F>>),
     Service(Spanned<RpServiceBody<F>>),
+    Const(Spanned<RpConstBody<F>>),
+    Alias(Spanned<RpAliasBody<F>>),
 }
 
 impl<F> RpDecl<F>
@@ -92,6 +118,8 @@ where
             Enum(ref body) => body.decls.iter().collect::<Vec<_>>(),
             Tuple(ref body) => body.decls.iter().collect::<Vec<_>>(),
             Service(ref body) => body.decls.iter().collect::<Vec<_>>(),
+            Const(ref body) => body.decls.iter().collect::<Vec<_>>(),
+            Alias(ref body) => body.decls.iter().collect::<Vec<_>>(),
         };
 
         decls.into_iter()
@@ -107,6 +135,8 @@ where
             Enum(ref body) => body.ident.as_str(),
             Tuple(ref body) => body.ident.as_str(),
             Service(ref body) => body.ident.as_str(),
+            Const(ref body) => body.ident.as_str(),
+            Alias(ref body) => body.ident.as_str(),
         }
     }
 
@@ -120,6 +150,8 @@ where
             Enum(ref body) => &body.name,
             Tuple(ref body) => &body.name,
             Service(ref body) => &body.name,
+            Const(ref body) => &body.name,
+            Alias(ref body) => &body.name,
         }
     }
 
@@ -133,6 +165,35 @@ where
             Enum(ref body) => &body.comment,
             Tuple(ref body) => &body.comment,
             Service(ref body) => &body.comment,
+            Const(ref body) => &body.comment,
+            Alias(ref body) => &body.comment,
+        }
+    }
+
+    /// Get the deprecation of the declaration, if it is deprecated.
+    pub fn deprecated(&self) -> Option<&RpDeprecated> {
+        use self::RpDecl::*;
+
+        match *self {
+            Type(ref body) => body.deprecated.as_ref(),
+            Interface(ref body) => body.deprecated.as_ref(),
+            Enum(ref body) => body.deprecated.as_ref(),
+            Tuple(ref body) => body.deprecated.as_ref(),
+            Service(ref body) => body.deprecated.as_ref(),
+            Const(ref body) => body.deprecated.as_ref(),
+            Alias(ref body) => body.deprecated.as_ref(),
+        }
+    }
+
+    /// Get the type parameters of the declaration, empty unless it is generic.
+    pub fn type_parameters(&self) -> &[Spanned<String>] {
+        use self::RpDecl::*;
+
+        match *self {
+            Type(ref body) => &body.type_parameters,
+            Interface(ref body) => &body.type_parameters,
+            Tuple(ref body) => &body.type_parameters,
+            _ => &[],
         }
     }
 
@@ -166,6 +227,12 @@ where
             Service(ref service) => {
                 out.push((&service.name, service.span(), RpReg::Service));
             }
+            Const(ref body) => {
+                out.push((&body.name, body.span(), RpReg::Const));
+            }
+            Alias(ref body) => {
+                out.push((&body.name, body.span(), RpReg::Alias));
+            }
         }
 
         out.extend(self.decls().flat_map(|d| d.to_reg()));
@@ -202,6 +269,12 @@ where
             Service(ref service) => {
                 out.push(RpNamed::Service(service));
             }
+            Const(ref body) => {
+                out.push(RpNamed::Const(body));
+            }
+            Alias(ref body) => {
+                out.push(RpNamed::Alias(body));
+            }
         }
 
         out.extend(self.decls().flat_map(|d| d.to_named()));
@@ -218,6 +291,8 @@ where
             Enum(_) => "enum",
             Tuple(_) => "tuple",
             Service(_) => "service",
+            Const(_) => "const",
+            Alias(_) => "alias",
         }
     }
 
@@ -231,6 +306,8 @@ where
             Enum(ref body) => body.span(),
             Tuple(ref body) => body.span(),
             Service(ref body) => body.span(),
+            Const(ref body) => body.span(),
+            Alias(ref body) => body.span(),
         }
     }
 
@@ -244,6 +321,8 @@ where
             Enum(ref body) => (&body.decls, &body.decl_idents),
             Tuple(ref body) => (&body.decls, &body.decl_idents),
             Service(ref body) => (&body.decls, &body.decl_idents),
+            Const(ref body) => (&body.decls, &body.decl_idents),
+            Alias(ref body) => (&body.decls, &body.decl_idents),
         };
 
         match decl_idents.get(ident) {
@@ -269,6 +348,8 @@ where
             Interface(body) => Interface(body.translate(diag, translator)?),
             Enum(body) => Enum(body.translate(diag, translator)?),
             Service(body) => Service(body.translate(diag, translator)?),
+            Const(body) => Const(body.translate(diag, translator)?),
+            Alias(body) => Alias(body.translate(diag, translator)?),
         };
 
         Ok(out)
@@ -289,6 +370,8 @@ where
             Enum(ref body) => write!(f, "enum {}", body.name),
             Tuple(ref body) => write!(f, "tuple {}", body.name),
             Service(ref body) => write!(f, "service {}", body.name),
+            Const(ref body) => write!(f, "const {}", body.name),
+            Alias(ref body) => write!(f, "alias {}", body.name),
         }
     }
 }
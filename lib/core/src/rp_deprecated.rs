@@ -0,0 +1,39 @@
+//! Model for deprecations
+
+use crate::Version;
+use serde::Serialize;
+use std::fmt;
+
+/// Deprecation of a declaration, field, variant, or endpoint.
+///
+/// Specified with the `#[deprecated(note = "..", since = "..")]` attribute.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct RpDeprecated {
+    /// Note explaining the deprecation, like what to use instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Version in which the item was deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<Version>,
+}
+
+impl RpDeprecated {
+    /// Human-readable message describing the deprecation, if it has a note or a version.
+    pub fn message(&self) -> Option<String> {
+        match (self.since.as_ref(), self.note.as_ref()) {
+            (Some(since), Some(note)) => Some(format!("deprecated since {}: {}", since, note)),
+            (Some(since), None) => Some(format!("deprecated since {}", since)),
+            (None, Some(note)) => Some(note.to_string()),
+            (None, None) => None,
+        }
+    }
+}
+
+impl fmt::Display for RpDeprecated {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.message() {
+            Some(message) => message.fmt(fmt),
+            None => "deprecated".fmt(fmt),
+        }
+    }
+}
@@ -2,7 +2,8 @@
 
 use crate::errors::Result;
 use crate::{
-    Attributes, Diagnostics, Flavor, RpChannel, RpPathSpec, Spanned, Translate, Translator,
+    Attributes, Diagnostics, Flavor, RpChannel, RpDeprecated, RpPathSpec, Spanned, Translate,
+    Translator,
 };
 use serde::Serialize;
 use std::default;
@@ -172,6 +173,9 @@ where
     pub name: Option<String>,
     /// Comments for documentation.
     pub comment: Vec<String>,
+    /// Deprecation of the endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<RpDeprecated>,
     /// Attributes associated with the endpoint.
     pub attributes: Attributes<F>,
     /// Arguments that this endpoint accepts.
@@ -237,6 +241,7 @@ where
             safe_ident: self.safe_ident,
             name: self.name,
             comment: self.comment,
+            deprecated: self.deprecated,
             attributes: self.attributes.translate(diag, translator)?,
             arguments: self.arguments.translate(diag, translator)?,
             request: self.request.translate(diag, translator)?,
@@ -2,8 +2,8 @@
 
 use crate::errors::Result;
 use crate::{
-    Diagnostics, Flavor, RpCode, RpNumber, RpNumberType, RpReg, RpStringType, RpValue, Span,
-    Spanned, Translate, Translator,
+    Diagnostics, Flavor, RpCode, RpDeprecated, RpNumber, RpNumberType, RpReg, RpStringType,
+    RpValue, Span, Spanned, Translate, Translator,
 };
 use serde::Serialize;
 use std::fmt;
@@ -41,6 +41,7 @@ where
             comment: self.comment,
             decls,
             decl_idents: self.decl_idents,
+            deprecated: self.deprecated,
             enum_type,
             variants,
             codes: self.codes,
@@ -89,6 +90,7 @@ where
     pub name: &'a F::Name,
     pub ident: &'a Spanned<String>,
     pub comment: &'a Vec<String>,
+    pub deprecated: Option<&'a RpDeprecated>,
     pub value: RpVariantValue<'a>,
 }
 
@@ -112,6 +114,7 @@ where
             name: self.name,
             ident: self.ident,
             comment: self.comment,
+            deprecated: self.deprecated,
             value: self.value,
         }
     }
@@ -139,6 +142,9 @@ where
     pub name: F::Name,
     pub ident: Spanned<String>,
     pub comment: Vec<String>,
+    /// Deprecation of the variant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<RpDeprecated>,
     pub value: V,
 }
 
@@ -179,6 +185,7 @@ where
             name,
             ident: self.ident,
             comment: self.comment,
+            deprecated: self.deprecated,
             value: self.value,
         })
     }
@@ -289,6 +296,7 @@ where
                             name: &value.name,
                             ident: &value.ident,
                             comment: &value.comment,
+                            deprecated: value.deprecated.as_ref(),
                             value: RpVariantValue::from(&value.value),
                         })
                     }
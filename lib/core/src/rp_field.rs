@@ -1,7 +1,9 @@
 //! Data Models for fields
 
 use crate::errors::Result;
-use crate::{Diagnostics, Flavor, FlavorField, Translate, Translator};
+use crate::{
+    Diagnostics, Flavor, FlavorField, RpDeprecated, RpLiteral, RpValidate, Translate, Translator,
+};
 use serde::Serialize;
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
@@ -24,6 +26,15 @@ where
     /// Alias of field in JSON.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub field_as: Option<String>,
+    /// Validation rules which must hold when the field is decoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate: Option<RpValidate>,
+    /// Value of the field when it is absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<RpLiteral>,
+    /// Deprecation of the field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<RpDeprecated>,
 }
 
 impl<F> FlavorField for RpField<F>
@@ -31,7 +42,7 @@ where
     F: Flavor,
 {
     fn is_discriminating(&self) -> bool {
-        self.required
+        self.required && self.default.is_none()
     }
 }
 
@@ -50,6 +61,9 @@ where
             comment: Vec::new(),
             ty,
             field_as: None,
+            validate: None,
+            default: None,
+            deprecated: None,
         }
     }
 
@@ -101,6 +115,9 @@ where
             comment: self.comment,
             ty: translator.translate_type(diag, self.ty)?,
             field_as: self.field_as,
+            validate: self.validate,
+            default: self.default,
+            deprecated: self.deprecated,
         })
     }
 }
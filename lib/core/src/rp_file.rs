@@ -15,7 +15,7 @@ pub struct RpEnabledFeature {
 #[derive(Debug, Clone, Serialize)]
 #[serde(
     bound = "F: Serialize, F::Field: Serialize, F::Endpoint: Serialize, F::Package: Serialize, \
-             F::Name: Serialize, F::EnumType: Serialize"
+             F::Name: Serialize, F::EnumType: Serialize, F::Type: Serialize"
 )]
 pub struct RpFile<F>
 where
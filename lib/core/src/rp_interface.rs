@@ -3,7 +3,8 @@
 use crate::errors::Result;
 use crate::translator;
 use crate::{
-    Diagnostics, Flavor, FlavorField, RpCode, RpDecl, RpReg, Spanned, Translate, Translator,
+    Diagnostics, Flavor, FlavorField, RpCode, RpDecl, RpDeprecated, RpReg, Spanned, Translate,
+    Translator,
 };
 use linked_hash_map::LinkedHashMap;
 use serde::Serialize;
@@ -32,6 +33,9 @@ impl Default for RpSubTypeStrategy {
 
 decl_body!(
     pub struct RpInterfaceBody<F> {
+        /// Type parameters, if this is a generic declaration.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub type_parameters: Vec<Spanned<String>>,
         pub fields: Vec<Spanned<F::Field>>,
         pub codes: Vec<Spanned<RpCode>>,
         pub sub_types: Vec<Spanned<RpSubType<F>>>,
@@ -73,6 +77,8 @@ where
             comment: self.comment,
             decls,
             decl_idents: self.decl_idents,
+            deprecated: self.deprecated,
+            type_parameters: self.type_parameters,
             fields,
             codes: self.codes,
             sub_types,
@@ -84,7 +90,7 @@ where
 #[derive(Debug, Clone, Serialize)]
 #[serde(
     bound = "F: Serialize, F::Field: Serialize, F::Endpoint: Serialize, F::Package: Serialize, \
-             F::Name: Serialize, F::EnumType: Serialize"
+             F::Name: Serialize, F::EnumType: Serialize, F::Type: Serialize"
 )]
 pub struct RpSubType<F>
 where
@@ -96,6 +102,9 @@ where
     /// Inner declarations.
     pub decls: Vec<RpDecl<F>>,
     pub decl_idents: LinkedHashMap<String, usize>,
+    /// Deprecation of the sub-type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<RpDeprecated>,
     pub fields: Vec<Spanned<F::Field>>,
     pub codes: Vec<Spanned<RpCode>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -150,6 +159,7 @@ where
             comment: self.comment,
             decls,
             decl_idents: self.decl_idents,
+            deprecated: self.deprecated,
             fields,
             codes: self.codes,
             sub_type_name: self.sub_type_name,
@@ -0,0 +1,28 @@
+//! Model for literal values
+
+use crate::RpNumber;
+use serde::Serialize;
+use std::fmt;
+
+/// A literal value which has been checked against the type it is used with.
+///
+/// Used for the default values of fields and the values of constants.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum RpLiteral {
+    String(String),
+    Number(RpNumber),
+    Boolean(bool),
+}
+
+impl fmt::Display for RpLiteral {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::RpLiteral::*;
+
+        match *self {
+            String(ref string) => write!(fmt, "{:?}", string),
+            Number(ref number) => number.fmt(fmt),
+            Boolean(boolean) => boolean.fmt(fmt),
+        }
+    }
+}
@@ -13,6 +13,8 @@ pub enum RpReg {
     Enum,
     EnumVariant,
     Service,
+    Const,
+    Alias,
 }
 
 impl RpReg {
@@ -30,7 +32,7 @@ impl RpReg {
         use self::RpReg::*;
 
         match *self {
-            Type | Interface | Enum | Tuple | Service => {
+            Type | Interface | Enum | Tuple | Service | Const | Alias => {
                 let p = name.path.iter().map(String::as_str).collect();
                 package_fn(p)
             }
@@ -70,6 +72,8 @@ impl fmt::Display for RpReg {
             Service => write!(fmt, "service"),
             SubType => write!(fmt, "subtype"),
             EnumVariant => write!(fmt, "variant"),
+            Const => write!(fmt, "const"),
+            Alias => write!(fmt, "alias"),
         }
     }
 }
@@ -44,6 +44,7 @@ where
             comment: self.comment,
             decls,
             decl_idents: self.decl_idents,
+            deprecated: self.deprecated,
             http: self.http,
             endpoints,
         })
@@ -6,6 +6,9 @@ use crate::{Diagnostics, Flavor, RpCode, RpReg, Spanned, Translate, Translator};
 
 decl_body!(
     pub struct RpTupleBody<F> {
+        /// Type parameters, if this is a generic declaration.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub type_parameters: Vec<Spanned<String>>,
         pub fields: Vec<Spanned<F::Field>>,
         pub codes: Vec<Spanned<RpCode>>,
     }
@@ -40,6 +43,8 @@ where
             comment: self.comment,
             decls,
             decl_idents: self.decl_idents,
+            deprecated: self.deprecated,
+            type_parameters: self.type_parameters,
             fields,
             codes: self.codes,
         })
@@ -9,7 +9,9 @@ use std::fmt;
 /// Describes number validation.
 #[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
 pub struct RpNumberValidate {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub min: Option<RpNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max: Option<RpNumber>,
 }
 
@@ -38,6 +40,14 @@ impl RpStringValidate {
     }
 }
 
+/// Validation rules associated with the type of a field.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RpValidate {
+    Number(RpNumberValidate),
+    String(RpStringValidate),
+}
+
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
 pub enum RpNumberKind {
     #[serde(rename = "u32")]
@@ -129,6 +139,17 @@ where
     Name {
         name: Spanned<RpName<F>>,
     },
+    /// A generic declaration applied to the given type arguments.
+    ///
+    /// These are instantiated into concrete declarations before being translated.
+    Generic {
+        name: Spanned<RpName<F>>,
+        arguments: Vec<RpType<F>>,
+    },
+    /// A type parameter of the generic declaration being defined.
+    Parameter {
+        name: Spanned<String>,
+    },
     Array {
         inner: Box<RpType<F>>,
     },
@@ -153,6 +174,21 @@ where
         }
     }
 
+    /// Get the validation rules associated with the type, if there are any.
+    pub fn validate(&self) -> Option<RpValidate> {
+        use self::RpType::*;
+
+        match *self {
+            Number(ref number) if !number.validate.is_empty() => {
+                Some(RpValidate::Number(number.validate.clone()))
+            }
+            String(ref string) if !string.validate.is_empty() => {
+                Some(RpValidate::String(string.validate.clone()))
+            }
+            _ => None,
+        }
+    }
+
     /// Modify any name components with the given operation.
     fn with_name<M>(self, f: M) -> Self
     where
@@ -164,6 +200,13 @@ where
             Name { name } => Name {
                 name: Spanned::map(name, f),
             },
+            Generic { name, arguments } => Generic {
+                name: Spanned::map(name, f.clone()),
+                arguments: arguments
+                    .into_iter()
+                    .map(|a| a.with_name(f.clone()))
+                    .collect(),
+            },
             Array { inner } => Array {
                 inner: Box::new(inner.with_name(f)),
             },
@@ -205,6 +248,23 @@ where
             String(..) => write!(f, "string"),
             DateTime => write!(f, "datetime"),
             Name { ref name } => write!(f, "{}", name),
+            Generic {
+                ref name,
+                ref arguments,
+            } => {
+                write!(f, "{}<", name)?;
+
+                for (i, argument) in arguments.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", argument)?;
+                }
+
+                write!(f, ">")
+            }
+            Parameter { ref name } => write!(f, "{}", name),
             Array { ref inner } => write!(f, "[{}]", inner),
             Map { ref key, ref value } => write!(f, "{{{}: {}}}", key, value),
             Any => write!(f, "any"),
@@ -5,7 +5,7 @@
 //! diagnostics.
 
 use crate::errors::Result;
-use crate::utils::{find_range, Position};
+use crate::utils::{find_offset, find_range, Position};
 use crate::{Encoding, RelativePathBuf, Span};
 use ropey::Rope;
 use std::fmt;
@@ -236,6 +236,18 @@ impl Source {
     pub fn span_to_range(&self, span: Span, encoding: Encoding) -> Result<(Position, Position)> {
         find_range(self.read()?, span, encoding)
     }
+
+    /// Convert a range of positions into a span of byte offsets.
+    ///
+    /// This is the inverse of `Source::span_to_range`.
+    pub fn range_to_span(&self, range: (Position, Position), encoding: Encoding) -> Result<Span> {
+        let (start, end) = range;
+
+        Ok(Span {
+            start: find_offset(self.read()?, start, encoding)?,
+            end: find_offset(self.read()?, end, encoding)?,
+        })
+    }
 }
 
 impl fmt::Display for Source {
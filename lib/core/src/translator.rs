@@ -3,8 +3,8 @@
 use crate::errors::Result;
 use crate::Flavor;
 use crate::{
-    CoreFlavor, Diagnostics, RpEndpoint, RpEnumType, RpField, RpName, RpNumberType, RpReg,
-    RpStringType, RpType, RpVersionedPackage, Spanned,
+    CoreFlavor, Diagnostics, RpAliasBody, RpEndpoint, RpEnumType, RpField, RpName, RpNumberType,
+    RpReg, RpStringType, RpType, RpValidate, RpVersionedPackage, Spanned,
 };
 use linked_hash_map::LinkedHashMap;
 use std::cell::RefCell;
@@ -13,6 +13,9 @@ use std::collections::HashMap;
 use std::hash;
 use std::rc::Rc;
 
+/// Aliases by name.
+pub type Aliases<F> = HashMap<RpName<F>, RpAliasBody<F>>;
+
 /// Method for translating package.
 pub trait PackageTranslator<K, V>
 where
@@ -70,6 +73,18 @@ where
         name: Spanned<RpName<Self::Target>>,
     ) -> Result<<Self::Target as Flavor>::Type>;
 
+    /// Translate a reference to an alias, given the translated name of the alias and the type
+    /// that it aliases.
+    ///
+    /// References are resolved into the aliased type by default.
+    fn translate_alias(
+        &self,
+        _name: <Self::Target as Flavor>::Type,
+        ty: <Self::Target as Flavor>::Type,
+    ) -> Result<<Self::Target as Flavor>::Type> {
+        Ok(ty)
+    }
+
     /// Translate the given field.
     fn translate_field<T>(
         &self,
@@ -350,6 +365,8 @@ where
     pub types: Rc<LinkedHashMap<RpName<T::Source>, Spanned<RpReg>>>,
     /// Cached and translated registered declarations.
     pub decls: Option<Rc<RefCell<LinkedHashMap<RpName<T::Source>, RpReg>>>>,
+    /// Aliases to translate references through, unless aliases are kept as newtypes.
+    pub aliases: Option<Rc<Aliases<T::Source>>>,
 }
 
 impl<'a, T> Context<'a, T>
@@ -379,6 +396,31 @@ where
         let reg = decls.entry(key).or_insert(reg);
         Ok(reg.clone())
     }
+
+    /// Lookup the alias with the given name, if aliases are being resolved.
+    fn alias(&self, name: &RpName<T::Source>) -> Option<&RpAliasBody<T::Source>> {
+        let aliases = self.aliases.as_ref()?;
+        aliases.get(&name.clone().without_prefix())
+    }
+
+    /// Find the validation rules of the alias that the given type refers to, if any.
+    ///
+    /// Aliases of aliases inherit the validation rules of the alias they refer to.
+    fn alias_validate(&self, ty: &RpType<T::Source>) -> Option<RpValidate> {
+        let mut ty = ty;
+
+        while let RpType::Name { ref name } = *ty {
+            let alias = self.alias(name)?;
+
+            if let Some(ref validate) = alias.validate {
+                return Some(validate.clone());
+            }
+
+            ty = &alias.ty;
+        }
+
+        None
+    }
 }
 
 impl<'a, T> Translator for Context<'a, T>
@@ -422,6 +464,22 @@ where
             }
             Name { name } => {
                 let reg = self.lookup(diag, &name)?;
+
+                if let RpReg::Const = reg {
+                    diag.err(
+                        name.span(),
+                        format!("`{}` is a constant and can't be used as a type", *name),
+                    );
+                    return Err(format!("not a type: {}", *name).into());
+                }
+
+                if let Some(alias) = self.alias(&name) {
+                    let ty = self.translate_type(diag, alias.ty.clone())?;
+                    let name = name.translate(diag, self)?;
+                    let name = self.flavor.translate_name(self.from, reg, name)?;
+                    return self.flavor.translate_alias(name, ty);
+                }
+
                 let name = name.translate(diag, self)?;
                 self.flavor.translate_name(&self.from, reg, name)?
             }
@@ -431,6 +489,14 @@ where
                 self.flavor.translate_map(key, value)?
             }
             Any => self.flavor.translate_any()?,
+            Generic { name, .. } => {
+                diag.err(name.span(), "generic type has not been instantiated");
+                return Err(format!("not instantiated: {}", *name).into());
+            }
+            Parameter { name } => {
+                diag.err(name.span(), "type parameter has not been instantiated");
+                return Err(format!("not instantiated: {}", *name).into());
+            }
         };
 
         Ok(out)
@@ -439,8 +505,12 @@ where
     fn translate_field(
         &self,
         diag: &mut Diagnostics,
-        source: <Self::Source as Flavor>::Field,
+        mut source: <Self::Source as Flavor>::Field,
     ) -> Result<<Self::Target as Flavor>::Field> {
+        if source.validate.is_none() {
+            source.validate = self.alias_validate(&source.ty);
+        }
+
         self.flavor.translate_field(self, diag, source)
     }
 
@@ -1,6 +1,6 @@
 use crate::errors::Result;
 use crate::Span;
-use std::io::Read;
+use std::io::{BufReader, Read};
 
 const NL: u8 = b'\n';
 const CR: u8 = b'\r';
@@ -68,11 +68,9 @@ pub fn find_range<'a, R: AsMut<dyn Read + 'a>, S: Into<Span>>(
 
     let r = reader.as_mut();
 
-    let mut start = Position::default();
-    let mut end = Position::default();
+    let mut start = None;
 
     let mut line = 0usize;
-    let mut col = 0usize;
 
     // keep the current line in buffer.
     let mut buffer = Vec::new();
@@ -81,6 +79,22 @@ pub fn find_range<'a, R: AsMut<dyn Read + 'a>, S: Into<Span>>(
     while let Some((c, b)) = it.next() {
         let b = b?;
 
+        if c == span.start {
+            start = Some(Position {
+                line,
+                col: encoding.column(&buffer, buffer.len())?,
+            });
+        }
+
+        if c == span.end {
+            let end = Position {
+                line,
+                col: encoding.column(&buffer, buffer.len())?,
+            };
+
+            return Ok((start.unwrap_or(end), end));
+        }
+
         let nl = match b {
             // macos
             CR => {
@@ -97,29 +111,83 @@ pub fn find_range<'a, R: AsMut<dyn Read + 'a>, S: Into<Span>>(
 
         if nl {
             line += 1;
-            col = 0;
             buffer.clear();
         } else {
             buffer.push(b);
         }
+    }
 
-        if c == span.start {
-            start.line = line;
-            start.col = encoding.column(&buffer, col)?;
-        }
+    // the span extends to the end of the input.
+    let end = Position {
+        line,
+        col: encoding.column(&buffer, buffer.len())?,
+    };
 
-        if c == span.end {
-            end.line = line;
-            end.col = encoding.column(&buffer, col)?;
-            break;
+    Ok((start.unwrap_or(end), end))
+}
+
+/// Find the offset corresponding to the given position.
+///
+/// Columns past the end of a line are clamped to the end of that line, and lines past the end of
+/// the input are clamped to the end of the input.
+pub fn find_offset<'a, R: AsMut<dyn Read + 'a>>(
+    mut reader: R,
+    position: Position,
+    encoding: Encoding,
+) -> Result<usize> {
+    let r = reader.as_mut();
+
+    let mut line = 0usize;
+    let mut len = 0usize;
+
+    // keep the current line in buffer.
+    let mut buffer = Vec::new();
+    let mut it = BufReader::new(r).bytes().enumerate().peekable();
+
+    while let Some((c, b)) = it.next() {
+        let b = b?;
+        len = c + 1;
+
+        // NB: only check on character boundaries.
+        if line == position.line
+            && !is_continuation(b)
+            && encoding.column(&buffer, buffer.len())? >= position.col
+        {
+            return Ok(c);
         }
 
-        if !nl {
-            col += 1;
+        let nl = match b {
+            // macos
+            CR => {
+                // windows
+                if let Some(&(_, Ok(NL))) = it.peek() {
+                    it.next();
+                    len += 1;
+                }
+
+                true
+            }
+            NL => true,
+            _ => false,
+        };
+
+        if nl {
+            if line == position.line {
+                return Ok(c);
+            }
+
+            line += 1;
+            buffer.clear();
+        } else {
+            buffer.push(b);
         }
     }
 
-    Ok((start, end))
+    return Ok(len);
+
+    fn is_continuation(b: u8) -> bool {
+        b & 0xc0 == 0x80
+    }
 }
 
 /// Encoding for which to check the range.
@@ -162,3 +230,43 @@ impl Encoding {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn range(content: &str, start: usize, end: usize) -> (Position, Position) {
+        let mut reader: Box<dyn Read> = Box::new(Cursor::new(content.as_bytes().to_vec()));
+        find_range(&mut reader, Span { start, end }, Encoding::Utf16).unwrap()
+    }
+
+    fn offset(content: &str, line: usize, col: usize) -> usize {
+        let mut reader: Box<dyn Read> = Box::new(Cursor::new(content.as_bytes().to_vec()));
+        find_offset(&mut reader, Position { line, col }, Encoding::Utf16).unwrap()
+    }
+
+    fn pos(line: usize, col: usize) -> Position {
+        Position { line, col }
+    }
+
+    #[test]
+    fn test_find_range() {
+        let content = "ab\n\u{1f600}cd\n";
+
+        assert_eq!((pos(0, 0), pos(0, 2)), range(content, 0, 2));
+        assert_eq!((pos(1, 0), pos(1, 2)), range(content, 3, 7));
+        assert_eq!((pos(1, 3), pos(2, 0)), range(content, 8, 10));
+    }
+
+    #[test]
+    fn test_find_offset() {
+        let content = "ab\n\u{1f600}cd\n";
+
+        assert_eq!(2, offset(content, 0, 2));
+        assert_eq!(2, offset(content, 0, 10));
+        assert_eq!(7, offset(content, 1, 2));
+        assert_eq!(10, offset(content, 2, 0));
+        assert_eq!(10, offset(content, 5, 0));
+    }
+}
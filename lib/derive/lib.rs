@@ -1,5 +1,6 @@
 mod format;
 mod json;
+mod openapi;
 mod sir;
 mod utils;
 mod yaml;
@@ -24,6 +25,7 @@ use reproto_core::{RpPackage, Source, Span, Spanned, DEFAULT_TAG};
 
 pub use crate::format::Format;
 pub use crate::json::Json;
+pub use crate::openapi::open_api;
 use crate::sir::{FieldSir, Sir, SubTypeSir};
 pub use crate::yaml::Yaml;
 
@@ -215,6 +217,7 @@ impl<'a, 'input> FieldInit<'a, 'input> {
 
                 Type::Name {
                     name: Spanned::new(name, Span::empty()),
+                    arguments: Vec::new(),
                 }
             }
         };
@@ -230,6 +233,7 @@ impl<'a, 'input> FieldInit<'a, 'input> {
             name: name.clone().into(),
             ty: Spanned::new(ty.into(), self.span.clone()),
             field_as: field_as,
+            default: None,
             endl: true,
         };
 
@@ -332,6 +336,7 @@ impl<'a, 'input> TypeRefiner<'a, 'input> {
     ) -> Result<Item<'input, TypeBody<'input>>> {
         let mut body = TypeBody {
             name: Spanned::new(self.ctx.ident()?.to_string().into(), Span::empty()),
+            type_parameters: Vec::new(),
             members: Vec::new(),
         };
 
@@ -440,6 +445,7 @@ impl<'a, 'input> InterfaceRefiner<'a, 'input> {
 
         let mut body = InterfaceBody {
             name: Spanned::new(self.ctx.ident()?.to_string().into(), Span::empty()),
+            type_parameters: Vec::new(),
             members: Vec::new(),
             sub_types: Vec::new(),
         };
@@ -483,6 +489,7 @@ impl<'a, 'input> TupleRefiner<'a, 'input> {
     fn derive(&mut self, array: &[FieldSir]) -> Result<Item<'input, TupleBody<'input>>> {
         let mut body = TupleBody {
             name: Spanned::new(self.ctx.ident()?.to_string().into(), Span::empty()),
+            type_parameters: Vec::new(),
             members: Vec::new(),
         };
 
@@ -0,0 +1,939 @@
+//! Derive declarations from an OpenAPI 3 document.
+//!
+//! * `components/schemas` are mapped to types, enums, and interfaces.
+//! * `paths` are mapped to endpoints in a single service.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use inflector::cases::pascalcase::to_pascal_case;
+use inflector::cases::snakecase::to_snake_case;
+use reproto_ast::{
+    Attribute, AttributeItem, Channel, Decl, Endpoint, EndpointArgument, EnumBody, EnumVariant,
+    Field, InterfaceBody, Item, Name, ServiceBody, ServiceMember, SubType, Type, TypeBody,
+    TypeMember, Value,
+};
+use reproto_core::errors::Result;
+use reproto_core::{RpNumber, Source, Span, Spanned, DEFAULT_TAG};
+use serde_json as json;
+
+/// Prefix of references to schemas.
+const SCHEMAS: &str = "#/components/schemas/";
+
+/// HTTP methods, in the order that they are imported.
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Derive declarations from an OpenAPI 3 document, which can be either JSON or YAML.
+///
+/// Endpoints are put in a service named after the title of the document, or `service_name` if it
+/// does not have one.
+pub fn open_api<'input>(service_name: &str, source: &Source) -> Result<Vec<Decl<'input>>> {
+    let document: json::Value = serde_yaml::from_reader(source.read()?)
+        .map_err(|e| format!("Bad OpenAPI document: {}", e))?;
+
+    let service_name = document
+        .pointer("/info/title")
+        .and_then(|t| t.as_str())
+        .map(to_pascal_case)
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| service_name.to_string());
+
+    let mut importer = Importer {
+        document: &document,
+        sub_types: HashMap::new(),
+    };
+
+    importer.index_sub_types()?;
+
+    let mut decls = Vec::new();
+
+    if let Some(schemas) = document
+        .pointer("/components/schemas")
+        .and_then(|s| s.as_object())
+    {
+        for (key, schema) in schemas {
+            // emitted as part of their interface.
+            if importer.sub_types.contains_key(key) {
+                continue;
+            }
+
+            let schema = importer.resolve(schema)?;
+
+            if !is_decl(schema) {
+                continue;
+            }
+
+            let path = vec![to_pascal_case(key)];
+            decls.push(importer.decl(&path, schema)?);
+        }
+    }
+
+    if let Some(paths) = document.get("paths").and_then(|p| p.as_object()) {
+        if !paths.is_empty() {
+            decls.push(importer.service(&service_name, paths)?);
+        }
+    }
+
+    Ok(decls)
+}
+
+struct Importer<'a> {
+    document: &'a json::Value,
+    /// Schemas which are sub-types of an interface, and the name of that interface.
+    sub_types: HashMap<String, String>,
+}
+
+impl<'a> Importer<'a> {
+    /// Find all schemas which are used as sub-types of interfaces.
+    fn index_sub_types(&mut self) -> Result<()> {
+        let schemas = match self
+            .document
+            .pointer("/components/schemas")
+            .and_then(|s| s.as_object())
+        {
+            Some(schemas) => schemas,
+            None => return Ok(()),
+        };
+
+        for (key, schema) in schemas {
+            for member in array(schema, "oneOf") {
+                if let Some(sub_type) = member.get("$ref").and_then(|r| r.as_str()) {
+                    if let Some(sub_type) = sub_type.strip_prefix(SCHEMAS) {
+                        self.sub_types.insert(sub_type.to_string(), key.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Follow any references to get the schema or object that they refer to.
+    fn resolve(&self, mut value: &'a json::Value) -> Result<&'a json::Value> {
+        // guard against cyclic references.
+        for _ in 0..32 {
+            let reference = match value.get("$ref").and_then(|r| r.as_str()) {
+                Some(reference) => reference,
+                None => return Ok(value),
+            };
+
+            let pointer = reference
+                .strip_prefix('#')
+                .ok_or_else(|| format!("Unsupported reference: {}", reference))?;
+
+            value = self
+                .document
+                .pointer(pointer)
+                .ok_or_else(|| format!("Missing reference: {}", reference))?;
+        }
+
+        Err("Too many nested references".into())
+    }
+
+    /// Build a declaration for a schema.
+    fn decl<'input>(&self, path: &[String], schema: &json::Value) -> Result<Decl<'input>> {
+        let comment = description(schema);
+        let name = spanned(Cow::from(path[path.len() - 1].clone()));
+
+        if let Some(values) = schema.get("enum").and_then(|e| e.as_array()) {
+            let mut variants = Vec::new();
+
+            for value in values {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| format!("Unsupported enum value: {}", value))?;
+
+                let variant = EnumVariant {
+                    name: spanned(Cow::from(variant_name(value))),
+                    argument: Some(spanned(Value::String(value.to_string()))),
+                };
+
+                variants.push(item(variant));
+            }
+
+            let body = EnumBody {
+                name,
+                ty: spanned(Type::String),
+                variants,
+                members: Vec::new(),
+            };
+
+            return Ok(Decl::Enum(Item {
+                comment,
+                ..item(body)
+            }));
+        }
+
+        if schema.get("oneOf").is_some() {
+            return self.interface(path, name, comment, schema);
+        }
+
+        let mut body = TypeBody {
+            name,
+            type_parameters: Vec::new(),
+            members: Vec::new(),
+        };
+
+        self.fields(path, schema, None, &mut body.members)?;
+
+        Ok(Decl::Type(Item {
+            comment,
+            ..item(body)
+        }))
+    }
+
+    /// Build an interface from a schema with `oneOf`.
+    ///
+    /// Sub-types are tagged if the schema has a `discriminator`, otherwise they are untagged.
+    fn interface<'input>(
+        &self,
+        path: &[String],
+        name: Spanned<Cow<'input, str>>,
+        comment: Vec<Cow<'input, str>>,
+        schema: &'a json::Value,
+    ) -> Result<Decl<'input>> {
+        let discriminator = schema.get("discriminator");
+
+        let tag = discriminator
+            .and_then(|d| d.get("propertyName"))
+            .and_then(|p| p.as_str());
+
+        let mapping = discriminator
+            .and_then(|d| d.get("mapping"))
+            .and_then(|m| m.as_object());
+
+        let mut values = Vec::new();
+
+        match tag {
+            Some(tag) => {
+                if tag != DEFAULT_TAG {
+                    values.push(name_value("strategy", "tagged"));
+                    values.push(name_value("tag", tag));
+                }
+            }
+            None => values.push(name_value("strategy", "untagged")),
+        }
+
+        let mut attributes = Vec::new();
+
+        if !values.is_empty() {
+            let type_info = Attribute::List(spanned(Cow::from("type_info")), values);
+            attributes.push(spanned(type_info));
+        }
+
+        let mut body = InterfaceBody {
+            name,
+            type_parameters: Vec::new(),
+            members: Vec::new(),
+            sub_types: Vec::new(),
+        };
+
+        for (index, member) in array(schema, "oneOf").enumerate() {
+            let reference = member.get("$ref").and_then(|r| r.as_str());
+
+            let (ident, alias) = match reference {
+                Some(reference) => {
+                    let key = reference.strip_prefix(SCHEMAS).unwrap_or(reference);
+                    let key = key.rsplit('/').next().unwrap_or(key);
+
+                    let alias = mapping
+                        .and_then(|m| m.iter().find(|(_, r)| r.as_str() == Some(reference)))
+                        .map(|(alias, _)| alias.to_string())
+                        .unwrap_or_else(|| key.to_string());
+
+                    (to_pascal_case(key), alias)
+                }
+                None => {
+                    let ident = format!("Variant{}", index + 1);
+                    (ident.clone(), ident)
+                }
+            };
+
+            let member = self.resolve(member)?;
+
+            let mut sub_path = path.to_vec();
+            sub_path.push(ident.clone());
+
+            let mut sub_type = SubType {
+                name: spanned(Cow::from(ident.clone())),
+                members: Vec::new(),
+                alias: None,
+            };
+
+            if tag.is_some() && alias != ident {
+                sub_type.alias = Some(spanned(Value::String(alias)));
+            }
+
+            self.fields(&sub_path, member, tag, &mut sub_type.members)?;
+
+            body.sub_types.push(Item {
+                comment: description(member),
+                ..item(sub_type)
+            });
+        }
+
+        Ok(Decl::Interface(Item {
+            comment,
+            attributes,
+            item: spanned(body),
+        }))
+    }
+
+    /// Add fields for the properties of an object, including the properties of all schemas it is
+    /// composed of through `allOf`.
+    ///
+    /// The `exclude`d property is skipped, which is used for the tag of sub-types.
+    fn fields<'input>(
+        &self,
+        path: &[String],
+        schema: &'a json::Value,
+        exclude: Option<&str>,
+        members: &mut Vec<TypeMember<'input>>,
+    ) -> Result<()> {
+        let mut inner = Vec::new();
+        self.properties(path, schema, exclude, members, &mut inner)?;
+        members.extend(inner.into_iter().map(TypeMember::InnerDecl));
+        Ok(())
+    }
+
+    fn properties<'input>(
+        &self,
+        path: &[String],
+        schema: &'a json::Value,
+        exclude: Option<&str>,
+        members: &mut Vec<TypeMember<'input>>,
+        inner: &mut Vec<Decl<'input>>,
+    ) -> Result<()> {
+        for part in array(schema, "allOf") {
+            let part = self.resolve(part)?;
+            self.properties(path, part, exclude, members, inner)?;
+        }
+
+        let properties = match schema.get("properties").and_then(|p| p.as_object()) {
+            Some(properties) => properties,
+            None => return Ok(()),
+        };
+
+        let required = array(schema, "required")
+            .filter_map(|r| r.as_str())
+            .collect::<Vec<_>>();
+
+        for (original, property) in properties {
+            if Some(original.as_str()) == exclude {
+                continue;
+            }
+
+            let name = to_snake_case(original);
+
+            let nullable = property
+                .get("nullable")
+                .and_then(|n| n.as_bool())
+                .unwrap_or_default();
+
+            let ty = self.ty(path, &name, property, inner)?;
+            let default = if nullable {
+                None
+            } else {
+                default(&ty, property)
+            };
+
+            let field = Field {
+                required: (required.contains(&original.as_str()) && !nullable) || default.is_some(),
+                name: Cow::from(name.clone()),
+                ty: spanned(ty),
+                field_as: if name != *original {
+                    Some(original.to_string())
+                } else {
+                    None
+                },
+                default: default.map(spanned),
+                endl: true,
+            };
+
+            let mut comment = description(property);
+            let attributes = validate(path, &name, property, &mut comment);
+
+            members.push(TypeMember::Field(Item {
+                comment,
+                attributes,
+                item: spanned(field),
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Convert a schema into a type.
+    ///
+    /// Inline objects and enums are added to `inner` as declarations, named after the field or
+    /// argument that they belong to.
+    fn ty<'input>(
+        &self,
+        path: &[String],
+        name: &str,
+        schema: &'a json::Value,
+        inner: &mut Vec<Decl<'input>>,
+    ) -> Result<Type<'input>> {
+        if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+            let target = self.resolve(schema)?;
+
+            // references to primitive schemas are inlined.
+            let key = match reference.strip_prefix(SCHEMAS) {
+                Some(key) if is_decl(target) => key,
+                _ => return self.ty(path, name, target, inner),
+            };
+
+            let mut parts = Vec::new();
+
+            if let Some(interface) = self.sub_types.get(key) {
+                parts.push(to_pascal_case(interface));
+            }
+
+            parts.push(to_pascal_case(key));
+            return Ok(name_type(parts));
+        }
+
+        // inline declaration.
+        if is_decl(schema) {
+            let mut inner_path = path.to_vec();
+            inner_path.push(to_pascal_case(name));
+            inner.push(self.decl(&inner_path, schema)?);
+            return Ok(name_type(inner_path));
+        }
+
+        let format = schema.get("format").and_then(|f| f.as_str());
+
+        let ty = match schema.get("type").and_then(|t| t.as_str()) {
+            Some("string") => match format {
+                Some("date-time") => Type::DateTime,
+                Some("byte") | Some("binary") => Type::Bytes,
+                _ => Type::String,
+            },
+            Some("integer") => match format {
+                Some("int32") => Type::Signed { size: 32 },
+                Some("uint32") => Type::Unsigned { size: 32 },
+                Some("uint64") => Type::Unsigned { size: 64 },
+                _ => Type::Signed { size: 64 },
+            },
+            Some("number") => match format {
+                Some("float") => Type::Float,
+                _ => Type::Double,
+            },
+            Some("boolean") => Type::Boolean,
+            Some("array") => {
+                let ty = match schema.get("items") {
+                    Some(items) => self.ty(path, name, items, inner)?,
+                    None => Type::Any,
+                };
+
+                Type::Array {
+                    inner: Box::new(spanned(ty)),
+                }
+            }
+            Some("object") => match schema.get("additionalProperties") {
+                Some(value) if value.is_object() => {
+                    let value = self.ty(path, name, value, inner)?;
+
+                    Type::Map {
+                        key: Box::new(spanned(Type::String)),
+                        value: Box::new(spanned(value)),
+                    }
+                }
+                _ => Type::Any,
+            },
+            _ => Type::Any,
+        };
+
+        Ok(ty)
+    }
+
+    /// Build a service from all paths.
+    fn service<'input>(
+        &self,
+        service_name: &str,
+        paths: &'a json::Map<String, json::Value>,
+    ) -> Result<Decl<'input>> {
+        let mut endpoints = Vec::new();
+        let mut inner = Vec::new();
+
+        for (path, item) in paths {
+            let item = self.resolve(item)?;
+
+            for method in METHODS {
+                let operation = match item.get(*method) {
+                    Some(operation) => operation,
+                    None => continue,
+                };
+
+                endpoints.push(self.endpoint(
+                    service_name,
+                    path,
+                    method,
+                    item,
+                    operation,
+                    &mut inner,
+                )?);
+            }
+        }
+
+        let mut members = Vec::new();
+        members.extend(endpoints.into_iter().map(ServiceMember::Endpoint));
+        members.extend(inner.into_iter().map(ServiceMember::InnerDecl));
+
+        let body = ServiceBody {
+            name: spanned(Cow::from(service_name.to_string())),
+            members,
+        };
+
+        Ok(Decl::Service(item(body)))
+    }
+
+    /// Build a single endpoint from an operation.
+    fn endpoint<'input>(
+        &self,
+        service_name: &str,
+        path: &str,
+        method: &str,
+        item: &'a json::Value,
+        operation: &'a json::Value,
+        inner: &mut Vec<Decl<'input>>,
+    ) -> Result<Item<'input, Endpoint<'input>>> {
+        let id = match operation.get("operationId").and_then(|o| o.as_str()) {
+            Some(id) => to_snake_case(id),
+            None => to_snake_case(&format!("{} {}", method, path)),
+        };
+
+        let service_path = vec![service_name.to_string()];
+        let mut comment = description(operation);
+        let mut path = path.to_string();
+        let mut arguments = Vec::new();
+
+        let parameters = array(item, "parameters").chain(array(operation, "parameters"));
+
+        for parameter in parameters {
+            let parameter = self.resolve(parameter)?;
+
+            let name = match parameter.get("name").and_then(|n| n.as_str()) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            // only path parameters can be represented in endpoints.
+            match parameter.get("in").and_then(|i| i.as_str()) {
+                Some("path") => {}
+                location => {
+                    comment.push(Cow::from(format!(
+                        "NOTE: {} parameter `{}` was not imported.",
+                        location.unwrap_or("unknown"),
+                        name
+                    )));
+                    continue;
+                }
+            }
+
+            let ident = to_snake_case(name);
+
+            // path variables must be named the same as their arguments.
+            path = path.replace(&format!("{{{}}}", name), &format!("{{{}}}", ident));
+
+            let ty = match parameter.get("schema") {
+                Some(schema) => {
+                    let name = format!("{}_{}", id, ident);
+                    self.ty(&service_path, &name, schema, inner)?
+                }
+                None => Type::String,
+            };
+
+            arguments.push(EndpointArgument {
+                ident: spanned(Cow::from(ident)),
+                channel: spanned(Channel::Unary { ty: spanned(ty) }),
+            });
+        }
+
+        if let Some(body) = operation.get("requestBody") {
+            let body = self.resolve(body)?;
+
+            if let Some(ty) =
+                self.content(&service_path, &format!("{}_request", id), body, inner)?
+            {
+                // the first argument is the request body of the endpoint.
+                arguments.insert(
+                    0,
+                    EndpointArgument {
+                        ident: spanned(Cow::from("body")),
+                        channel: spanned(Channel::Unary { ty: spanned(ty) }),
+                    },
+                );
+            }
+        }
+
+        let mut response = None;
+
+        if let Some(responses) = operation.get("responses").and_then(|r| r.as_object()) {
+            let success = responses
+                .iter()
+                .find(|(status, _)| status.starts_with('2'))
+                .or_else(|| responses.iter().find(|(status, _)| *status == "default"));
+
+            if let Some((_, value)) = success {
+                let value = self.resolve(value)?;
+                let name = format!("{}_response", id);
+
+                if let Some(ty) = self.content(&service_path, &name, value, inner)? {
+                    response = Some(spanned(Channel::Unary { ty: spanned(ty) }));
+                }
+            }
+        }
+
+        let mut values = vec![name_value("path", &path)];
+
+        if method != "get" {
+            values.push(name_value("method", &method.to_uppercase()));
+        }
+
+        let http = Attribute::List(spanned(Cow::from("http")), values);
+
+        let endpoint = Endpoint {
+            id: spanned(Cow::from(id)),
+            alias: None,
+            arguments,
+            response,
+        };
+
+        Ok(Item {
+            comment,
+            attributes: vec![spanned(http)],
+            item: spanned(endpoint),
+        })
+    }
+
+    /// Convert the content of a request body or a response into a type.
+    fn content<'input>(
+        &self,
+        path: &[String],
+        name: &str,
+        value: &'a json::Value,
+        inner: &mut Vec<Decl<'input>>,
+    ) -> Result<Option<Type<'input>>> {
+        let content = match value.get("content").and_then(|c| c.as_object()) {
+            Some(content) => content,
+            None => return Ok(None),
+        };
+
+        if let Some(schema) = content
+            .get("application/json")
+            .and_then(|j| j.get("schema"))
+        {
+            return Ok(Some(self.ty(path, name, schema, inner)?));
+        }
+
+        if content.contains_key("text/plain") {
+            return Ok(Some(Type::String));
+        }
+
+        Ok(None)
+    }
+}
+
+/// Check if the schema should be imported as a declaration.
+fn is_decl(schema: &json::Value) -> bool {
+    if let Some(values) = schema.get("enum").and_then(|e| e.as_array()) {
+        return values.iter().all(|v| v.is_string());
+    }
+
+    if schema.get("oneOf").is_some() || schema.get("allOf").is_some() {
+        return true;
+    }
+
+    if schema.get("properties").is_some() {
+        return true;
+    }
+
+    schema.get("type").and_then(|t| t.as_str()) == Some("object")
+        && schema.get("additionalProperties").is_none()
+}
+
+/// Convert a value into the name of an enum variant.
+fn variant_name(value: &str) -> String {
+    let name = to_pascal_case(value);
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        _ => format!("V{}", name),
+    }
+}
+
+/// Iterate over the array stored under the given key.
+fn array<'a>(value: &'a json::Value, key: &str) -> impl Iterator<Item = &'a json::Value> {
+    value
+        .get(key)
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+}
+
+/// Build a comment from the description of an object.
+fn description<'input>(value: &json::Value) -> Vec<Cow<'input, str>> {
+    let description = match value.get("description").and_then(|d| d.as_str()) {
+        Some(description) => description,
+        None => return Vec::new(),
+    };
+
+    description
+        .trim_end()
+        .lines()
+        .map(|l| Cow::from(l.to_string()))
+        .collect()
+}
+
+/// Build the default value of a property, if it is supported by the type of the property.
+fn default<'input>(ty: &Type<'input>, schema: &json::Value) -> Option<Value<'input>> {
+    let value = match (ty, schema.get("default")?) {
+        (Type::String, json::Value::String(string)) => Value::String(string.to_string()),
+        (Type::Boolean, json::Value::Bool(boolean)) => {
+            Value::Identifier(Cow::from(if *boolean { "true" } else { "false" }))
+        }
+        (Type::Signed { .. }, json::Value::Number(number))
+        | (Type::Unsigned { .. }, json::Value::Number(number))
+        | (Type::Float, json::Value::Number(number))
+        | (Type::Double, json::Value::Number(number)) => {
+            // only whole numbers are supported.
+            match (number.as_u64(), number.as_i64()) {
+                (Some(n), _) => Value::Number(RpNumber::from(n)),
+                (None, Some(n)) => Value::Number(RpNumber::from(n)),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Build `#[validate(..)]` attributes from the constraints of a schema.
+///
+/// Constraints which can't be represented are noted in `comment` instead.
+fn validate<'input>(
+    path: &[String],
+    name: &str,
+    schema: &json::Value,
+    comment: &mut Vec<Cow<'input, str>>,
+) -> Vec<Spanned<Attribute<'input>>> {
+    let mut values = Vec::new();
+
+    for (key, name) in &[
+        ("minimum", "min"),
+        ("maximum", "max"),
+        ("minLength", "min_length"),
+        ("maxLength", "max_length"),
+    ] {
+        let number = match schema.get(key) {
+            Some(json::Value::Number(number)) => number,
+            _ => continue,
+        };
+
+        // only whole numbers are supported.
+        let number = match (number.as_u64(), number.as_i64()) {
+            (Some(n), _) => RpNumber::from(n),
+            (None, Some(n)) => RpNumber::from(n),
+            _ => continue,
+        };
+
+        values.push(AttributeItem::NameValue {
+            name: spanned(Cow::from(*name)),
+            value: spanned(Value::Number(number)),
+        });
+    }
+
+    if let Some(original) = schema.get("pattern").and_then(|p| p.as_str()) {
+        // reproto patterns always match the whole string, so anchors are redundant.
+        let pattern = original.strip_prefix('^').unwrap_or(original);
+
+        let pattern = match pattern.strip_suffix('$') {
+            Some(stripped) if !stripped.ends_with('\\') => stripped,
+            _ => pattern,
+        };
+
+        match regex_parser::parse(pattern) {
+            Ok(_) => values.push(name_value("pattern", pattern)),
+            Err(e) => comment.push(Cow::from(format!(
+                "NOTE: pattern `{}` of `{}::{}` was not imported: {}",
+                original,
+                path.join("::"),
+                name,
+                e.display()
+            ))),
+        }
+    }
+
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    vec![spanned(Attribute::List(
+        spanned(Cow::from("validate")),
+        values,
+    ))]
+}
+
+/// Build a `name = "value"` attribute item.
+fn name_value<'input>(name: &'static str, value: &str) -> AttributeItem<'input> {
+    AttributeItem::NameValue {
+        name: spanned(Cow::from(name)),
+        value: spanned(Value::String(value.to_string())),
+    }
+}
+
+/// Build a type referring to the declaration at the given path.
+fn name_type<'input>(path: Vec<String>) -> Type<'input> {
+    let name = Name::Absolute {
+        prefix: None,
+        path: path.into_iter().map(|p| spanned(Cow::from(p))).collect(),
+    };
+
+    Type::Name {
+        name: spanned(name),
+        arguments: Vec::new(),
+    }
+}
+
+fn item<'input, T>(value: T) -> Item<'input, T> {
+    Item {
+        comment: Vec::new(),
+        attributes: Vec::new(),
+        item: spanned(value),
+    }
+}
+
+fn spanned<T>(value: T) -> Spanned<T> {
+    Spanned::new(value, Span::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::open_api;
+    use reproto_ast::{Attribute, AttributeItem, Decl, ServiceMember, TypeMember, Value};
+    use reproto_core::Source;
+
+    const DOCUMENT: &str = r##"
+openapi: 3.0.0
+info:
+  title: pets
+paths:
+  /pets/{petId}:
+    get:
+      operationId: getPet
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+components:
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: "#/components/schemas/Dog"
+      discriminator:
+        propertyName: kind
+        mapping:
+          dog: "#/components/schemas/Dog"
+    Dog:
+      type: object
+      required: [kind, name]
+      properties:
+        kind:
+          type: string
+        name:
+          type: string
+          minLength: 1
+          pattern: "^[a-z]+$"
+        breed:
+          type: string
+          pattern: "^[a-z]+$|^[0-9]+$"
+        owner:
+          type: object
+          properties:
+            id:
+              type: integer
+              format: int32
+    Size:
+      type: string
+      enum: [small, large]
+"##;
+
+    #[test]
+    fn test_derive() {
+        let source = Source::bytes("test", DOCUMENT.as_bytes().to_vec());
+        let decls = open_api("Generated", &source).expect("bad document");
+
+        let names = decls.iter().map(|d| *d.name()).collect::<Vec<_>>();
+        assert_eq!(vec!["Pet", "Size", "Pets"], names);
+
+        let interface = match decls[0] {
+            Decl::Interface(ref interface) => interface,
+            ref other => panic!("expected interface, got: {:?}", other),
+        };
+
+        assert_eq!(1, interface.attributes.len());
+        assert_eq!(1, interface.sub_types.len());
+
+        // the tag is excluded, and the inline object is an inner declaration.
+        let dog = &interface.sub_types[0];
+        assert_eq!("Dog", dog.name.as_ref());
+        assert_eq!(4, dog.members.len());
+        assert!(matches!(dog.members[3], TypeMember::InnerDecl(..)));
+
+        // anchors are stripped, since reproto patterns are always anchored.
+        let name = match dog.members[0] {
+            TypeMember::Field(ref name) => name,
+            _ => panic!("expected field"),
+        };
+
+        match *name.attributes[0] {
+            Attribute::List(_, ref values) => match values[1] {
+                AttributeItem::NameValue {
+                    ref name,
+                    ref value,
+                } => {
+                    assert_eq!("pattern", name.as_ref());
+                    assert_eq!(Value::String("[a-z]+".to_string()), **value);
+                }
+                _ => panic!("expected pattern"),
+            },
+            _ => panic!("expected validate attribute"),
+        }
+
+        // unsupported patterns are dropped with a note.
+        let breed = match dog.members[1] {
+            TypeMember::Field(ref breed) => breed,
+            _ => panic!("expected field"),
+        };
+
+        assert!(breed.attributes.is_empty());
+        assert_eq!(
+            vec!["NOTE: pattern `^[a-z]+$|^[0-9]+$` of `Pet::Dog::breed` was not imported: syntax error"],
+            breed.comment
+        );
+
+        let service = match decls[2] {
+            Decl::Service(ref service) => service,
+            ref other => panic!("expected service, got: {:?}", other),
+        };
+
+        let endpoint = match service.members[0] {
+            ServiceMember::Endpoint(ref endpoint) => endpoint,
+            _ => panic!("expected endpoint"),
+        };
+
+        assert_eq!("get_pet", endpoint.id.as_ref());
+        assert_eq!("pet_id", endpoint.arguments[0].ident.as_ref());
+        assert!(endpoint.response.is_some());
+    }
+}
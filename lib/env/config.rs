@@ -14,6 +14,11 @@ pub struct Repository {
     /// URL to objects source.
     /// FIXME: Can't use Url type directly here with `url_serde`, since it's not seen as optional.
     pub objects: Option<String>,
+    /// Token to authenticate with when accessing `index` or `objects` over HTTP.
+    pub token: Option<String>,
+    /// Permit accessing `index` or `objects` over plain HTTP, which sends `token` unencrypted.
+    #[serde(default)]
+    pub allow_http: bool,
 }
 
 impl Default for Repository {
@@ -21,6 +26,8 @@ impl Default for Repository {
         Repository {
             index: None,
             objects: None,
+            token: None,
+            allow_http: false,
         }
     }
 }
@@ -13,6 +13,8 @@ pub struct ConfigEnvironment {
     pub bin_home: PathBuf,
     pub index: Option<String>,
     pub objects: Option<String>,
+    pub token: Option<String>,
+    pub allow_http: bool,
 }
 
 impl ConfigEnvironment {
@@ -42,6 +44,8 @@ impl ConfigEnvironment {
 
         let mut index = None;
         let mut objects = None;
+        let mut token = None;
+        let mut allow_http = false;
 
         if config.is_file() {
             let config = read_config(&config)?;
@@ -50,6 +54,8 @@ impl ConfigEnvironment {
                 // set values from configuration (if not already set).
                 index = index.or(repository.index);
                 objects = objects.or(repository.objects);
+                token = token.or(repository.token);
+                allow_http = repository.allow_http;
             }
 
             if let Some(out) = config.cache_home {
@@ -71,6 +77,8 @@ impl ConfigEnvironment {
             bin_home,
             index,
             objects,
+            token,
+            allow_http,
         }));
     }
 }
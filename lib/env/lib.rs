@@ -23,6 +23,8 @@ fn load_index(
     url: &str,
     publishing: bool,
     config: IndexConfig,
+    token: Option<String>,
+    allow_http: bool,
 ) -> Result<Box<dyn Index>> {
     let index_path = Path::new(url);
 
@@ -41,7 +43,19 @@ fn load_index(
             index_from_path(&path).map_err(Into::into)
         }
         Err(e) => return Err(e.into()),
-        Ok(url) => index_from_url(config, &url, publishing).map_err(Into::into),
+        Ok(url) => index_from_url(
+            config,
+            &url,
+            |_, scheme, url| match scheme {
+                "http" | "https" => Ok(Some(repository_http::index_from_url(
+                    url,
+                    token.clone(),
+                    allow_http,
+                )?)),
+                _ => Ok(None),
+            },
+            publishing,
+        ),
     }
 }
 
@@ -51,6 +65,8 @@ fn load_objects(
     index_url: &str,
     objects: Option<String>,
     config: ObjectsConfig,
+    token: Option<String>,
+    allow_http: bool,
 ) -> Result<Box<dyn Objects>> {
     let (objects_url, publishing) = if let Some(ref objects) = objects {
         (objects.as_ref(), true)
@@ -81,8 +97,12 @@ fn load_objects(
             config,
             &url,
             |config, scheme, url| match scheme {
-                "http" => Ok(Some(repository_http::objects_from_url(config, url)?)),
-                "https" => Ok(Some(repository_http::objects_from_url(config, url)?)),
+                "http" | "https" => Ok(Some(repository_http::objects_from_url(
+                    config,
+                    url,
+                    token.clone(),
+                    allow_http,
+                )?)),
                 _ => Ok(None),
             },
             publishing,
@@ -109,14 +129,26 @@ pub fn repository(manifest: &Manifest) -> Result<Repository> {
 
     let repo_dir;
     let cache_home;
+    let index_token;
+    let objects_token;
+    let allow_http;
     let mut index = repository.index.clone();
     let mut objects = repository.objects.clone();
 
     if let Some(config_env) = ConfigEnvironment::new()? {
         repo_dir = config_env.repo_dir;
+        allow_http = config_env.allow_http;
         cache_home = config_env.cache_home;
         index = index.or(config_env.index.clone());
         objects = objects.or(config_env.objects.clone());
+
+        index_token = credentials_for(&config_env.token, &config_env.index, &index);
+
+        // objects without an explicit URL are loaded from the index.
+        objects_token = match objects {
+            Some(_) => credentials_for(&config_env.token, &config_env.objects, &objects),
+            None => index_token.clone(),
+        };
     } else {
         return Ok(Repository::new(Box::new(NoIndex), Box::new(NoObjects)));
     }
@@ -130,7 +162,14 @@ pub fn repository(manifest: &Manifest) -> Result<Repository> {
         repo_dir: repo_dir.clone(),
     };
 
-    let index = load_index(base, index_url.as_str(), index_publishing, index_config)?;
+    let index = load_index(
+        base,
+        index_url.as_str(),
+        index_publishing,
+        index_config,
+        index_token,
+        allow_http,
+    )?;
 
     let objects_config = ObjectsConfig {
         repo_dir,
@@ -144,11 +183,26 @@ pub fn repository(manifest: &Manifest) -> Result<Repository> {
         index_url.as_str(),
         objects,
         objects_config,
+        objects_token,
+        allow_http,
     )?;
 
     Ok(Repository::new(index, objects))
 }
 
+/// Only use credentials for the URL that they were configured together with, to avoid sending
+/// them to other repositories.
+fn credentials_for(
+    token: &Option<String>,
+    configured: &Option<String>,
+    url: &Option<String>,
+) -> Option<String> {
+    match (configured, url) {
+        (Some(configured), Some(url)) if configured == url => token.clone(),
+        _ => None,
+    }
+}
+
 /// Setup the path-based resolver from a manifest.
 pub fn path_resolver(manifest: &Manifest) -> Result<Option<Box<dyn Resolver>>> {
     if manifest.paths.is_empty() {
@@ -203,6 +257,7 @@ pub fn convert_lang(input: Language) -> Box<dyn Lang> {
         Reproto => Box::new(reproto::ReprotoLang),
         Rust => Box::new(rust::RustLang),
         Swift => Box::new(swift::SwiftLang),
+        TypeScript => Box::new(ts::TsLang),
         OpenApi => Box::new(openapi::OpenApiLang),
     }
 }
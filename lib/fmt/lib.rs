@@ -0,0 +1,842 @@
+//! Formatter for reproto specifications.
+//!
+//! Formatting is performed on the syntax tree together with the original input, so unlike
+//! `reproto-backend-reproto` it preserves comments, the order of `use` declarations, and
+//! attributes which are not understood by the compiler.
+
+use ast::{
+    Attribute, AttributeItem, Channel, Code, Decl, EnumMember, Item, Name, ServiceMember, Type,
+    TypeMember, UseDecl, Value,
+};
+use reproto_core::{Diagnostics, Span, Spanned};
+use std::borrow::Cow;
+use std::error;
+use std::fmt;
+
+/// Indentation used for each level of nesting.
+const INDENT: &str = "  ";
+
+/// Error raised when the input can't be formatted.
+///
+/// The reason is reported to the diagnostics passed to the formatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("input contains errors")
+    }
+}
+
+impl error::Error for Error {}
+
+/// Format the given input.
+///
+/// Syntax errors are reported to `diag`.
+pub fn format(diag: &mut Diagnostics, input: &str) -> Result<String, Error> {
+    let file = parse(diag, input)?;
+
+    let mut f = Formatter::new(input)?;
+    f.file(&file);
+    Ok(f.out)
+}
+
+/// Format all top-level declarations which overlap with the given span.
+///
+/// Returns the span of the input that should be replaced, and what to replace it with.
+pub fn format_range(
+    diag: &mut Diagnostics,
+    input: &str,
+    span: Span,
+) -> Result<Option<(Span, String)>, Error> {
+    let file = parse(diag, input)?;
+
+    let decls = file
+        .decls
+        .iter()
+        .filter(|decl| {
+            let s = decl_span(input, decl);
+            s.start <= span.end && span.start <= s.end
+        })
+        .collect::<Vec<_>>();
+
+    let (first, last) = match (decls.first(), decls.last()) {
+        (Some(first), Some(last)) => (decl_span(input, first), decl_span(input, last)),
+        _ => return Ok(None),
+    };
+
+    let mut f = Formatter::new(input)?;
+    f.seek(first.start);
+
+    for decl in decls {
+        f.decl(decl);
+        f.blank = true;
+    }
+
+    f.comments(last.end);
+
+    // NB: trailing comments might extend past the last declaration.
+    let span = Span {
+        start: first.start,
+        end: usize::max(last.end, f.cursor),
+    };
+
+    let out = f.out.trim_end_matches('\n').to_string();
+    Ok(Some((span, out)))
+}
+
+/// Parse the given input, making sure that it doesn't contain any errors.
+fn parse<'input>(diag: &mut Diagnostics, input: &'input str) -> Result<ast::File<'input>, Error> {
+    let file = parser::parse(diag, input).map_err(|()| Error)?;
+
+    if diag.has_errors() {
+        return Err(Error);
+    }
+
+    Ok(file)
+}
+
+/// Get the span of an item, including its attributes.
+fn item_span<T>(item: &Item<T>) -> Span {
+    let span = item.item.span();
+
+    match item.attributes.first() {
+        Some(a) => Span {
+            start: a.span().start,
+            end: span.end,
+        },
+        None => span,
+    }
+}
+
+/// Format the type parameters of a declaration, if it has any.
+fn type_parameters(parameters: &[Spanned<Cow<str>>]) -> String {
+    if parameters.is_empty() {
+        return String::new();
+    }
+
+    let parameters = parameters.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    format!("<{}>", parameters.join(", "))
+}
+
+/// Get the span of a declaration, including its documentation and attributes.
+fn decl_span(input: &str, decl: &Decl) -> Span {
+    let span = match *decl {
+        Decl::Type(ref item) => item_span(item),
+        Decl::Tuple(ref item) => item_span(item),
+        Decl::Interface(ref item) => item_span(item),
+        Decl::Enum(ref item) => item_span(item),
+        Decl::Service(ref item) => item_span(item),
+        Decl::Const(ref item) => item_span(item),
+        Decl::Alias(ref item) => item_span(item),
+    };
+
+    return Span {
+        start: doc_start(input, span.start),
+        end: span.end,
+    };
+
+    /// Documentation comments are not part of the span of an item, so find where they start by
+    /// walking backwards from the item one line at a time.
+    fn doc_start(input: &str, mut start: usize) -> usize {
+        loop {
+            let before = input[..start].trim_end();
+            let line_start = before.rfind('\n').map(|n| n + 1).unwrap_or(0);
+            let line = before[line_start..].trim_start();
+
+            if !line.starts_with("///") {
+                return start;
+            }
+
+            start = before.len() - line.len();
+        }
+    }
+}
+
+/// Quote the given string, using the escape sequences supported by the lexer.
+fn quote(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() + 2);
+    out.push('"');
+
+    for c in input.chars() {
+        match c {
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+struct Formatter<'a> {
+    input: &'a str,
+    /// Spans of comments which have not been written yet, in reverse order.
+    comments: Vec<Span>,
+    /// Formatted output.
+    out: String,
+    /// Current level of indentation.
+    indent: usize,
+    /// Offset in the input up until which everything has been formatted.
+    cursor: usize,
+    /// If nothing has been written to the current block.
+    block_start: bool,
+    /// If the next line should be separated by an empty line.
+    blank: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(input: &'a str) -> Result<Self, Error> {
+        // NB: can't fail, since the input has already been parsed.
+        let mut comments = lexer::comments(input)
+            .map_err(|_| Error)?
+            .into_iter()
+            .map(Span::from)
+            .collect::<Vec<_>>();
+
+        comments.reverse();
+
+        Ok(Self {
+            input,
+            comments,
+            out: String::new(),
+            indent: 0,
+            cursor: 0,
+            block_start: true,
+            blank: false,
+        })
+    }
+
+    /// Move the cursor to the given position, discarding any comments before it.
+    fn seek(&mut self, pos: usize) {
+        self.cursor = pos;
+
+        while let Some(c) = self.comments.last() {
+            if c.start >= pos {
+                break;
+            }
+
+            self.comments.pop();
+        }
+    }
+
+    /// Access the input of the given span.
+    fn source(&self, span: Span) -> &'a str {
+        &self.input[span.start..span.end]
+    }
+
+    /// Write a single indented line.
+    fn line(&mut self, line: &str) {
+        if !line.is_empty() {
+            for _ in 0..self.indent {
+                self.out.push_str(INDENT);
+            }
+        }
+
+        self.out.push_str(line);
+        self.out.push('\n');
+    }
+
+    /// Write an empty line if the input had one between the cursor and the next line to write,
+    /// or if one has been requested.
+    fn separate(&mut self) {
+        let rest = &self.input[self.cursor..];
+        let whitespace = &rest[..rest.len() - rest.trim_start().len()];
+
+        if (self.blank || whitespace.matches('\n').count() > 1) && !self.block_start {
+            self.out.push('\n');
+        }
+
+        self.blank = false;
+        self.block_start = false;
+    }
+
+    /// Write all comments which start before the given position.
+    fn comments(&mut self, pos: usize) {
+        while let Some(c) = self.comments.last().copied() {
+            if c.start >= pos {
+                break;
+            }
+
+            self.comments.pop();
+            self.separate();
+
+            let comment = self.source(c);
+            let mut lines = comment.lines();
+
+            if let Some(first) = lines.next() {
+                self.line(first.trim_end());
+            }
+
+            // NB: continued lines of block comments are written as-is.
+            for line in lines {
+                self.out.push_str(line.trim_end());
+                self.out.push('\n');
+            }
+
+            self.cursor = c.end;
+        }
+    }
+
+    /// Prepare to write something starting at the given position.
+    fn leading(&mut self, pos: usize) {
+        self.comments(pos);
+        self.separate();
+    }
+
+    /// Finish writing something ending at the given position, moving any comment on the same line
+    /// in the input to the end of the last written line.
+    fn trailing(&mut self, end: usize) {
+        self.cursor = end;
+
+        let c = match self.comments.last().copied() {
+            Some(c) => c,
+            None => return,
+        };
+
+        if c.start < end || self.input[end..c.start].contains('\n') {
+            return;
+        }
+
+        let comment = self.source(c);
+
+        if comment.contains('\n') {
+            return;
+        }
+
+        self.comments.pop();
+        self.out.pop();
+        self.out.push(' ');
+        self.out.push_str(comment.trim_end());
+        self.out.push('\n');
+        self.cursor = c.end;
+    }
+
+    /// Open a block, where the opening brace is located after `pos`.
+    fn open(&mut self, line: String, pos: usize) {
+        self.line(&line);
+        self.indent += 1;
+        self.block_start = true;
+        self.cursor = self.input[pos..]
+            .find('{')
+            .map(|n| pos + n + 1)
+            .unwrap_or(pos);
+    }
+
+    /// Close a block, where the closing brace ends at `end`.
+    fn close(&mut self, end: usize) {
+        self.comments(end.saturating_sub(1));
+        self.indent -= 1;
+        self.line("}");
+        self.block_start = false;
+        self.trailing(end);
+    }
+
+    /// Write the documentation and attributes of an item.
+    fn item<T>(&mut self, item: &Item<T>) {
+        self.leading(item_span(item).start);
+
+        for line in &item.comment {
+            self.line(&format!("///{}", line.trim_end()));
+        }
+
+        for a in &item.attributes {
+            let a = self.attribute(a);
+            self.line(&format!("#{}", a));
+        }
+    }
+
+    fn file(&mut self, file: &ast::File) {
+        for line in &file.comment {
+            self.line(&format!("//!{}", line.trim_end()));
+            self.block_start = false;
+        }
+
+        if !file.comment.is_empty() {
+            self.blank = true;
+        }
+
+        for a in &file.attributes {
+            self.leading(a.span().start);
+            let attribute = self.attribute(a);
+            self.line(&format!("#!{}", attribute));
+            self.trailing(a.span().end);
+        }
+
+        if !file.attributes.is_empty() {
+            self.blank = true;
+        }
+
+        for u in &file.uses {
+            self.leading(u.span().start);
+            let line = self.use_decl(u);
+            self.line(&line);
+            self.trailing(u.span().end);
+        }
+
+        if !file.uses.is_empty() {
+            self.blank = true;
+        }
+
+        for decl in &file.decls {
+            self.decl(decl);
+            self.blank = true;
+        }
+
+        self.comments(self.input.len());
+    }
+
+    fn use_decl(&self, u: &Spanned<UseDecl>) -> String {
+        let mut out = String::from("use ");
+
+        if let ast::Package::Package { ref parts } = *Spanned::borrow(&u.package) {
+            let parts = parts
+                .iter()
+                .map(|p| self.source(p.span()))
+                .collect::<Vec<_>>();
+            out.push_str(&parts.join("."));
+        }
+
+        if let Some(range) = u.range.as_ref() {
+            out.push(' ');
+            out.push_str(self.source(range.span()));
+        }
+
+        if let Some(alias) = u.alias.as_ref() {
+            out.push_str(" as ");
+            out.push_str(self.source(alias.span()));
+        }
+
+        out.push(';');
+        out
+    }
+
+    fn decl(&mut self, decl: &Decl) {
+        match *decl {
+            Decl::Type(ref item) => {
+                self.item(item);
+                let line = format!(
+                    "type {}{} {{",
+                    item.name,
+                    type_parameters(&item.type_parameters)
+                );
+                self.open(line, item.name.span().end);
+                self.members(&item.members);
+                self.close(item.item.span().end);
+            }
+            Decl::Tuple(ref item) => {
+                self.item(item);
+                let line = format!(
+                    "tuple {}{} {{",
+                    item.name,
+                    type_parameters(&item.type_parameters)
+                );
+                self.open(line, item.name.span().end);
+                self.members(&item.members);
+                self.close(item.item.span().end);
+            }
+            Decl::Interface(ref item) => {
+                self.item(item);
+                let line = format!(
+                    "interface {}{} {{",
+                    item.name,
+                    type_parameters(&item.type_parameters)
+                );
+                self.open(line, item.name.span().end);
+                self.members(&item.members);
+
+                for sub_type in &item.sub_types {
+                    self.item(sub_type);
+
+                    let mut line = sub_type.name.to_string();
+                    let mut pos = sub_type.name.span().end;
+
+                    if let Some(alias) = sub_type.alias.as_ref() {
+                        line.push_str(" as ");
+                        line.push_str(&self.value(alias));
+                        pos = alias.span().end;
+                    }
+
+                    let end = sub_type.item.span().end;
+
+                    if sub_type.members.is_empty() && self.input[..end].ends_with(';') {
+                        line.push(';');
+                        self.line(&line);
+                        self.trailing(end);
+                        continue;
+                    }
+
+                    line.push_str(" {");
+                    self.open(line, pos);
+                    self.members(&sub_type.members);
+                    self.close(end);
+                }
+
+                self.close(item.item.span().end);
+            }
+            Decl::Enum(ref item) => {
+                self.item(item);
+                let line = format!("enum {} as {} {{", item.name, self.ty(&item.ty));
+                self.open(line, item.ty.span().end);
+
+                for variant in &item.variants {
+                    self.item(variant);
+
+                    let mut line = variant.name.to_string();
+
+                    if let Some(argument) = variant.argument.as_ref() {
+                        line.push_str(" as ");
+                        line.push_str(&self.value(argument));
+                    }
+
+                    line.push(';');
+                    self.line(&line);
+                    self.trailing(variant.item.span().end);
+                }
+
+                for m in &item.members {
+                    match *m {
+                        EnumMember::Code(ref code) => self.code(code),
+                    }
+                }
+
+                self.close(item.item.span().end);
+            }
+            Decl::Service(ref item) => {
+                self.item(item);
+                let line = format!("service {} {{", item.name);
+                self.open(line, item.name.span().end);
+
+                for m in &item.members {
+                    match *m {
+                        ServiceMember::Endpoint(ref endpoint) => {
+                            self.item(endpoint);
+
+                            let arguments = endpoint
+                                .arguments
+                                .iter()
+                                .map(|a| {
+                                    let channel = self.channel(&a.channel);
+                                    format!("{}: {}", self.source(a.ident.span()), channel)
+                                })
+                                .collect::<Vec<_>>();
+
+                            let mut line = format!(
+                                "{}({})",
+                                self.source(endpoint.id.span()),
+                                arguments.join(", ")
+                            );
+
+                            if let Some(response) = endpoint.response.as_ref() {
+                                line.push_str(" -> ");
+                                line.push_str(&self.channel(response));
+                            }
+
+                            if let Some(alias) = endpoint.alias.as_ref() {
+                                line.push_str(" as ");
+                                line.push_str(&quote(alias));
+                            }
+
+                            line.push(';');
+                            self.line(&line);
+                            self.trailing(endpoint.item.span().end);
+                        }
+                        ServiceMember::InnerDecl(ref decl) => self.decl(decl),
+                    }
+                }
+
+                self.close(item.item.span().end);
+            }
+            Decl::Const(ref item) => {
+                self.item(item);
+                let line = format!(
+                    "const {}: {} = {};",
+                    item.name,
+                    self.ty(&item.ty),
+                    self.value(&item.value)
+                );
+                self.line(&line);
+                self.trailing(item.item.span().end);
+            }
+            Decl::Alias(ref item) => {
+                self.item(item);
+                let line = format!("type {} = {};", item.name, self.ty(&item.ty));
+                self.line(&line);
+                self.trailing(item.item.span().end);
+            }
+        }
+    }
+
+    fn members(&mut self, members: &[TypeMember]) {
+        for m in members {
+            match *m {
+                TypeMember::Field(ref field) => {
+                    self.item(field);
+
+                    let span = field.item.span();
+
+                    // NB: field names don't have a span, but they are always first in the item.
+                    let name = self.input[span.start..]
+                        .split(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                        .next()
+                        .unwrap_or_default();
+
+                    let modifier = if field.required { "" } else { "?" };
+                    let mut line = format!("{}{}: {}", name, modifier, self.ty(&field.ty));
+
+                    if let Some(field_as) = field.field_as.as_ref() {
+                        line.push_str(" as ");
+                        line.push_str(&quote(field_as));
+                    }
+
+                    if let Some(default) = field.default.as_ref() {
+                        line.push_str(" = ");
+                        line.push_str(&self.value(default));
+                    }
+
+                    line.push(';');
+                    self.line(&line);
+                    self.trailing(span.end);
+                }
+                TypeMember::Code(ref code) => self.code(code),
+                TypeMember::InnerDecl(ref decl) => self.decl(decl),
+            }
+        }
+    }
+
+    fn code(&mut self, code: &Spanned<Code>) {
+        self.leading(code.span().start);
+
+        for a in &code.attributes {
+            let a = self.attribute(a);
+            self.line(&format!("#{}", a));
+        }
+
+        let context = self.source(code.context.span());
+        self.line(&format!("{} {{{{", context));
+
+        for line in &code.content {
+            self.line(line.trim_end());
+        }
+
+        self.line("}}");
+        self.trailing(code.span().end);
+    }
+
+    /// Format an attribute, without the leading `#` or `#!`.
+    fn attribute(&self, attribute: &Spanned<Attribute>) -> String {
+        match *Spanned::borrow(attribute) {
+            Attribute::Word(ref word) => format!("[{}]", self.source(word.span())),
+            Attribute::List(ref name, ref items) => {
+                let items = items
+                    .iter()
+                    .map(|item| match *item {
+                        AttributeItem::Word(ref value) => self.value(value),
+                        AttributeItem::NameValue {
+                            ref name,
+                            ref value,
+                        } => format!("{} = {}", self.source(name.span()), self.value(value)),
+                    })
+                    .collect::<Vec<_>>();
+
+                format!("[{}({})]", self.source(name.span()), items.join(", "))
+            }
+        }
+    }
+
+    fn value(&self, value: &Spanned<Value>) -> String {
+        match *Spanned::borrow(value) {
+            Value::Array(ref values) => {
+                let values = values.iter().map(|v| self.value(v)).collect::<Vec<_>>();
+                format!("({})", values.join(", "))
+            }
+            Value::Name(ref name) => self.name(name),
+            _ => self.source(value.span()).to_string(),
+        }
+    }
+
+    fn channel(&self, channel: &Spanned<Channel>) -> String {
+        match *Spanned::borrow(channel) {
+            Channel::Unary { ref ty } => self.ty(ty),
+            Channel::Streaming { ref ty } => format!("stream {}", self.ty(ty)),
+        }
+    }
+
+    fn ty(&self, ty: &Spanned<Type>) -> String {
+        match *Spanned::borrow(ty) {
+            Type::Name {
+                ref name,
+                ref arguments,
+            } if !arguments.is_empty() => {
+                let arguments = arguments.iter().map(|a| self.ty(a)).collect::<Vec<_>>();
+                format!("{}<{}>", self.name(name), arguments.join(", "))
+            }
+            Type::Name { ref name, .. } => self.name(name),
+            Type::Array { ref inner } => format!("[{}]", self.ty(inner)),
+            Type::Map { ref key, ref value } => format!("{{{}: {}}}", self.ty(key), self.ty(value)),
+            _ => self.source(ty.span()).to_string(),
+        }
+    }
+
+    fn name(&self, name: &Spanned<Name>) -> String {
+        let (prefix, path) = match *Spanned::borrow(name) {
+            Name::Relative { ref path } => (Some(""), path),
+            Name::Absolute {
+                ref prefix,
+                ref path,
+            } => (prefix.as_ref().map(|p| self.source(p.span())), path),
+        };
+
+        let mut parts = prefix.into_iter().collect::<Vec<_>>();
+        parts.extend(path.iter().map(|p| self.source(p.span())));
+        parts.join("::")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format, format_range};
+    use reproto_core::{Diagnostics, Source, Span};
+
+    fn fmt(input: &str) -> String {
+        let mut diag = Diagnostics::new(Source::empty("test"));
+        let out = format(&mut diag, input).expect("bad input");
+        let again = format(&mut diag, &out).expect("bad output");
+        assert_eq!(out, again, "formatting should be idempotent");
+        out
+    }
+
+    #[test]
+    fn test_format() {
+        let input = concat!(
+            "//! Package.\n",
+            "#![field_naming(upper_camel)]\n",
+            "use foo.bar \"^1\"  as  bar;\n",
+            "use foo.baz;\n",
+            "/// A type.\n",
+            "#[validate( min=1,max =2 )]\n",
+            "type   Foo{\n",
+            "    // leading\n",
+            "    a:string;// trailing\n",
+            "\n",
+            "\n",
+            "    b ?: [ bar::Bar ] as \"bee\";\n",
+            "    c: u32 as \"see\"=  10;\n",
+            "    /* before close */\n",
+            "}\n",
+            "enum E as string { A as \"a\"; B; }\n",
+            "const  Size :u32=10;\n",
+            "type  Id= string ;\n",
+            "service S {\n",
+            "  get(id: u32,body:stream ::Foo)->{string: Foo} as \"get_it\";\n",
+            "}\n",
+            "// end\n",
+        );
+
+        let expected = concat!(
+            "//! Package.\n",
+            "\n",
+            "#![field_naming(upper_camel)]\n",
+            "\n",
+            "use foo.bar \"^1\" as bar;\n",
+            "use foo.baz;\n",
+            "\n",
+            "/// A type.\n",
+            "#[validate(min = 1, max = 2)]\n",
+            "type Foo {\n",
+            "  // leading\n",
+            "  a: string; // trailing\n",
+            "\n",
+            "  b?: [bar::Bar] as \"bee\";\n",
+            "  c: u32 as \"see\" = 10;\n",
+            "  /* before close */\n",
+            "}\n",
+            "\n",
+            "enum E as string {\n",
+            "  A as \"a\";\n",
+            "  B;\n",
+            "}\n",
+            "\n",
+            "const Size: u32 = 10;\n",
+            "\n",
+            "type Id = string;\n",
+            "\n",
+            "service S {\n",
+            "  get(id: u32, body: stream ::Foo) -> {string: Foo} as \"get_it\";\n",
+            "}\n",
+            "\n",
+            "// end\n",
+        );
+
+        assert_eq!(expected, fmt(input));
+    }
+
+    #[test]
+    fn test_format_nested() {
+        let input = concat!(
+            "interface I {\n",
+            "  _type: string;\n",
+            "  java {{\n",
+            "    void foo();\n",
+            "  }}\n",
+            "  A as \"a\" {\n",
+            "    type Inner {}\n",
+            "  }\n",
+            "  B;\n",
+            "}\n",
+        );
+
+        let expected = concat!(
+            "interface I {\n",
+            "  _type: string;\n",
+            "  java {{\n",
+            "  void foo();\n",
+            "  }}\n",
+            "  A as \"a\" {\n",
+            "    type Inner {\n",
+            "    }\n",
+            "  }\n",
+            "  B;\n",
+            "}\n",
+        );
+
+        assert_eq!(expected, fmt(input));
+    }
+
+    #[test]
+    fn test_format_generic() {
+        let input = concat!(
+            "type  Page<T,E> {\n",
+            "  items: [ T ];\n",
+            "  other: Page< {string: E},u32 >;\n",
+            "}\n",
+        );
+
+        let expected = concat!(
+            "type Page<T, E> {\n",
+            "  items: [T];\n",
+            "  other: Page<{string: E}, u32>;\n",
+            "}\n",
+        );
+
+        assert_eq!(expected, fmt(input));
+    }
+
+    #[test]
+    fn test_format_range() {
+        let input = "type A {\n}\n\n/// B\ntype  B { a : u32; }\n";
+        let mut diag = Diagnostics::new(Source::empty("test"));
+
+        let result = format_range(&mut diag, input, Span { start: 26, end: 26 }).unwrap();
+        let expected = (
+            Span { start: 12, end: 38 },
+            "/// B\ntype B {\n  a: u32;\n}".to_string(),
+        );
+
+        assert_eq!(Some(expected), result);
+    }
+}
@@ -0,0 +1,90 @@
+//! Collects items of interest from a parsed file.
+
+use reproto_core::Spanned;
+
+/// Collects all attributes and endpoints in a file.
+#[derive(Default)]
+pub struct Collector<'a, 'input> {
+    pub attributes: Vec<&'a Spanned<ast::Attribute<'input>>>,
+    pub endpoints: Vec<&'a ast::Item<'input, ast::Endpoint<'input>>>,
+}
+
+impl<'a, 'input> Collector<'a, 'input> {
+    /// Collect everything in the given file.
+    pub fn file(&mut self, file: &'a ast::File<'input>) {
+        self.attributes.extend(&file.attributes);
+
+        for decl in &file.decls {
+            self.decl(decl);
+        }
+    }
+
+    fn decl(&mut self, decl: &'a ast::Decl<'input>) {
+        use ast::Decl::*;
+
+        match *decl {
+            Type(ref body) => {
+                self.attributes.extend(&body.attributes);
+                self.members(&body.members);
+            }
+            Tuple(ref body) => {
+                self.attributes.extend(&body.attributes);
+                self.members(&body.members);
+            }
+            Interface(ref body) => {
+                self.attributes.extend(&body.attributes);
+                self.members(&body.members);
+
+                for sub_type in &body.sub_types {
+                    self.attributes.extend(&sub_type.attributes);
+                    self.members(&sub_type.members);
+                }
+            }
+            Enum(ref body) => {
+                self.attributes.extend(&body.attributes);
+
+                for variant in &body.variants {
+                    self.attributes.extend(&variant.attributes);
+                }
+
+                for member in &body.members {
+                    let ast::EnumMember::Code(ref code) = *member;
+                    self.attributes.extend(&code.attributes);
+                }
+            }
+            Service(ref body) => {
+                self.attributes.extend(&body.attributes);
+
+                for member in &body.members {
+                    match *member {
+                        ast::ServiceMember::Endpoint(ref endpoint) => {
+                            self.attributes.extend(&endpoint.attributes);
+                            self.endpoints.push(endpoint);
+                        }
+                        ast::ServiceMember::InnerDecl(ref decl) => self.decl(decl),
+                    }
+                }
+            }
+            Const(ref body) => {
+                self.attributes.extend(&body.attributes);
+            }
+            Alias(ref body) => {
+                self.attributes.extend(&body.attributes);
+            }
+        }
+    }
+
+    fn members(&mut self, members: &'a [ast::TypeMember<'input>]) {
+        for member in members {
+            match *member {
+                ast::TypeMember::Field(ref field) => {
+                    self.attributes.extend(&field.attributes);
+                }
+                ast::TypeMember::Code(ref code) => {
+                    self.attributes.extend(&code.attributes);
+                }
+                ast::TypeMember::InnerDecl(ref decl) => self.decl(decl),
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
+mod collector;
 mod envelope;
 mod loaded_file;
 mod models;
+mod quick_fix;
+mod semantic_tokens;
 mod triggers;
 mod workspace;
 
@@ -15,12 +18,16 @@ use url::Url;
 
 use envelope::RequestId;
 use reproto_core::errors::Result;
-use reproto_core::{Diagnostic, Encoding, Filesystem, RealFilesystem, Reported, Rope, Source};
+use reproto_core::{
+    Diagnostic, Diagnostics, Encoding, Filesystem, Position, RealFilesystem, Reported, Rope,
+    Source, Span,
+};
 use serde::Deserialize;
 
 use crate::loaded_file::LoadedFile;
-use crate::models::{Completion, Jump, Range, RenameResult};
-use crate::workspace::Workspace;
+use crate::models::{Completion, Hover, Jump, Range, RenameResult, Semantic};
+use crate::quick_fix::quick_fixes;
+use crate::workspace::{hover_markdown, Workspace};
 use crate::ContentType::*;
 
 #[derive(Debug)]
@@ -543,6 +550,30 @@ where
                 let params = ty::TextDocumentPositionParams::deserialize(request.params)?;
                 self.text_document_definition(request.id, params)?;
             }
+            "textDocument/hover" => {
+                let params = ty::HoverParams::deserialize(request.params)?;
+                self.text_document_hover(request.id, params)?;
+            }
+            "textDocument/formatting" => {
+                let params = ty::DocumentFormattingParams::deserialize(request.params)?;
+                self.text_document_formatting(request.id, params)?;
+            }
+            "textDocument/rangeFormatting" => {
+                let params = ty::DocumentRangeFormattingParams::deserialize(request.params)?;
+                self.text_document_range_formatting(request.id, params)?;
+            }
+            "textDocument/codeAction" => {
+                let params = ty::CodeActionParams::deserialize(request.params)?;
+                self.text_document_code_action(request.id, params)?;
+            }
+            "textDocument/semanticTokens/full" => {
+                let params = ty::SemanticTokensParams::deserialize(request.params)?;
+                self.text_document_semantic_tokens_full(request.id, params)?;
+            }
+            "textDocument/semanticTokens/range" => {
+                let params = ty::SemanticTokensRangeParams::deserialize(request.params)?;
+                self.text_document_semantic_tokens_range(request.id, params)?;
+            }
             "textDocument/rename" => {
                 let params = ty::RenameParams::deserialize(request.params)?;
                 self.text_document_rename(request.id, params)?;
@@ -559,6 +590,10 @@ where
                 let params = ty::WorkspaceSymbolParams::deserialize(request.params)?;
                 self.workspace_symbol(request.id, params)?;
             }
+            "workspace/executeCommand" => {
+                let params = ty::ExecuteCommandParams::deserialize(request.params)?;
+                self.workspace_execute_command(request.id, params)?;
+            }
             "workspace/didChangeConfiguration" => {
                 let params = ty::DidChangeConfigurationParams::deserialize(request.params)?;
                 self.workspace_did_change_configuration(request.id, params)?;
@@ -713,6 +748,30 @@ where
                     ..ty::CompletionOptions::default()
                 }),
                 definition_provider: Some(ty::OneOf::Left(true)),
+                hover_provider: Some(ty::HoverProviderCapability::Simple(true)),
+                document_formatting_provider: Some(ty::OneOf::Left(true)),
+                document_range_formatting_provider: Some(ty::OneOf::Left(true)),
+                code_action_provider: Some(ty::CodeActionProviderCapability::Options(
+                    ty::CodeActionOptions {
+                        code_action_kinds: Some(vec![ty::CodeActionKind::QUICKFIX]),
+                        work_done_progress_options: ty::WorkDoneProgressOptions::default(),
+                        resolve_provider: None,
+                    },
+                )),
+                execute_command_provider: Some(ty::ExecuteCommandOptions {
+                    commands: vec![OPEN_PROJECT_MANIFEST.to_string()],
+                    ..ty::ExecuteCommandOptions::default()
+                }),
+                semantic_tokens_provider: Some(
+                    ty::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        ty::SemanticTokensOptions {
+                            legend: semantic_tokens::legend(),
+                            range: Some(true),
+                            full: Some(ty::SemanticTokensFullOptions::Bool(true)),
+                            ..ty::SemanticTokensOptions::default()
+                        },
+                    ),
+                ),
                 rename_provider: Some(ty::OneOf::Left(true)),
                 document_symbol_provider: Some(ty::OneOf::Left(true)),
                 workspace_symbol_provider: Some(ty::OneOf::Left(true)),
@@ -842,6 +901,288 @@ where
         Ok(())
     }
 
+    /// Handler for `textDocument/formatting`.
+    fn text_document_formatting(
+        &mut self,
+        request_id: Option<RequestId>,
+        params: ty::DocumentFormattingParams,
+    ) -> Result<()> {
+        let mut response = None::<Vec<ty::TextEdit>>;
+        self.formatting(&params.text_document.uri, None, &mut response)?;
+        self.channel.send(request_id, response)?;
+        Ok(())
+    }
+
+    /// Handler for `textDocument/rangeFormatting`.
+    fn text_document_range_formatting(
+        &mut self,
+        request_id: Option<RequestId>,
+        params: ty::DocumentRangeFormattingParams,
+    ) -> Result<()> {
+        let mut response = None::<Vec<ty::TextEdit>>;
+        self.formatting(&params.text_document.uri, Some(params.range), &mut response)?;
+        self.channel.send(request_id, response)?;
+        Ok(())
+    }
+
+    /// Format the given document, or only the declarations overlapping with the given range.
+    ///
+    /// Documents with syntax errors are left as-is, since those are already reported as
+    /// diagnostics.
+    fn formatting(
+        &mut self,
+        url: &Url,
+        range: Option<ty::Range>,
+        response: &mut Option<Vec<ty::TextEdit>>,
+    ) -> Result<()> {
+        let workspace = match &self.workspace {
+            Some(workspace) => workspace,
+            None => return Ok(()),
+        };
+
+        let source = match workspace.source(url) {
+            Some(source) => source,
+            None => return Ok(()),
+        };
+
+        let content = parser::read_to_string(source.read()?)?;
+        let mut diag = Diagnostics::new(source.clone());
+
+        let result = match range {
+            Some(range) => {
+                let span = source.range_to_span(convert_lsp_range(range), Encoding::Utf16)?;
+                reproto_fmt::format_range(&mut diag, &content, span)
+            }
+            None => reproto_fmt::format(&mut diag, &content).map(|formatted| {
+                let span = Span {
+                    start: 0,
+                    end: content.len(),
+                };
+
+                Some((span, formatted))
+            }),
+        };
+
+        let (span, formatted) = match result {
+            Ok(Some(result)) => result,
+            Ok(None) | Err(reproto_fmt::Error) => return Ok(()),
+        };
+
+        if content[span.start..span.end] == formatted {
+            *response = Some(Vec::new());
+            return Ok(());
+        }
+
+        let range = convert_range(source.span_to_range(span, Encoding::Utf16)?);
+
+        *response = Some(vec![ty::TextEdit {
+            range,
+            new_text: formatted,
+        }]);
+
+        Ok(())
+    }
+
+    /// Handler for `textDocument/codeAction`.
+    fn text_document_code_action(
+        &mut self,
+        request_id: Option<RequestId>,
+        params: ty::CodeActionParams,
+    ) -> Result<()> {
+        let mut response = None::<ty::CodeActionResponse>;
+        self.code_action(params, &mut response)?;
+        self.channel.send(request_id, response)?;
+        Ok(())
+    }
+
+    /// Build quick fixes for the diagnostics in the code action request.
+    fn code_action(
+        &mut self,
+        params: ty::CodeActionParams,
+        response: &mut Option<ty::CodeActionResponse>,
+    ) -> Result<()> {
+        let workspace = match &self.workspace {
+            Some(workspace) => workspace,
+            None => return Ok(()),
+        };
+
+        let url = params.text_document.uri;
+
+        let source = match workspace.source(&url) {
+            Some(source) => source,
+            None => return Ok(()),
+        };
+
+        let content = parser::read_to_string(source.read()?)?;
+        let mut diag = Diagnostics::new(source.clone());
+
+        // files with syntax errors are not fixed, since the edits could end up anywhere.
+        let file = match parser::parse(&mut diag, &content) {
+            Ok(file) => file,
+            Err(()) => return Ok(()),
+        };
+
+        let current = workspace.file(&url).map(|f| &f.package.package);
+
+        let packages = workspace
+            .packages
+            .keys()
+            .map(|p| &p.package)
+            .chain(workspace.available_packages.iter())
+            .filter(|p| Some(*p) != current)
+            .map(|p| p.to_string())
+            .collect::<BTreeSet<_>>();
+
+        let mut actions = Vec::new();
+
+        for d in params.context.diagnostics {
+            if d.message.starts_with("imported package ") {
+                let command = ty::Command {
+                    title: "Open project manifest".to_string(),
+                    command: OPEN_PROJECT_MANIFEST.to_string(),
+                    arguments: None,
+                };
+
+                actions.push(ty::CodeActionOrCommand::CodeAction(ty::CodeAction {
+                    title: command.title.clone(),
+                    kind: Some(ty::CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![d.clone()]),
+                    command: Some(command),
+                    ..ty::CodeAction::default()
+                }));
+
+                continue;
+            }
+
+            let span = source.range_to_span(convert_lsp_range(d.range), Encoding::Utf16)?;
+
+            let fixes = quick_fixes(
+                &content,
+                &file,
+                span,
+                &d.message,
+                packages.iter().map(String::as_str),
+            );
+
+            for fix in fixes {
+                let edits = fix
+                    .edits
+                    .into_iter()
+                    .map(|(span, new_text)| {
+                        let range = convert_range(source.span_to_range(span, Encoding::Utf16)?);
+                        Ok(ty::OneOf::Left(ty::TextEdit { range, new_text }))
+                    })
+                    .collect::<Result<_>>()?;
+
+                let changes = vec![ty::TextDocumentEdit {
+                    text_document: ty::OptionalVersionedTextDocumentIdentifier {
+                        uri: url.clone(),
+                        version: None,
+                    },
+                    edits,
+                }];
+
+                actions.push(ty::CodeActionOrCommand::CodeAction(ty::CodeAction {
+                    title: fix.title,
+                    kind: Some(ty::CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![d.clone()]),
+                    edit: Some(ty::WorkspaceEdit {
+                        document_changes: Some(ty::DocumentChanges::Edits(changes)),
+                        ..ty::WorkspaceEdit::default()
+                    }),
+                    ..ty::CodeAction::default()
+                }));
+            }
+        }
+
+        *response = Some(actions);
+        Ok(())
+    }
+
+    /// Handler for `textDocument/semanticTokens/full`.
+    fn text_document_semantic_tokens_full(
+        &mut self,
+        request_id: Option<RequestId>,
+        params: ty::SemanticTokensParams,
+    ) -> Result<()> {
+        let mut response = None::<ty::SemanticTokens>;
+        self.semantic_tokens(&params.text_document.uri, None, &mut response)?;
+        let response = response.map(ty::SemanticTokensResult::Tokens);
+        self.channel.send(request_id, response)?;
+        Ok(())
+    }
+
+    /// Handler for `textDocument/semanticTokens/range`.
+    fn text_document_semantic_tokens_range(
+        &mut self,
+        request_id: Option<RequestId>,
+        params: ty::SemanticTokensRangeParams,
+    ) -> Result<()> {
+        let mut response = None::<ty::SemanticTokens>;
+        self.semantic_tokens(&params.text_document.uri, Some(params.range), &mut response)?;
+        let response = response.map(ty::SemanticTokensRangeResult::Tokens);
+        self.channel.send(request_id, response)?;
+        Ok(())
+    }
+
+    /// Build semantic tokens for the given document, optionally limited to the given range.
+    fn semantic_tokens(
+        &mut self,
+        url: &Url,
+        range: Option<ty::Range>,
+        response: &mut Option<ty::SemanticTokens>,
+    ) -> Result<()> {
+        let workspace = match &self.workspace {
+            Some(workspace) => workspace,
+            None => return Ok(()),
+        };
+
+        let file = match workspace.file(url) {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+
+        // NB: use the content that the file was loaded from, which the semantic information
+        // refers to.
+        let content = parser::read_to_string(file.diag.source.read()?)?;
+
+        let span = match range {
+            Some(range) => Some(
+                file.diag
+                    .source
+                    .range_to_span(convert_lsp_range(range), Encoding::Utf16)?,
+            ),
+            None => None,
+        };
+
+        let data = semantic_tokens::semantic_tokens(workspace, file, &content, span);
+
+        *response = Some(ty::SemanticTokens {
+            result_id: None,
+            data,
+        });
+
+        Ok(())
+    }
+
+    /// Handler for `workspace/executeCommand`.
+    fn workspace_execute_command(
+        &mut self,
+        request_id: Option<RequestId>,
+        params: ty::ExecuteCommandParams,
+    ) -> Result<()> {
+        if let Some(workspace) = &self.workspace {
+            if params.command == OPEN_PROJECT_MANIFEST {
+                let manifest_url = workspace.manifest_url()?;
+
+                self.channel.notification::<OpenUrl>(manifest_url)?;
+            }
+        }
+
+        self.channel.send(request_id, None::<json::Value>)?;
+        Ok(())
+    }
+
     /// Handler for `textDocument/references`.
     fn text_document_references(
         &mut self,
@@ -914,18 +1255,20 @@ where
             for file in workspace.files() {
                 let by_url = by_url.remove(&file.url);
                 let by_url_chain = by_url.into_iter().flat_map(|d| d.into_iter()).map(|d| d.1);
+                let deprecations = deprecation_diagnostics(workspace, file)?;
 
                 self.send_diagnostics(
                     &file.url,
                     &file.diag.source,
                     file.diag.items().chain(by_url_chain),
+                    deprecations,
                 )?;
             }
 
             // diagnostics about other random files
             for (url, diag) in by_url {
                 for (source, d) in diag {
-                    self.send_diagnostics(&url, source, ::std::iter::once(d))?;
+                    self.send_diagnostics(&url, source, ::std::iter::once(d), Vec::new())?;
                 }
             }
         }
@@ -960,12 +1303,18 @@ where
     }
 
     /// Send diagnostics for a single URL.
-    fn send_diagnostics<'a, I>(&self, url: &Url, source: &Source, diagnostics: I) -> Result<()>
+    ///
+    /// `out` contains diagnostics which have already been converted, like deprecations.
+    fn send_diagnostics<'a, I>(
+        &self,
+        url: &Url,
+        source: &Source,
+        diagnostics: I,
+        mut out: Vec<ty::Diagnostic>,
+    ) -> Result<()>
     where
         I: IntoIterator<Item = &'a Diagnostic>,
     {
-        let mut out = Vec::new();
-
         for d in diagnostics.into_iter() {
             match *d {
                 reproto_core::Diagnostic::Error {
@@ -1390,6 +1739,18 @@ where
         Ok(())
     }
 
+    /// Handler for `textDocument/hover`.
+    fn text_document_hover(
+        &mut self,
+        request_id: Option<RequestId>,
+        params: ty::HoverParams,
+    ) -> Result<()> {
+        let mut response = None::<ty::Hover>;
+        self.hover(params.text_document_position_params, &mut response)?;
+        self.channel.send(request_id, response)?;
+        Ok(())
+    }
+
     /// Handler for renaming
     fn text_document_rename(
         &mut self,
@@ -1491,6 +1852,75 @@ where
         }
     }
 
+    /// Populate the hover response.
+    fn hover(
+        &mut self,
+        params: ty::TextDocumentPositionParams,
+        response: &mut Option<ty::Hover>,
+    ) -> Result<()> {
+        let url = params.text_document.uri;
+
+        let workspace = match &self.workspace {
+            Some(workspace) => workspace,
+            None => return Ok(()),
+        };
+
+        // type references are resolved in the same way as when jumping to their definition.
+        let value = if let Some((file, jump)) = workspace.find_jump(&url, params.position) {
+            match *jump {
+                Jump::Absolute {
+                    ref package,
+                    ref path,
+                } => {
+                    let file = if let Some(ref package) = *package {
+                        match workspace
+                            .packages
+                            .get(package)
+                            .and_then(|u| workspace.file(u))
+                        {
+                            Some(file) => file,
+                            None => return Ok(()),
+                        }
+                    } else {
+                        file
+                    };
+
+                    declaration_hover(file, path)
+                }
+                _ => None,
+            }
+        } else if let Some((file, hover)) = workspace.find_hover(&url, params.position) {
+            match *hover {
+                Hover::Declaration { ref path } => declaration_hover(file, path),
+                Hover::Markdown { ref value } => Some(value.clone()),
+            }
+        } else {
+            None
+        };
+
+        let value = match value {
+            Some(value) => value,
+            None => return Ok(()),
+        };
+
+        *response = Some(ty::Hover {
+            contents: ty::HoverContents::Markup(ty::MarkupContent {
+                kind: ty::MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        });
+
+        return Ok(());
+
+        /// Render the hover for the declaration at the given path.
+        fn declaration_hover(file: &LoadedFile, path: &[String]) -> Option<String> {
+            let declaration = file.declarations.get(path)?;
+            let signature = format!("{} {}::{}", declaration.kind, file.package, path.join("::"));
+            Some(hover_markdown(&signature, &[], declaration.comment.clone()))
+        }
+    }
+
     /// Populate the goto definition response.
     fn definition(
         &mut self,
@@ -1572,6 +2002,63 @@ where
     }
 }
 
+/// Build hints for all references to deprecated declarations in the given file.
+fn deprecation_diagnostics(
+    workspace: &Workspace,
+    file: &LoadedFile,
+) -> Result<Vec<ty::Diagnostic>> {
+    let mut out = Vec::new();
+
+    for (span, semantic) in &file.semantic {
+        let (package, path) = match *semantic {
+            Semantic::Reference {
+                package: Some(ref package),
+                ref path,
+            } => (package, path),
+            _ => continue,
+        };
+
+        let deprecated = match workspace.declaration_deprecated(package, path) {
+            Some(deprecated) => deprecated,
+            None => continue,
+        };
+
+        let name = path.join("::");
+
+        let message = match deprecated.message() {
+            Some(message) => format!("`{}` is deprecated: {}", name, message),
+            None => format!("`{}` is deprecated", name),
+        };
+
+        let range = file.diag.source.span_to_range(*span, Encoding::Utf16)?;
+
+        out.push(ty::Diagnostic {
+            range: convert_range(range),
+            message,
+            severity: Some(ty::DiagnosticSeverity::HINT),
+            tags: Some(vec![ty::DiagnosticTag::DEPRECATED]),
+            ..ty::Diagnostic::default()
+        });
+    }
+
+    Ok(out)
+}
+
+/// Convert a language-server range into an internal range.
+fn convert_lsp_range(range: ty::Range) -> (Position, Position) {
+    let start = Position {
+        line: range.start.line as usize,
+        col: range.start.character as usize,
+    };
+
+    let end = Position {
+        line: range.end.line as usize,
+        col: range.end.character as usize,
+    };
+
+    (start, end)
+}
+
 /// Convert an internal range into a language-server range.
 fn convert_range<R: Into<Range>>(range: R) -> ty::Range {
     let range = range.into();
@@ -1600,6 +2087,9 @@ pub enum Expected {
     ProjectAddMissing,
 }
 
+/// Command which opens the project manifest in the client.
+const OPEN_PROJECT_MANIFEST: &str = "reproto.openProjectManifest";
+
 /// $/openUrl custom notification.
 pub enum OpenUrl {}
 
@@ -1,6 +1,8 @@
 //! A file that is loaded into a workspace.
 
-use crate::models::{Completion, Jump, Prefix, Range, Reference, Rename, Symbol};
+use crate::models::{
+    Completion, Declaration, Hover, Jump, Prefix, Range, Reference, Rename, Semantic, Symbol,
+};
 use crate::triggers::Triggers;
 use reproto_core::errors::Result;
 use reproto_core::{Diagnostics, Encoding, Position, RpVersionedPackage, Source, Span};
@@ -21,6 +23,10 @@ pub struct LoadedFile {
     pub rename_triggers: Triggers<Rename>,
     /// Local reference triggers.
     pub reference_triggers: Triggers<Reference>,
+    /// Hover locations.
+    pub hover_triggers: Triggers<Hover>,
+    /// Semantic classification of locations, used for semantic highlighting.
+    pub semantic: Vec<(Span, Semantic)>,
     /// All the locations that a given prefix is present at.
     pub prefix_ranges: HashMap<String, Vec<Range>>,
     /// Implicit prefixes which _cannot_ be renamed.
@@ -33,6 +39,8 @@ pub struct LoadedFile {
     pub symbols: HashMap<Vec<String>, Vec<Symbol>>,
     /// Exact symbol lookup.
     pub symbol: HashMap<Vec<String>, Span>,
+    /// Declarations present in the file, keyed by their path.
+    pub declarations: HashMap<Vec<String>, Declaration>,
     /// All references for a given type.
     pub references: HashMap<Reference, Vec<Range>>,
     /// Type ranges to be modified when changing the name of a given type.
@@ -52,6 +60,8 @@ impl LoadedFile {
             completion_triggers: Triggers::new(),
             rename_triggers: Triggers::new(),
             reference_triggers: Triggers::new(),
+            hover_triggers: Triggers::new(),
+            semantic: Vec::new(),
             prefix_ranges: HashMap::new(),
             implicit_prefixes: HashMap::new(),
             prefixes: HashMap::new(),
@@ -59,6 +69,7 @@ impl LoadedFile {
             references: HashMap::new(),
             type_ranges: HashMap::new(),
             symbol: HashMap::new(),
+            declarations: HashMap::new(),
             diag: Diagnostics::new(source),
             dirty: false,
         }
@@ -75,6 +86,18 @@ impl LoadedFile {
         self.jump_triggers.insert(range, jump);
     }
 
+    /// Insert the specified hover.
+    pub fn register_hover(&mut self, span: Span, hover: Hover) -> Result<()> {
+        let range = self.range(span)?;
+        self.hover_triggers.insert(range, hover);
+        Ok(())
+    }
+
+    /// Insert the semantic classification of a location.
+    pub fn register_semantic(&mut self, span: Span, semantic: Semantic) {
+        self.semantic.push((span, semantic));
+    }
+
     /// Set an implicit prefix.
     ///
     /// These prefixes _can not_ be renamed since they are the last part of the package.
@@ -1,6 +1,6 @@
 //! Data models that are shared for the language server.
 
-use reproto_core::{Position, RpVersionedPackage};
+use reproto_core::{Position, RpDeprecated, RpVersionedPackage};
 use std::collections::BTreeSet;
 use url::Url;
 
@@ -73,6 +73,41 @@ pub enum Jump {
     Prefix { prefix: String },
 }
 
+/// Specifies what to show when hovering over a location.
+#[derive(Debug, Clone)]
+pub enum Hover {
+    /// Hover over the name of a local declaration.
+    Declaration { path: Vec<String> },
+    /// Hover over a field, endpoint, or enum variant with pre-rendered markdown.
+    Markdown { value: String },
+}
+
+/// Semantic classification of a location in a file.
+#[derive(Debug, Clone)]
+pub enum Semantic {
+    /// The name of a declaration, of the given kind like `type` or `enum`.
+    Declaration { kind: &'static str },
+    /// A reference to a declaration, which is classified once the declaration is looked up.
+    Reference {
+        package: Option<RpVersionedPackage>,
+        path: Vec<String>,
+    },
+    /// An imported package, or a prefix referring to it.
+    Package,
+    /// The name of a sub-type.
+    SubType,
+    /// The name of a field.
+    Field,
+    /// The name of an enum variant.
+    Variant,
+    /// The name of an endpoint.
+    Endpoint,
+    /// The name of an endpoint argument.
+    Argument,
+    /// The name of an attribute.
+    Attribute,
+}
+
 /// Specifies a reference to some type.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Reference {
@@ -122,6 +157,17 @@ pub struct Prefix {
     pub read_only: bool,
 }
 
+/// Information about a single declaration.
+#[derive(Debug, Clone)]
+pub struct Declaration {
+    /// The kind of the declaration, like `type` or `enum`.
+    pub kind: &'static str,
+    /// Markdown documentation comment.
+    pub comment: Option<String>,
+    /// Deprecation specified through `#[deprecated]`, if any.
+    pub deprecated: Option<RpDeprecated>,
+}
+
 /// Information about a single symbol.
 #[derive(Debug, Clone)]
 pub struct Symbol {
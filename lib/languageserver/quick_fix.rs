@@ -0,0 +1,379 @@
+//! Quick fixes for diagnostics reported when compiling the workspace.
+//!
+//! Diagnostics are identified through their message, and fixes are computed against the parsed
+//! file that they were reported for.
+
+use crate::collector::Collector;
+use reproto_core::{Span, Spanned};
+
+/// A single quick fix, which consists of a number of edits to the same file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickFix {
+    /// Title of the fix, as presented to the user.
+    pub title: String,
+    /// Spans to replace, and what to replace them with.
+    pub edits: Vec<(Span, String)>,
+}
+
+impl QuickFix {
+    fn new(title: String, span: Span, new_text: String) -> Self {
+        Self {
+            title,
+            edits: vec![(span, new_text)],
+        }
+    }
+}
+
+/// Build quick fixes for a diagnostic with the given message, reported at the given span.
+///
+/// `packages` are the packages known to the workspace, which are candidates for missing imports.
+pub fn quick_fixes<'a, P>(
+    content: &str,
+    file: &ast::File,
+    span: Span,
+    message: &str,
+    packages: P,
+) -> Vec<QuickFix>
+where
+    P: IntoIterator<Item = &'a str>,
+{
+    let mut out = Vec::new();
+
+    if message == "unknown attribute" {
+        out.extend(remove_attribute(content, file, span));
+    } else if let Some(prefix) = quoted(message, "missing prefix `", "`") {
+        for package in packages {
+            if package.rsplit('.').next() == Some(prefix) {
+                out.push(add_use(content, file, package));
+            }
+        }
+
+        out.sort_by(|a, b| a.title.cmp(&b.title));
+    } else if let Some(var) = quoted(
+        message,
+        "path variable `",
+        "` is not an argument to endpoint",
+    ) {
+        out.extend(add_path_variable(content, file, span, var));
+    } else if let Some(name) = quoted(message, "`", "` does not exist") {
+        out.extend(create_declaration(content, span, name));
+    }
+
+    out
+}
+
+/// Extract the part of a message which is surrounded by the given prefix and suffix.
+fn quoted<'a>(message: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    message.strip_prefix(prefix)?.strip_suffix(suffix)
+}
+
+/// Remove the attribute, or the part of a selection, at the given span.
+fn remove_attribute(content: &str, file: &ast::File, span: Span) -> Option<QuickFix> {
+    let mut collector = Collector::default();
+    collector.file(file);
+
+    for attribute in collector.attributes {
+        let (name, items) = match Spanned::borrow(attribute) {
+            ast::Attribute::Word(name) => (name, None),
+            ast::Attribute::List(name, items) => (name, Some(items)),
+        };
+
+        if attribute.span() == span || name.span() == span {
+            let title = format!("Remove unknown attribute `{}`", name.as_ref());
+            let span = whole_line(content, attribute.span());
+            return Some(QuickFix::new(title, span, String::new()));
+        }
+
+        let items = match items {
+            Some(items) => items,
+            None => continue,
+        };
+
+        for (index, item) in items.iter().enumerate() {
+            let (key, item_span) = match *item {
+                ast::AttributeItem::Word(ref value) => (value.span(), value.span()),
+                ast::AttributeItem::NameValue {
+                    ref name,
+                    ref value,
+                } => {
+                    let item_span = Span {
+                        start: name.span().start,
+                        end: value.span().end,
+                    };
+
+                    (name.span(), item_span)
+                }
+            };
+
+            if key != span {
+                continue;
+            }
+
+            let title = format!(
+                "Remove unknown attribute `{}` from `{}`",
+                &content[key.start..key.end],
+                name.as_ref()
+            );
+
+            // last remaining item, so remove the whole attribute.
+            if items.len() == 1 {
+                let span = whole_line(content, attribute.span());
+                return Some(QuickFix::new(title, span, String::new()));
+            }
+
+            // remove up until the next item, or from the end of the previous one.
+            let span = match items.get(index + 1) {
+                Some(next) => Span {
+                    start: item_span.start,
+                    end: item_start(next),
+                },
+                None => Span {
+                    start: item_end(&items[index - 1]),
+                    end: item_span.end,
+                },
+            };
+
+            return Some(QuickFix::new(title, span, String::new()));
+        }
+    }
+
+    return None;
+
+    fn item_start(item: &ast::AttributeItem) -> usize {
+        match *item {
+            ast::AttributeItem::Word(ref value) => value.span().start,
+            ast::AttributeItem::NameValue { ref name, .. } => name.span().start,
+        }
+    }
+
+    fn item_end(item: &ast::AttributeItem) -> usize {
+        match *item {
+            ast::AttributeItem::Word(ref value) => value.span().end,
+            ast::AttributeItem::NameValue { ref value, .. } => value.span().end,
+        }
+    }
+}
+
+/// Add a use declaration for the given package.
+fn add_use(content: &str, file: &ast::File, package: &str) -> QuickFix {
+    let title = format!("Add `use {};`", package);
+
+    // add after the last use declaration.
+    if let Some(last) = file.uses.last() {
+        let end = last.span().end;
+        let span = Span { start: end, end };
+        return QuickFix::new(title, span, format!("\nuse {};", package));
+    }
+
+    // add after the file attributes.
+    if let Some(last) = file.attributes.last() {
+        let end = last.span().end;
+        let span = Span { start: end, end };
+        return QuickFix::new(title, span, format!("\n\nuse {};", package));
+    }
+
+    // add after the file comment.
+    let mut start = 0;
+
+    for line in content.split_inclusive('\n') {
+        if !line.trim_start().starts_with("//!") {
+            break;
+        }
+
+        start += line.len();
+    }
+
+    let mut new_text = String::new();
+
+    // separate the use declaration from the file comment and what follows with empty lines.
+    if start > 0 {
+        new_text.push_str(if content[..start].ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        });
+    }
+
+    new_text.push_str(&format!("use {};\n", package));
+
+    let rest = &content[start..];
+
+    if !rest.is_empty() && !rest.starts_with('\n') {
+        new_text.push('\n');
+    }
+
+    QuickFix::new(title, Span { start, end: start }, new_text)
+}
+
+/// Add a missing path variable as an argument to the endpoint that declares it.
+fn add_path_variable(content: &str, file: &ast::File, span: Span, var: &str) -> Option<QuickFix> {
+    let mut collector = Collector::default();
+    collector.file(file);
+
+    let endpoint = collector.endpoints.into_iter().find(|e| {
+        e.attributes
+            .iter()
+            .any(|a| a.span().start <= span.start && span.end <= a.span().end)
+    })?;
+
+    let title = format!(
+        "Add argument `{}` to endpoint `{}`",
+        var,
+        endpoint.id.as_ref()
+    );
+
+    let (start, new_text) = match endpoint.arguments.last() {
+        Some(last) => (last.channel.span().end, format!(", {}: string", var)),
+        None => {
+            let end = endpoint.id.span().end;
+            let open = content[end..].find('(')?;
+            (end + open + 1, format!("{}: string", var))
+        }
+    };
+
+    let span = Span { start, end: start };
+    Some(QuickFix::new(title, span, new_text))
+}
+
+/// Create a stub for a missing declaration at the end of the file.
+///
+/// This only applies to local names which are not nested.
+fn create_declaration(content: &str, span: Span, name: &str) -> Option<QuickFix> {
+    if content.get(span.start..span.end) != Some(name) {
+        return None;
+    }
+
+    let mut chars = name.chars();
+
+    if !chars.next()?.is_ascii_uppercase() || !chars.all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let title = format!("Create type `{}`", name);
+
+    let separator = if content.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    let new_text = format!("{}type {} {{\n}}\n", separator, name);
+
+    let end = content.len();
+    Some(QuickFix::new(title, Span { start: end, end }, new_text))
+}
+
+/// Extend the span to cover the entire line if there is nothing else on it.
+fn whole_line(content: &str, span: Span) -> Span {
+    let start = content[..span.start]
+        .rfind('\n')
+        .map(|n| n + 1)
+        .unwrap_or(0);
+    let end = content[span.end..]
+        .find('\n')
+        .map(|n| span.end + n + 1)
+        .unwrap_or(content.len());
+
+    let before = &content[start..span.start];
+    let after = &content[span.end..end];
+
+    if before.trim().is_empty() && after.trim().is_empty() {
+        return Span { start, end };
+    }
+
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{quick_fixes, QuickFix};
+    use reproto_core::{Diagnostics, Source, Span};
+
+    /// Apply the first quick fix for the diagnostic at the first occurrence of `at`.
+    fn apply(content: &str, at: &str, message: &str, packages: &[&str]) -> Option<String> {
+        let mut diag = Diagnostics::new(Source::empty("test"));
+        let file = parser::parse(&mut diag, content).expect("bad input");
+
+        let start = content.find(at).expect("missing position");
+        let span = Span {
+            start,
+            end: start + at.len(),
+        };
+
+        let fixes = quick_fixes(content, &file, span, message, packages.iter().cloned());
+
+        let QuickFix { mut edits, .. } = fixes.into_iter().next()?;
+        edits.sort_by_key(|e| e.0.start);
+
+        let mut out = content.to_string();
+
+        for (span, new_text) in edits.into_iter().rev() {
+            out.replace_range(span.start..span.end, &new_text);
+        }
+
+        Some(out)
+    }
+
+    #[test]
+    fn test_remove_attribute() {
+        let content = "type Foo {\n  #[unknown]\n  a: string;\n}\n";
+        let out = apply(content, "unknown", "unknown attribute", &[]);
+        assert_eq!(Some("type Foo {\n  a: string;\n}\n".to_string()), out);
+
+        let content = "#[http(path = \"/\", other = 42)]\n";
+        let content = format!("service Foo {{\n  {}  get();\n}}\n", content);
+        let out = apply(&content, "other", "unknown attribute", &[]);
+        let expected = "service Foo {\n  #[http(path = \"/\")]\n  get();\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+    }
+
+    #[test]
+    fn test_add_use() {
+        let content = "use foo.bar;\n\ntype Foo {\n  a: c::Baz;\n}\n";
+        let out = apply(content, "c", "missing prefix `c`", &["foo.bar", "a.b.c"]);
+        let expected = "use foo.bar;\nuse a.b.c;\n\ntype Foo {\n  a: c::Baz;\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+
+        let content = "//! Docs.\ntype Foo {\n  a: c::Baz;\n}\n";
+        let out = apply(content, "c", "missing prefix `c`", &["a.b.c"]);
+        let expected = "//! Docs.\n\nuse a.b.c;\n\ntype Foo {\n  a: c::Baz;\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+
+        let content = "//! Docs.\n//! More docs.\n\ntype Foo {\n  a: c::Baz;\n}\n";
+        let out = apply(content, "c", "missing prefix `c`", &["a.b.c"]);
+        let expected = "//! Docs.\n//! More docs.\n\nuse a.b.c;\n\ntype Foo {\n  a: c::Baz;\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+
+        let content = "type Foo {\n  a: c::Baz;\n}\n";
+        let out = apply(content, "c", "missing prefix `c`", &["a.b.c"]);
+        let expected = "use a.b.c;\n\ntype Foo {\n  a: c::Baz;\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+    }
+
+    #[test]
+    fn test_add_path_variable() {
+        let message = "path variable `id` is not an argument to endpoint";
+
+        let content = "service Foo {\n  #[http(path = \"/{id}\")]\n  get();\n}\n";
+        let out = apply(content, "\"/{id}\"", message, &[]);
+        let expected = "service Foo {\n  #[http(path = \"/{id}\")]\n  get(id: string);\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+
+        let content = "service Foo {\n  #[http(path = \"/{id}\")]\n  get(a: u32);\n}\n";
+        let out = apply(content, "\"/{id}\"", message, &[]);
+        let expected =
+            "service Foo {\n  #[http(path = \"/{id}\")]\n  get(a: u32, id: string);\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+    }
+
+    #[test]
+    fn test_create_declaration() {
+        let content = "type Foo {\n  a: Bar;\n}\n";
+        let out = apply(content, "Bar", "`Bar` does not exist", &[]);
+        let expected = "type Foo {\n  a: Bar;\n}\n\ntype Bar {\n}\n";
+        assert_eq!(Some(expected.to_string()), out);
+
+        let content = "type Foo {\n  a: c::Bar;\n}\n";
+        let out = apply(content, "c::Bar", "`Bar` does not exist", &[]);
+        assert_eq!(None, out);
+    }
+}
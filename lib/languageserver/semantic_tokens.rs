@@ -0,0 +1,284 @@
+//! Semantic tokens for highlighting.
+//!
+//! Tokens are built from the lexer, which classifies keywords, literals and comments, combined
+//! with the semantic information collected when the file was loaded into the workspace.
+
+use crate::loaded_file::LoadedFile;
+use crate::models::Semantic;
+use crate::workspace::Workspace;
+use lexer::{Keyword, Token};
+use reproto_core::Span;
+use std::collections::BTreeMap;
+
+/// Modifier for the location where something is declared.
+const DECLARATION: u32 = 1 << 0;
+/// Modifier for documentation comments.
+const DOCUMENTATION: u32 = 1 << 1;
+/// Modifier for built-in types.
+const DEFAULT_LIBRARY: u32 = 1 << 2;
+
+/// Kind of a token, which is its index in the legend.
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Namespace,
+    Type,
+    Struct,
+    Interface,
+    Enum,
+    Class,
+    EnumMember,
+    Property,
+    Method,
+    Parameter,
+    Decorator,
+    Keyword,
+    Comment,
+    String,
+    Number,
+    Variable,
+}
+
+impl Kind {
+    /// All kinds, in the order that they are declared in the legend.
+    const ALL: [Kind; 16] = [
+        Kind::Namespace,
+        Kind::Type,
+        Kind::Struct,
+        Kind::Interface,
+        Kind::Enum,
+        Kind::Class,
+        Kind::EnumMember,
+        Kind::Property,
+        Kind::Method,
+        Kind::Parameter,
+        Kind::Decorator,
+        Kind::Keyword,
+        Kind::Comment,
+        Kind::String,
+        Kind::Number,
+        Kind::Variable,
+    ];
+
+    fn token_type(self) -> ty::SemanticTokenType {
+        match self {
+            Kind::Namespace => ty::SemanticTokenType::NAMESPACE,
+            Kind::Type => ty::SemanticTokenType::TYPE,
+            Kind::Struct => ty::SemanticTokenType::STRUCT,
+            Kind::Interface => ty::SemanticTokenType::INTERFACE,
+            Kind::Enum => ty::SemanticTokenType::ENUM,
+            Kind::Class => ty::SemanticTokenType::CLASS,
+            Kind::EnumMember => ty::SemanticTokenType::ENUM_MEMBER,
+            Kind::Property => ty::SemanticTokenType::PROPERTY,
+            Kind::Method => ty::SemanticTokenType::METHOD,
+            Kind::Parameter => ty::SemanticTokenType::PARAMETER,
+            Kind::Decorator => ty::SemanticTokenType::new("decorator"),
+            Kind::Keyword => ty::SemanticTokenType::KEYWORD,
+            Kind::Comment => ty::SemanticTokenType::COMMENT,
+            Kind::String => ty::SemanticTokenType::STRING,
+            Kind::Number => ty::SemanticTokenType::NUMBER,
+            Kind::Variable => ty::SemanticTokenType::VARIABLE,
+        }
+    }
+}
+
+/// The legend of all token types and modifiers used.
+pub fn legend() -> ty::SemanticTokensLegend {
+    ty::SemanticTokensLegend {
+        token_types: Kind::ALL.iter().map(|k| k.token_type()).collect(),
+        token_modifiers: vec![
+            ty::SemanticTokenModifier::DECLARATION,
+            ty::SemanticTokenModifier::DOCUMENTATION,
+            ty::SemanticTokenModifier::DEFAULT_LIBRARY,
+        ],
+    }
+}
+
+/// Build semantic tokens for a loaded file, optionally limited to the given span.
+///
+/// `content` must be the content that the file was loaded from.
+pub fn semantic_tokens(
+    workspace: &Workspace,
+    file: &LoadedFile,
+    content: &str,
+    span: Option<Span>,
+) -> Vec<ty::SemanticToken> {
+    // tokens keyed by their start offset.
+    let mut tokens = BTreeMap::new();
+
+    // the file might be incomplete, so only highlight up until the first error.
+    for token in lexer::lex(content) {
+        let (start, token, end) = match token {
+            Ok(token) => token,
+            Err(_) => break,
+        };
+
+        let (kind, modifiers) = match token {
+            Token::Keyword(keyword) => keyword_kind(keyword),
+            // `const` is only a keyword at the start of a declaration, fields which use it as a
+            // name are overridden by the semantic information below.
+            Token::Identifier(ref ident) if ident == "const" => (Kind::Keyword, 0),
+            Token::QuotedString(_) => (Kind::String, 0),
+            Token::Number(_) => (Kind::Number, 0),
+            Token::DocComment(_) | Token::PackageDocComment(_) => (Kind::Comment, DOCUMENTATION),
+            _ => continue,
+        };
+
+        tokens.insert(start, (end, kind, modifiers));
+    }
+
+    if let Ok(comments) = lexer::comments(content) {
+        for (start, end) in comments {
+            tokens.insert(start, (end, Kind::Comment, 0));
+        }
+    }
+
+    // semantic information takes precedence, since keywords can also be used as identifiers.
+    for (span, semantic) in &file.semantic {
+        let (kind, modifiers) = match *semantic {
+            Semantic::Declaration { kind } => (declaration_kind(kind), DECLARATION),
+            Semantic::Reference {
+                ref package,
+                ref path,
+            } => {
+                let kind = package
+                    .as_ref()
+                    .and_then(|package| workspace.declaration_kind(package, path))
+                    .map(declaration_kind)
+                    .unwrap_or(Kind::Type);
+
+                (kind, 0)
+            }
+            Semantic::Package => (Kind::Namespace, 0),
+            Semantic::SubType => (Kind::Class, DECLARATION),
+            Semantic::Field => (Kind::Property, DECLARATION),
+            Semantic::Variant => (Kind::EnumMember, DECLARATION),
+            Semantic::Endpoint => (Kind::Method, DECLARATION),
+            Semantic::Argument => (Kind::Parameter, DECLARATION),
+            Semantic::Attribute => (Kind::Decorator, 0),
+        };
+
+        tokens.insert(span.start, (span.end, kind, modifiers));
+    }
+
+    encode(content, tokens, span)
+}
+
+/// Classify a keyword.
+fn keyword_kind(keyword: Keyword) -> (Kind, u32) {
+    use lexer::Keyword::*;
+
+    match keyword {
+        Any | Boolean | Bytes | Datetime | Float | Double | I32 | I64 | String | U32 | U64 => {
+            (Kind::Type, DEFAULT_LIBRARY)
+        }
+        As | Enum | Interface | Service | Stream | Tuple | Type | Use => (Kind::Keyword, 0),
+    }
+}
+
+/// Classify a declaration by its kind.
+fn declaration_kind(kind: &str) -> Kind {
+    match kind {
+        "type" | "tuple" => Kind::Struct,
+        "interface" => Kind::Interface,
+        "enum" => Kind::Enum,
+        "service" => Kind::Class,
+        "const" => Kind::Variable,
+        _ => Kind::Type,
+    }
+}
+
+/// Encode tokens into the relative format used by the protocol.
+///
+/// Tokens spanning multiple lines are split up, since not all clients support them.
+fn encode(
+    content: &str,
+    tokens: BTreeMap<usize, (usize, Kind, u32)>,
+    span: Option<Span>,
+) -> Vec<ty::SemanticToken> {
+    let line_starts = ::std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(n, _)| n + 1))
+        .collect::<Vec<_>>();
+
+    let mut out = Vec::new();
+    let mut previous = (0u32, 0u32);
+    let mut last_end = 0;
+
+    for (start, (end, kind, modifiers)) in tokens {
+        // overlapping tokens are not permitted.
+        if start < last_end {
+            continue;
+        }
+
+        if let Some(span) = span {
+            if end <= span.start || span.end <= start {
+                continue;
+            }
+        }
+
+        last_end = end;
+
+        for (n, segment) in content[start..end].split('\n').enumerate() {
+            let trimmed = segment.trim_start();
+
+            if trimmed.trim_end().is_empty() {
+                continue;
+            }
+
+            let line = line_starts.partition_point(|s| *s <= start) - 1 + n;
+
+            let offset = if n == 0 { start } else { line_starts[line] };
+
+            let offset = offset + segment.len() - trimmed.len();
+            let character = content[line_starts[line]..offset].encode_utf16().count() as u32;
+            let line = line as u32;
+
+            let delta_start = if line == previous.0 {
+                character - previous.1
+            } else {
+                character
+            };
+
+            out.push(ty::SemanticToken {
+                delta_line: line - previous.0,
+                delta_start,
+                length: trimmed.trim_end().encode_utf16().count() as u32,
+                token_type: kind as u32,
+                token_modifiers_bitset: modifiers,
+            });
+
+            previous = (line, character);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, Kind};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_encode() {
+        let content = "type Foo {\n  /* a\n   b */\n}\n";
+
+        let mut tokens = BTreeMap::new();
+        tokens.insert(0, (4, Kind::Keyword, 0));
+        tokens.insert(5, (8, Kind::Struct, 1));
+        tokens.insert(13, (25, Kind::Comment, 0));
+
+        let out = encode(content, tokens, None)
+            .into_iter()
+            .map(|t| (t.delta_line, t.delta_start, t.length, t.token_type))
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            (0, 0, 4, Kind::Keyword as u32),
+            (0, 5, 3, Kind::Struct as u32),
+            (1, 2, 4, Kind::Comment as u32),
+            (1, 3, 4, Kind::Comment as u32),
+        ];
+
+        assert_eq!(expected, out);
+    }
+}
@@ -1,13 +1,18 @@
 //! A dynamically compiled and updated environment.
 
+use crate::collector::Collector;
 use crate::loaded_file::LoadedFile;
-use crate::models::{Completion, Jump, Prefix, Range, Rename, RenameResult, Symbol};
+use crate::models::{
+    Completion, Declaration, Hover, Jump, Prefix, Range, Rename, RenameResult, Semantic, Symbol,
+};
+use naming::Naming;
 use repository::{path_to_package, Packages, EXT};
 use reproto_core::errors::{Error, Result};
 use reproto_core::{
-    Encoding, Filesystem, Handle, Reported, Resolved, Resolver, RpPackage, RpRequiredPackage,
-    RpVersionedPackage, Source, Spanned,
+    Encoding, Filesystem, Handle, Reported, Resolved, Resolver, RpDeprecated, RpPackage,
+    RpRequiredPackage, RpVersionedPackage, Source, Span, Spanned, Version,
 };
+use std::borrow::Cow;
 use std::collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fs::File;
 use std::io::Read;
@@ -24,6 +29,8 @@ pub struct Workspace {
     pub manifest_error: Option<Error>,
     /// Packages which have been loaded through project.
     pub packages: HashMap<RpVersionedPackage, Url>,
+    /// Packages which are available to import through the resolver.
+    pub available_packages: BTreeSet<RpPackage>,
     /// Versioned packages that have been looked up.
     lookup_required: HashMap<RpRequiredPackage, Option<(RpVersionedPackage, bool)>>,
     /// Versioned packaged that have been loaded.
@@ -51,6 +58,7 @@ impl Workspace {
             manifest_path: root_path.as_ref().join(env::MANIFEST_NAME),
             manifest_error: None,
             packages: HashMap::new(),
+            available_packages: BTreeSet::new(),
             lookup_required: HashMap::new(),
             lookup_versioned: HashSet::new(),
             files: HashMap::new(),
@@ -76,6 +84,37 @@ impl Workspace {
         None
     }
 
+    /// Find the kind of the declaration with the given path in the given package.
+    pub fn declaration_kind(
+        &self,
+        package: &RpVersionedPackage,
+        path: &[String],
+    ) -> Option<&'static str> {
+        let url = self.packages.get(package)?;
+        let file = self.file(url)?;
+        file.declarations.get(path).map(|d| d.kind)
+    }
+
+    /// Find the deprecation of the declaration with the given path in the given package.
+    pub fn declaration_deprecated(
+        &self,
+        package: &RpVersionedPackage,
+        path: &[String],
+    ) -> Option<&RpDeprecated> {
+        let url = self.packages.get(package)?;
+        let file = self.file(url)?;
+        file.declarations.get(path)?.deprecated.as_ref()
+    }
+
+    /// Access the most recent source for the given Url, preferring open files.
+    pub fn source(&self, url: &Url) -> Option<Source> {
+        if let Some(source) = self.open_files.get(url) {
+            return Some(source.clone());
+        }
+
+        self.file(url).map(|file| file.diag.source.clone())
+    }
+
     /// Initialize the current project.
     pub fn initialize(&mut self, handle: &dyn Handle) -> Result<()> {
         env::initialize(handle)?;
@@ -257,12 +296,23 @@ impl Workspace {
             };
 
             self.packages.clear();
+            self.available_packages.clear();
             self.lookup_required.clear();
             self.lookup_versioned.clear();
             self.files.clear();
             sources
         };
 
+        match resolver.resolve_by_prefix(&RpPackage::empty()) {
+            Ok(resolved) => {
+                self.available_packages
+                    .extend(resolved.into_iter().map(|r| r.package.package));
+            }
+            Err(e) => {
+                log::error!("failed to list available packages: {}", e.display());
+            }
+        }
+
         for s in &sources {
             let manifest::Source {
                 ref package,
@@ -467,6 +517,7 @@ impl Workspace {
                 let content = &content[span.start..span.end];
                 let completion = self.package_completion(content, resolver)?;
                 loaded.completion_triggers.insert(range, completion);
+                loaded.register_semantic(span, Semantic::Package);
                 package
             };
 
@@ -487,6 +538,7 @@ impl Workspace {
                 let (alias, span) = Spanned::borrow_pair(alias);
                 let range = loaded.range(span)?;
                 loaded.register_rename_immediate_prefix(range, alias.as_ref())?;
+                loaded.register_semantic(span, Semantic::Package);
                 Some((alias.as_ref(), span))
             } else {
                 match parts.last() {
@@ -536,31 +588,48 @@ impl Workspace {
             }
         }
 
+        let mut collector = Collector::default();
+        collector.file(&file);
+
+        for attribute in collector.attributes {
+            let name = match Spanned::borrow(attribute) {
+                ast::Attribute::Word(name) => name,
+                ast::Attribute::List(name, _) => name,
+            };
+
+            loaded.register_semantic(name.span(), Semantic::Attribute);
+        }
+
+        let field_naming = field_naming(&file.attributes);
+
         let mut queue = VecDeque::new();
 
         queue.extend(file.decls.iter().map(|d| (vec![], d)));
 
         while let Some((mut path, decl)) = queue.pop_front() {
-            let comment = decl.comment();
-
-            let comment = if !comment.is_empty() {
-                Some(
-                    comment
-                        .iter()
-                        .map(|s| s.to_string())
-                        .collect::<Vec<_>>()
-                        .join("\n"),
-                )
-            } else {
-                None
-            };
+            let comment = join_comment(decl.comment());
 
             let symbol_path = path.clone();
             path.push(decl.name().to_string());
 
             loaded.symbol.insert(path.clone(), decl.name().span());
 
-            self.process_decl(&path, loaded, content.as_str(), decl)?;
+            loaded.declarations.insert(
+                path.clone(),
+                Declaration {
+                    kind: decl_kind(decl),
+                    comment: comment.clone(),
+                    deprecated: deprecated(decl.attributes()),
+                },
+            );
+
+            self.process_decl(
+                &path,
+                loaded,
+                content.as_str(),
+                field_naming.as_deref(),
+                decl,
+            )?;
 
             queue.extend(decl.decls().map(|decl| (path.clone(), decl)));
 
@@ -592,6 +661,7 @@ impl Workspace {
         current: &Vec<String>,
         loaded: &mut LoadedFile,
         content: &str,
+        field_naming: Option<&dyn Naming>,
         decl: &ast::Decl<'input>,
     ) -> Result<()> {
         use ast::Decl::*;
@@ -613,34 +683,156 @@ impl Workspace {
         // reference triggers are unconditionally set for names.
         loaded.register_reference(range, package, current.clone())?;
 
+        loaded.register_semantic(
+            span,
+            Semantic::Declaration {
+                kind: decl_kind(decl),
+            },
+        );
+
+        loaded.register_hover(
+            span,
+            Hover::Declaration {
+                path: current.clone(),
+            },
+        )?;
+
         match *decl {
             Type(ref ty) => {
-                for f in ty.fields() {
-                    self.process_ty(current, loaded, content, &f.ty)?;
-                }
+                self.process_members(current, loaded, content, field_naming, &ty.members)?;
             }
             Tuple(ref tuple) => {
-                for f in tuple.fields() {
-                    self.process_ty(current, loaded, content, &f.ty)?;
-                }
+                self.process_members(current, loaded, content, field_naming, &tuple.members)?;
             }
             Interface(ref interface) => {
-                for f in interface.fields() {
-                    self.process_ty(current, loaded, content, &f.ty)?;
+                self.process_members(current, loaded, content, field_naming, &interface.members)?;
+
+                for sub_type in &interface.sub_types {
+                    loaded.register_semantic(sub_type.name.span(), Semantic::SubType);
+
+                    self.process_members(
+                        current,
+                        loaded,
+                        content,
+                        field_naming,
+                        &sub_type.members,
+                    )?;
+                }
+            }
+            Enum(ref en) => {
+                for variant in &en.variants {
+                    let (name, span) = Spanned::borrow_pair(&variant.name);
+                    loaded.register_semantic(span, Semantic::Variant);
+
+                    let value = match variant.argument {
+                        Some(ref argument) => {
+                            let span = argument.span();
+                            content[span.start..span.end].to_string()
+                        }
+                        None => format!("{:?}", name.as_ref()),
+                    };
+
+                    let signature = format!("{}::{} = {}", current.join("::"), name, value);
+                    let value = hover_markdown(&signature, &[], join_comment(&variant.comment));
+                    loaded.register_hover(span, Hover::Markdown { value })?;
                 }
             }
-            Enum(ref _en) => {}
             Service(ref service) => {
-                for e in service.endpoints() {
+                for m in &service.members {
+                    let e = match *m {
+                        ast::ServiceMember::Endpoint(ref e) => e,
+                        _ => continue,
+                    };
+
+                    loaded.register_semantic(e.id.span(), Semantic::Endpoint);
+
+                    let mut arguments = Vec::new();
+
                     for a in &e.arguments {
+                        loaded.register_semantic(a.ident.span(), Semantic::Argument);
                         self.process_ty(current, loaded, content, a.channel.ty())?;
+
+                        let span = a.channel.span();
+                        let channel = &content[span.start..span.end];
+                        arguments.push(format!("{}: {}", a.ident, channel));
                     }
 
+                    let mut signature = format!("{}({})", e.id, arguments.join(", "));
+
                     if let Some(response) = e.response.as_ref() {
                         self.process_ty(current, loaded, content, response.ty())?;
+
+                        let span = response.span();
+                        signature.push_str(" -> ");
+                        signature.push_str(&content[span.start..span.end]);
+                    }
+
+                    let mut details = Vec::new();
+
+                    if let Some(alias) = e.alias.as_ref() {
+                        details.push(format!("Name: `{}`", alias));
                     }
+
+                    let value = hover_markdown(&signature, &details, join_comment(&e.comment));
+                    loaded.register_hover(e.id.span(), Hover::Markdown { value })?;
                 }
             }
+            Const(ref body) => {
+                self.process_ty(current, loaded, content, &body.ty)?;
+            }
+            Alias(ref body) => {
+                self.process_ty(current, loaded, content, &body.ty)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process all fields in the given members.
+    fn process_members<'input>(
+        &mut self,
+        current: &Vec<String>,
+        loaded: &mut LoadedFile,
+        content: &str,
+        field_naming: Option<&dyn Naming>,
+        members: &[ast::TypeMember<'input>],
+    ) -> Result<()> {
+        for m in members {
+            let field = match *m {
+                ast::TypeMember::Field(ref field) => field,
+                _ => continue,
+            };
+
+            self.process_ty(current, loaded, content, &field.ty)?;
+
+            let ty = &content[field.ty.span().start..field.ty.span().end];
+            let modifier = if field.required { "" } else { "?" };
+            let signature = format!("{}{}: {}", field.name, modifier, ty);
+
+            let name = match (field.field_as.as_ref(), field_naming) {
+                (Some(field_as), _) => field_as.to_string(),
+                (None, Some(naming)) => naming.convert(field.name.as_ref()),
+                (None, None) => field.name.to_string(),
+            };
+
+            let details = vec![format!("JSON: `{:?}`", name)];
+
+            // NB: the field name has no span of its own, so use everything leading up to the type.
+            let span = Span {
+                start: field.item.span().start,
+                end: field.ty.span().start,
+            };
+
+            let value = hover_markdown(&signature, &details, join_comment(&field.comment));
+            loaded.register_hover(span, Hover::Markdown { value })?;
+
+            let start = field.item.span().start;
+            let end = content[start..]
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .map(|n| start + n)
+                .unwrap_or(content.len());
+
+            loaded.register_semantic(Span { start, end }, Semantic::Field);
         }
 
         Ok(())
@@ -665,8 +857,16 @@ impl Workspace {
             }
             ref ty => {
                 // load jump-to definitions
-                if let ast::Type::Name { ref name } = *ty {
+                if let ast::Type::Name {
+                    ref name,
+                    ref arguments,
+                } = *ty
+                {
                     self.process_name(name, current, loaded)?;
+
+                    for argument in arguments {
+                        self.process_ty(current, loaded, content, argument)?;
+                    }
                 }
 
                 let (start, end) = loaded.diag.source.span_to_range(span, Encoding::Utf16)?;
@@ -711,7 +911,15 @@ impl Workspace {
 
                     loaded.register_rename_trigger(range, None, full_path.clone())?;
                     loaded.register_type_range(range, package.clone(), full_path.clone())?;
-                    loaded.register_reference(range, package, full_path.clone())?;
+                    loaded.register_reference(range, package.clone(), full_path.clone())?;
+
+                    loaded.register_semantic(
+                        span,
+                        Semantic::Reference {
+                            package: Some(package),
+                            path: full_path.clone(),
+                        },
+                    );
 
                     loaded.register_jump(
                         range,
@@ -736,6 +944,7 @@ impl Workspace {
 
                     // register prefix rename.
                     loaded.register_rename_immediate_prefix(range, prefix)?;
+                    loaded.register_semantic(span, Semantic::Package);
 
                     loaded.register_jump(
                         range,
@@ -779,6 +988,14 @@ impl Workspace {
                     if let Some(package) = package.as_ref() {
                         loaded.register_reference(range, package.clone(), full_path.clone())?;
                     }
+
+                    loaded.register_semantic(
+                        span,
+                        Semantic::Reference {
+                            package: package.clone(),
+                            path: full_path.clone(),
+                        },
+                    );
                 }
             }
         }
@@ -908,6 +1125,17 @@ impl Workspace {
         None
     }
 
+    /// Find the associated hover.
+    pub fn find_hover(&self, url: &Url, position: ty::Position) -> Option<(&LoadedFile, &Hover)> {
+        let file = self.file(url)?;
+
+        if let Some(value) = file.hover_triggers.find(position) {
+            return Some((file, value));
+        }
+
+        None
+    }
+
     /// Find the specified rename.
     pub fn find_rename<'a>(
         &'a self,
@@ -1016,6 +1244,131 @@ impl Workspace {
     }
 }
 
+/// Join a documentation comment into markdown, if present.
+fn join_comment(comment: &[Cow<str>]) -> Option<String> {
+    if comment.is_empty() {
+        return None;
+    }
+
+    Some(
+        comment
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// Get the keyword used to declare the given declaration.
+fn decl_kind(decl: &ast::Decl) -> &'static str {
+    use ast::Decl::*;
+
+    match *decl {
+        Type(..) | Alias(..) => "type",
+        Tuple(..) => "tuple",
+        Interface(..) => "interface",
+        Enum(..) => "enum",
+        Service(..) => "service",
+        Const(..) => "const",
+    }
+}
+
+/// Find the naming policy for fields specified through `#![field_naming(..)]`.
+///
+/// Errors are ignored, since they are reported when the file is compiled.
+fn field_naming(attributes: &[Spanned<ast::Attribute>]) -> Option<Box<dyn Naming>> {
+    for attribute in attributes {
+        let (name, items) = match *Spanned::borrow(attribute) {
+            ast::Attribute::List(ref name, ref items) => (name, items),
+            _ => continue,
+        };
+
+        if Spanned::borrow(name).as_ref() != "field_naming" {
+            continue;
+        }
+
+        let naming = match items.first() {
+            Some(ast::AttributeItem::Word(word)) => match *Spanned::borrow(word) {
+                ast::Value::Identifier(ref naming) => naming,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        return match naming.as_ref() {
+            "upper_camel" => Some(Box::new(naming::to_upper_camel())),
+            "lower_camel" => Some(Box::new(naming::to_lower_camel())),
+            "upper_snake" => Some(Box::new(naming::to_upper_snake())),
+            _ => None,
+        };
+    }
+
+    None
+}
+
+/// Find the deprecation specified through `#[deprecated(..)]`.
+///
+/// Errors are ignored, since they are reported when the file is compiled.
+fn deprecated(attributes: &[Spanned<ast::Attribute>]) -> Option<RpDeprecated> {
+    for attribute in attributes {
+        let (name, items) = match *Spanned::borrow(attribute) {
+            ast::Attribute::Word(ref name) => (name, &[][..]),
+            ast::Attribute::List(ref name, ref items) => (name, &items[..]),
+        };
+
+        if Spanned::borrow(name).as_ref() != "deprecated" {
+            continue;
+        }
+
+        let mut deprecated = RpDeprecated::default();
+
+        for item in items {
+            let (name, value) = match *item {
+                ast::AttributeItem::NameValue {
+                    ref name,
+                    ref value,
+                } => (name, value),
+                _ => continue,
+            };
+
+            let value = match *Spanned::borrow(value) {
+                ast::Value::String(ref value) => value,
+                _ => continue,
+            };
+
+            match Spanned::borrow(name).as_ref() {
+                "note" => deprecated.note = Some(value.to_string()),
+                "since" => deprecated.since = Version::parse(value).ok(),
+                _ => {}
+            }
+        }
+
+        return Some(deprecated);
+    }
+
+    None
+}
+
+/// Render markdown for a hover.
+///
+/// The signature is rendered as a code block, followed by any details and the documentation
+/// comment.
+pub fn hover_markdown(signature: &str, details: &[String], comment: Option<String>) -> String {
+    let mut out = format!("```reproto\n{}\n```", signature);
+
+    for detail in details {
+        out.push_str("\n\n");
+        out.push_str(detail);
+    }
+
+    if let Some(comment) = comment {
+        out.push_str("\n\n");
+        out.push_str(&comment);
+    }
+
+    out
+}
+
 fn relative<'a>(from: &Path, to: &'a Path) -> Option<&'a Path> {
     let mut f = from.components();
     let mut t = to.components();
@@ -18,6 +18,8 @@ pub struct Lexer<'input> {
     buffer: String,
     code_block: Option<(usize, usize)>,
     code_close: Option<(usize, usize)>,
+    /// Collected comments, if requested.
+    comments: Option<Vec<(usize, usize)>>,
 }
 
 pub fn match_keyword(content: &str) -> Option<Keyword> {
@@ -369,13 +371,18 @@ impl<'input> Lexer<'input> {
         }
     }
 
-    fn line_comment(&mut self) {
-        let start = self.step_n(2);
-        let _ = take_until!(self, start, '\n' | '\r');
+    fn line_comment(&mut self, start: usize) {
+        let content_start = self.step_n(2);
+        let (_, content) = take_until!(self, content_start, '\n' | '\r');
+        let end = content_start + content.len();
+
+        if let Some(comments) = self.comments.as_mut() {
+            comments.push((start, end));
+        }
     }
 
     // block comments have no semantics and are completely ignored.
-    fn block_comment(&mut self) {
+    fn block_comment(&mut self, start: usize) {
         self.step_n(2);
 
         while let Some((_, a, b)) = self.two() {
@@ -387,6 +394,12 @@ impl<'input> Lexer<'input> {
 
             self.step();
         }
+
+        let end = self.pos();
+
+        if let Some(comments) = self.comments.as_mut() {
+            comments.push((start, end));
+        }
     }
 
     fn normal_mode_next(&mut self) -> Option<Result<(usize, Token<'input>, usize)>> {
@@ -416,11 +429,11 @@ impl<'input> Lexer<'input> {
             if let Some((start, a, b)) = self.two() {
                 let token = match (a, b) {
                     ('/', '/') => {
-                        self.line_comment();
+                        self.line_comment(start);
                         continue;
                     }
                     ('/', '*') => {
-                        self.block_comment();
+                        self.block_comment(start);
                         continue;
                     }
                     ('{', '{') => {
@@ -448,6 +461,8 @@ impl<'input> Lexer<'input> {
                     ']' => Token::RightBracket,
                     '(' => Token::LeftParen,
                     ')' => Token::RightParen,
+                    '<' => Token::LeftAngle,
+                    '>' => Token::RightAngle,
                     ';' => Token::SemiColon,
                     ':' => Token::Colon,
                     ',' => Token::Comma,
@@ -504,9 +519,25 @@ pub fn lex(input: &str) -> Lexer {
         buffer: String::new(),
         code_block: None,
         code_close: None,
+        comments: None,
     }
 }
 
+/// Collect the spans of all line and block comments in the given input.
+///
+/// These have no semantics, so they are otherwise discarded by the lexer. Doc comments are not
+/// included since they are part of the syntax tree.
+pub fn comments(input: &str) -> Result<Vec<(usize, usize)>> {
+    let mut lexer = lex(input);
+    lexer.comments = Some(Vec::new());
+
+    for token in &mut lexer {
+        token?;
+    }
+
+    Ok(lexer.comments.unwrap_or_default())
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::Keyword::*;
@@ -578,6 +609,20 @@ pub mod tests {
         assert_eq!(expected, tokenize("foo::Bar.Baz").unwrap());
     }
 
+    #[test]
+    pub fn test_type_arguments() {
+        let expected = vec![
+            (0, TypeIdentifier("Page".into()), 4),
+            (4, LeftAngle, 5),
+            (5, TypeIdentifier("T".into()), 6),
+            (6, Comma, 7),
+            (8, Keyword(String), 14),
+            (14, RightAngle, 15),
+        ];
+
+        assert_eq!(expected, tokenize("Page<T, string>").unwrap());
+    }
+
     #[test]
     pub fn test_strings() {
         let expected = vec![(0, QuotedString("foo\nbar".to_owned()), 10)];
@@ -620,6 +665,13 @@ pub mod tests {
         assert_eq!(vec![(16, Identifier("hello".into()), 21)], tokens.unwrap());
     }
 
+    #[test]
+    pub fn test_collect_comments() {
+        let input = "// hello \n/// doc\nworld /* a */ \"// no\" {{ // code }}";
+        let comments = comments(input).unwrap();
+        assert_eq!(vec![(0, 9), (24, 31)], comments);
+    }
+
     #[test]
     pub fn test_identifier_stripping() {
         let a = &tokenize("my_version").unwrap()[0].1;
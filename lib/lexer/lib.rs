@@ -7,5 +7,5 @@ pub(crate) mod lexer;
 pub(crate) mod token;
 
 pub use self::errors::Error;
-pub use self::lexer::{lex, match_keyword};
+pub use self::lexer::{comments, lex, match_keyword};
 pub use self::token::{Keyword, Token};
@@ -90,6 +90,8 @@ pub enum Token<'input> {
     RightBracket,
     LeftParen,
     RightParen,
+    LeftAngle,
+    RightAngle,
     SemiColon,
     Colon,
     Equal,
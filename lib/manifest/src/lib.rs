@@ -290,6 +290,7 @@ pub enum Language {
     Reproto,
     Rust,
     Swift,
+    TypeScript,
 }
 
 impl Language {
@@ -308,6 +309,7 @@ impl Language {
             "reproto" => Reproto,
             "rust" => Rust,
             "swift" => Swift,
+            "typescript" => TypeScript,
             _ => return None,
         };
 
@@ -202,6 +202,92 @@ mod tests {
         parse_member("java{{\na { b { c } d } e\n}}");
     }
 
+    #[test]
+    fn test_field_default() {
+        if let TypeMember::Field(field) = parse_member("a: u32 as \"b\" = 10;") {
+            let field = Spanned::borrow(&field.item);
+            assert_eq!(Some("b".to_string()), field.field_as);
+
+            if let Some(ref default) = field.default {
+                assert_eq!(Value::Number(10.into()), *Spanned::borrow(default));
+                return;
+            }
+        }
+
+        panic!("Expected field with default value");
+    }
+
+    #[test]
+    fn test_const() {
+        let file = parse_file("/// Size of a page.\nconst PageSize: u32 = 10;");
+
+        if let Some(Decl::Const(item)) = file.decls.first() {
+            assert_eq!(vec![" Size of a page."], item.comment);
+
+            let body = Spanned::borrow(&item.item);
+            assert_eq!("PageSize", *Spanned::borrow(&body.name));
+            assert_eq!(Value::Number(10.into()), *Spanned::borrow(&body.value));
+            return;
+        }
+
+        panic!("Expected const declaration");
+    }
+
+    #[test]
+    fn test_const_field() {
+        let file = parse_file("type Entry { const?: string; const Size: u32 = 10; }");
+
+        if let Some(Decl::Type(item)) = file.decls.first() {
+            let body = Spanned::borrow(&item.item);
+
+            if let [TypeMember::Field(field), TypeMember::InnerDecl(Decl::Const(_))] =
+                body.members.as_slice()
+            {
+                assert_eq!("const", Spanned::borrow(&field.item).name);
+                return;
+            }
+        }
+
+        panic!("Expected type with a const field and a const declaration");
+    }
+
+    #[test]
+    fn test_alias() {
+        let file = parse_file("/// Identifier of a user.\ntype UserId = string;");
+
+        if let Some(Decl::Alias(item)) = file.decls.first() {
+            assert_eq!(vec![" Identifier of a user."], item.comment);
+
+            let body = Spanned::borrow(&item.item);
+            assert_eq!("UserId", *Spanned::borrow(&body.name));
+            assert_eq!(Type::String, *Spanned::borrow(&body.ty));
+            return;
+        }
+
+        panic!("Expected alias declaration");
+    }
+
+    #[test]
+    fn test_generic() {
+        let file = parse_file("type Page<T> { items: [T]; }");
+
+        if let Some(Decl::Type(item)) = file.decls.first() {
+            let body = Spanned::borrow(&item.item);
+            assert_eq!("Page", *Spanned::borrow(&body.name));
+
+            let type_parameters = body
+                .type_parameters
+                .iter()
+                .map(|p| Spanned::borrow(p).as_ref())
+                .collect::<Vec<_>>();
+
+            assert_eq!(vec!["T"], type_parameters);
+            return;
+        }
+
+        panic!("Expected generic type declaration");
+    }
+
     #[test]
     fn test_interface() {
         let input = ::std::str::from_utf8(INTERFACE1).unwrap();
@@ -264,9 +350,18 @@ mod tests {
         assert_type!(Type::String, "string");
         assert_type!(
             Type::Name {
-                name: Spanned::new(c, Span::empty())
+                name: Spanned::new(c.clone(), Span::empty()),
+                arguments: vec![],
             },
             "Hello::World"
         );
+
+        assert_type!(
+            Type::Name {
+                name: Spanned::new(c, Span::empty()),
+                arguments: vec![Spanned::new(Type::String, Span::empty())],
+            },
+            "Hello::World<string>"
+        );
     }
 }
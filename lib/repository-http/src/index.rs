@@ -0,0 +1,167 @@
+//! ## Load an index from a remote repository over HTTP
+
+use crate::{authorize, bad_response, check_scheme, client, send, to_uri, HttpClient};
+use hyper::{Body, Method, Request};
+use repository::{Checksum, Deployment, Index, Objects};
+use reproto_core::errors::{Error, Result};
+use reproto_core::{Range, RelativePath, RpPackage, Version};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use url::Url;
+
+/// A deployment of a package, as returned when resolving packages by prefix.
+#[derive(Deserialize)]
+struct PackageDeployment {
+    package: RpPackage,
+    deployment: Deployment,
+}
+
+pub struct HttpIndex {
+    url: Url,
+    objects_url: String,
+    token: Option<String>,
+    client: HttpClient,
+}
+
+impl HttpIndex {
+    /// Build the URL for the given path segments in the index.
+    fn index_url<'a, I>(&self, segments: I) -> Result<Url>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut url = self.url.join("index/")?;
+
+        url.path_segments_mut()
+            .map_err(|_| format!("not a base URL: {}", self.url))?
+            .pop_if_empty()
+            .extend(segments);
+
+        Ok(url)
+    }
+
+    /// Perform a request, and deserialize the JSON response.
+    fn get_json<T>(&self, url: Url) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let request = authorize(Request::builder(), self.token.as_deref())
+            .method(Method::GET)
+            .uri(to_uri(&url)?)
+            .body(Body::empty())?;
+
+        let body = futures_executor::block_on(async {
+            let (body, status) = send(&self.client, request).await?;
+
+            if !status.is_success() {
+                return Err(bad_response(status, body));
+            }
+
+            Ok::<_, Error>(body)
+        })?;
+
+        serde_json::from_slice(&body)
+            .map_err(|e| format!("bad response from index: {}: {}", url, e).into())
+    }
+}
+
+impl Index for HttpIndex {
+    fn resolve(&self, package: &RpPackage, range: &Range) -> Result<Vec<Deployment>> {
+        let mut url = self.index_url(vec![package.to_string().as_str()])?;
+        url.query_pairs_mut()
+            .append_pair("range", range.to_string().as_str());
+        self.get_json(url)
+    }
+
+    fn resolve_by_prefix(&self, package: &RpPackage) -> Result<Vec<(Deployment, RpPackage)>> {
+        let mut url = self.index_url(None)?;
+        url.query_pairs_mut()
+            .append_pair("prefix", package.to_string().as_str());
+
+        let deployments: Vec<PackageDeployment> = self.get_json(url)?;
+
+        Ok(deployments
+            .into_iter()
+            .map(|d| (d.deployment, d.package))
+            .collect())
+    }
+
+    fn all(&self, package: &RpPackage) -> Result<Vec<Deployment>> {
+        let url = self.index_url(vec![package.to_string().as_str()])?;
+        self.get_json(url)
+    }
+
+    fn put_version(
+        &self,
+        checksum: &Checksum,
+        package: &RpPackage,
+        version: &Version,
+        force: bool,
+    ) -> Result<()> {
+        let package = package.to_string();
+        let version = version.to_string();
+        let checksum = checksum.to_string();
+
+        let mut url =
+            self.index_url(vec![package.as_str(), version.as_str(), checksum.as_str()])?;
+
+        if force {
+            url.query_pairs_mut().append_pair("force", "true");
+        }
+
+        let request = authorize(Request::builder(), self.token.as_deref())
+            .method(Method::PUT)
+            .uri(to_uri(&url)?)
+            .body(Body::empty())?;
+
+        futures_executor::block_on(async {
+            let (body, status) = send(&self.client, request).await?;
+
+            if !status.is_success() {
+                return Err(bad_response(status, body));
+            }
+
+            Ok::<_, Error>(())
+        })
+    }
+
+    fn get_deployments(&self, package: &RpPackage, version: &Version) -> Result<Vec<Deployment>> {
+        let package = package.to_string();
+        let version = version.to_string();
+        let url = self.index_url(vec![package.as_str(), version.as_str()])?;
+        self.get_json(url)
+    }
+
+    fn objects_url(&self) -> Result<&str> {
+        Ok(self.objects_url.as_str())
+    }
+
+    fn objects_from_index(&self, relative_path: &RelativePath) -> Result<Box<dyn Objects>> {
+        Err(format!(
+            "objects relative to an HTTP index are not supported: {}",
+            relative_path
+        )
+        .into())
+    }
+}
+
+/// Load an index from an HTTP url.
+///
+/// Objects are loaded from the `objects/` path of the same server.
+/// If `token` is specified, it is used to authenticate all requests.
+/// Plain HTTP URLs are only permitted if `allow_http` is set.
+pub fn index_from_url(
+    url: &Url,
+    token: Option<String>,
+    allow_http: bool,
+) -> Result<Box<dyn Index>> {
+    check_scheme(url, allow_http)?;
+
+    let objects_url = url.join("objects/")?.to_string();
+
+    Ok(Box::new(HttpIndex {
+        url: url.clone(),
+        objects_url,
+        token,
+        client: client(allow_http),
+    }))
+}
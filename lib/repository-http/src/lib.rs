@@ -1,7 +1,12 @@
-//! ## Load objects from a remote repository over HTTP
+//! ## Load objects and indexes from a remote repository over HTTP
+
+mod index;
+
+pub use self::index::{index_from_url, HttpIndex};
 
 use hyper::client::HttpConnector;
-use hyper::{Body, Client, Method, Request, StatusCode};
+use hyper::http::request;
+use hyper::{header, Body, Client, Method, Request, StatusCode};
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
 use repository::{CachedObjects, Checksum, HexSlice, Objects, ObjectsConfig};
 use reproto_core::errors::{Error, Result};
@@ -11,9 +16,78 @@ use std::time::Duration;
 use tokio_stream::StreamExt;
 use url::Url;
 
+type HttpClient = Client<HttpsConnector<HttpConnector>, Body>;
+
+/// Build a client which only supports HTTPS, unless plain HTTP is explicitly allowed.
+fn client(allow_http: bool) -> HttpClient {
+    let builder = HttpsConnectorBuilder::new().with_native_roots();
+
+    let connector = if allow_http {
+        builder.https_or_http().enable_http1().build()
+    } else {
+        builder.https_only().enable_http1().build()
+    };
+
+    Client::builder().build(connector)
+}
+
+/// Check that the given URL may be used, since plain HTTP would send credentials unencrypted.
+fn check_scheme(url: &Url, allow_http: bool) -> Result<()> {
+    if url.scheme() == "http" && !allow_http {
+        return Err(format!(
+            "plain HTTP is not permitted: {}: set `allow_http = true` in the `[repository]` \
+             section of the user configuration to allow it",
+            url
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Convert an URL into an URI that can be used in a request.
+fn to_uri(url: &Url) -> Result<hyper::Uri> {
+    url.to_string()
+        .parse::<hyper::Uri>()
+        .map_err(|e| format!("Failed to parse URL: {}: {}", e, url).into())
+}
+
+/// Authenticate the request with the given token, if present.
+fn authorize(builder: request::Builder, token: Option<&str>) -> request::Builder {
+    match token {
+        Some(token) => builder.header(header::AUTHORIZATION, format!("Bearer {}", token)),
+        None => builder,
+    }
+}
+
+/// Send the request and collect the body of the response.
+async fn send(client: &HttpClient, request: Request<Body>) -> Result<(Vec<u8>, StatusCode)> {
+    let mut res = client.request(request).await?;
+
+    let body = res.body_mut();
+    let mut output = Vec::new();
+
+    while let Some(chunk) = body.next().await {
+        let bytes = chunk?;
+        output.extend(&bytes[..]);
+    }
+
+    Ok((output, res.status()))
+}
+
+/// Build an error for an unexpected response.
+fn bad_response(status: StatusCode, body: Vec<u8>) -> Error {
+    if let Ok(body) = String::from_utf8(body) {
+        return format!("bad response: {}: {}", status, body).into();
+    }
+
+    format!("bad response: {}", status).into()
+}
+
 pub struct HttpObjects {
     url: Url,
-    client: Client<HttpsConnector<HttpConnector>, Body>,
+    token: Option<String>,
+    client: HttpClient,
 }
 
 impl HttpObjects {
@@ -22,26 +96,7 @@ impl HttpObjects {
             .url
             .join(HexSlice::new(checksum).to_string().as_ref())?;
 
-        let url = url
-            .to_string()
-            .parse::<hyper::Uri>()
-            .map_err(|e| format!("Failed to parse URL: {}: {}", e, url))?;
-
-        Ok(url)
-    }
-
-    async fn handle_request(&mut self, request: Request<Body>) -> Result<(Vec<u8>, StatusCode)> {
-        let mut res = self.client.request(request).await?;
-
-        let body = res.body_mut();
-        let mut output = Vec::new();
-
-        while let Some(chunk) = body.next().await {
-            let bytes = chunk?;
-            output.extend(&bytes[..]);
-        }
-
-        Ok((output, res.status()))
+        to_uri(&url)
     }
 }
 
@@ -57,20 +112,16 @@ impl Objects for HttpObjects {
 
         let url = self.checksum_url(checksum)?;
 
-        let request = Request::builder()
+        let request = authorize(Request::builder(), self.token.as_deref())
             .method(Method::PUT)
             .uri(url)
             .body(Body::from(buffer))?;
 
         futures_executor::block_on(async {
-            let (body, status) = self.handle_request(request).await?;
+            let (body, status) = send(&self.client, request).await?;
 
             if !status.is_success() {
-                if let Ok(body) = String::from_utf8(body) {
-                    return Err(format!("bad response: {}: {}", status, body).into());
-                }
-
-                return Err(format!("bad response: {}", status).into());
+                return Err(bad_response(status, body));
             }
 
             Ok::<_, Error>(())
@@ -84,13 +135,13 @@ impl Objects for HttpObjects {
         let url = self.checksum_url(checksum)?;
         let name = url.to_string();
 
-        let request = Request::builder()
+        let request = authorize(Request::builder(), self.token.as_deref())
             .method(Method::GET)
             .uri(url)
             .body(Body::empty())?;
 
         let out = futures_executor::block_on(async {
-            let (body, status) = self.handle_request(request).await?;
+            let (body, status) = send(&self.client, request).await?;
 
             if status.is_success() {
                 return Ok::<_, Error>(Some(body));
@@ -100,29 +151,28 @@ impl Objects for HttpObjects {
                 return Ok(None);
             }
 
-            if let Ok(body) = String::from_utf8(body) {
-                return Err(format!("bad response: {}: {}", status, body).into());
-            }
-
-            return Err(format!("bad response: {}", status).into());
+            Err(bad_response(status, body))
         })?;
         Ok(out.map(|out| Source::bytes(name, out)))
     }
 }
 
 /// Load objects from an HTTP url.
-pub fn objects_from_url(config: ObjectsConfig, url: &Url) -> Result<Box<dyn Objects>> {
-    let client = Client::builder().build(
-        HttpsConnectorBuilder::new()
-            .with_native_roots()
-            .https_only()
-            .enable_http1()
-            .build(),
-    );
+///
+/// If `token` is specified, it is used to authenticate all requests.
+/// Plain HTTP URLs are only permitted if `allow_http` is set.
+pub fn objects_from_url(
+    config: ObjectsConfig,
+    url: &Url,
+    token: Option<String>,
+    allow_http: bool,
+) -> Result<Box<dyn Objects>> {
+    check_scheme(url, allow_http)?;
 
     let http_objects = HttpObjects {
         url: url.clone(),
-        client,
+        token,
+        client: client(allow_http),
     };
 
     let missing_cache_time = config
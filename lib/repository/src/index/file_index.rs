@@ -125,20 +125,10 @@ impl FileIndex {
         Ok(())
     }
 
-    fn path_for(&self, package: &RpPackage) -> PathBuf {
-        package
-            .parts()
-            .fold(self.path.clone(), |path, next| path.join(next))
-    }
-}
-
-impl Index for FileIndex {
-    fn resolve(&self, package: &RpPackage, range: &Range) -> Result<Vec<Deployment>> {
-        self.read_package(package, |d| range.matches(&d.version))
-            .map(|r| r.0)
-    }
-
-    fn resolve_by_prefix(&self, package: &RpPackage) -> Result<Vec<(Deployment, RpPackage)>> {
+    /// Find all packages matching the given prefix, together with all of their deployments.
+    ///
+    /// Packages without any deployments are skipped.
+    fn packages_by_prefix(&self, package: &RpPackage) -> Result<Vec<(Vec<Deployment>, RpPackage)>> {
         let root = package.parts().fold(self.path.to_owned(), |p, f| p.join(f));
         let parts = package.parts().map(|p| p.to_string()).collect::<Vec<_>>();
 
@@ -153,8 +143,8 @@ impl Index for FileIndex {
 
                 let res = self.read_package(&package, |_| true)?;
 
-                if let Some(d) = res.0.into_iter().next_back() {
-                    out.push((d, package));
+                if !res.0.is_empty() {
+                    out.push((res.0, package));
                 }
 
                 continue;
@@ -187,6 +177,35 @@ impl Index for FileIndex {
         Ok(out)
     }
 
+    fn path_for(&self, package: &RpPackage) -> PathBuf {
+        package
+            .parts()
+            .fold(self.path.clone(), |path, next| path.join(next))
+    }
+}
+
+impl Index for FileIndex {
+    fn resolve(&self, package: &RpPackage, range: &Range) -> Result<Vec<Deployment>> {
+        self.read_package(package, |d| range.matches(&d.version))
+            .map(|r| r.0)
+    }
+
+    fn resolve_by_prefix(&self, package: &RpPackage) -> Result<Vec<(Deployment, RpPackage)>> {
+        let mut out = Vec::new();
+
+        for (mut deployments, package) in self.packages_by_prefix(package)? {
+            if let Some(d) = deployments.pop() {
+                out.push((d, package));
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn all_by_prefix(&self, package: &RpPackage) -> Result<Vec<(Vec<Deployment>, RpPackage)>> {
+        self.packages_by_prefix(package)
+    }
+
     fn all(&self, package: &RpPackage) -> Result<Vec<Deployment>> {
         self.read_package(package, |_| true)
             .map(|r| r.0)
@@ -44,6 +44,10 @@ impl Index for GitIndex {
         self.file_index.all(package)
     }
 
+    fn all_by_prefix(&self, package: &RpPackage) -> Result<Vec<(Vec<Deployment>, RpPackage)>> {
+        self.file_index.all_by_prefix(package)
+    }
+
     fn put_version(
         &self,
         checksum: &Checksum,
@@ -47,6 +47,19 @@ pub trait Index: Send {
     /// The returned versions are sorted.
     fn all(&self, package: &RpPackage) -> Result<Vec<Deployment>>;
 
+    /// Get all versions available of all packages matching the given prefix.
+    ///
+    /// The returned versions are sorted for each package.
+    fn all_by_prefix(&self, package: &RpPackage) -> Result<Vec<(Vec<Deployment>, RpPackage)>> {
+        let mut out = Vec::new();
+
+        for (_, package) in self.resolve_by_prefix(package)? {
+            out.push((self.all(&package)?, package));
+        }
+
+        Ok(out)
+    }
+
     fn put_version(
         &self,
         checksum: &Checksum,
@@ -167,7 +180,18 @@ where
     open_git_index(url, git_repo, publishing)
 }
 
-pub fn index_from_url(config: IndexConfig, url: &Url, publishing: bool) -> Result<Box<dyn Index>> {
+/// Load an index from an URL.
+///
+/// Schemes which are not supported natively are delegated to `fallback`.
+pub fn index_from_url<F>(
+    config: IndexConfig,
+    url: &Url,
+    fallback: F,
+    publishing: bool,
+) -> Result<Box<dyn Index>>
+where
+    F: Fn(IndexConfig, &str, &Url) -> Result<Option<Box<dyn Index>>>,
+{
     let mut scheme = url.scheme().split("+");
 
     let first = scheme
@@ -180,7 +204,10 @@ pub fn index_from_url(config: IndexConfig, url: &Url, publishing: bool) -> Resul
             .map_err(|_| format!("url is not a file path: {}", url).into())
             .and_then(|path| index_from_path(&path)),
         "git" => index_from_git(config, scheme, url, publishing),
-        scheme => Err(format!("bad scheme: {}", scheme).into()),
+        scheme => match fallback(config, scheme, url)? {
+            Some(index) => Ok(index),
+            None => Err(format!("bad scheme: {}", scheme).into()),
+        },
     }
     .chain_err(|| format!("loading index from URL: {}", url))
 }
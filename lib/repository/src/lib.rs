@@ -13,7 +13,7 @@ pub use self::checksum::Checksum;
 pub use self::git::GitRepo;
 pub use self::hex_slice::HexSlice;
 pub use self::index::{
-    index_from_path, index_from_url, init_file_index, Index, IndexConfig, NoIndex,
+    index_from_path, index_from_url, init_file_index, Deployment, Index, IndexConfig, NoIndex,
 };
 pub use self::objects::{
     objects_from_path, objects_from_url, CachedObjects, FileObjects, NoObjects, Objects,
@@ -55,6 +55,12 @@ pub enum Violation {
     EndpointRequestChange(Component, Option<RpChannel>, Span, Option<RpChannel>, Span),
     /// Endpoint response type changed.
     EndpointResponseChange(Component, Option<RpChannel>, Span, Option<RpChannel>, Span),
+    /// Type parameters of a generic declaration changed.
+    TypeParametersChange(Component, Vec<String>, Span, Vec<String>, Span),
+    /// A deprecated declaration, field, variant, or endpoint was removed.
+    ///
+    /// Deprecated items may only be removed in a major version.
+    RemoveDeprecated(Component, &'static str, Span),
 }
 
 fn fields<'a>(named: &RpNamed<'a>) -> Vec<&'a Spanned<RpField>> {
@@ -67,6 +73,39 @@ fn fields<'a>(named: &RpNamed<'a>) -> Vec<&'a Spanned<RpField>> {
     }
 }
 
+fn type_parameters(named: &RpNamed) -> Vec<String> {
+    let type_parameters = match *named {
+        RpNamed::Type(target) => &target.type_parameters,
+        RpNamed::Tuple(target) => &target.type_parameters,
+        RpNamed::Interface(target) => &target.type_parameters,
+        _ => return vec![],
+    };
+
+    type_parameters.iter().map(|p| p.to_string()).collect()
+}
+
+/// Check that the type parameters of a generic declaration are unchanged, since every use of it
+/// depends on them.
+fn check_type_parameters(
+    component: Component,
+    violations: &mut Vec<Violation>,
+    from_named: &RpNamed,
+    to_named: &RpNamed,
+) {
+    let from_parameters = type_parameters(from_named);
+    let to_parameters = type_parameters(to_named);
+
+    if from_parameters != to_parameters {
+        violations.push(TypeParametersChange(
+            component,
+            from_parameters,
+            from_named.span(),
+            to_parameters,
+            to_named.span(),
+        ));
+    }
+}
+
 fn enum_variants<'a>(named: &'a RpNamed) -> Vec<RpVariantRef<'a>> {
     match *named {
         RpNamed::Enum(target) => target.variants.iter().collect(),
@@ -263,12 +302,16 @@ fn check_minor(from: &RpFile, to: &RpFile) -> Result<Vec<Violation>> {
 
     for (name, from_named) in from_storage {
         if let Some(to_named) = to_storage.remove(&name) {
+            check_type_parameters(Minor, &mut violations, &from_named, &to_named);
+
             let from_fields = fields_to_map(fields(&from_named));
             let mut to_fields = fields_to_map(fields(&to_named));
 
             for (name, from_field) in from_fields.into_iter() {
                 if let Some(to_field) = to_fields.remove(&name) {
                     check_field(&mut violations, from_field, to_field)?;
+                } else if from_field.deprecated.is_some() {
+                    violations.push(RemoveDeprecated(Minor, "field", from_field.span()));
                 } else {
                     violations.push(RemoveField(Minor, from_field.span().into()));
                 }
@@ -287,6 +330,8 @@ fn check_minor(from: &RpFile, to: &RpFile) -> Result<Vec<Violation>> {
             for (name, from_variant) in from_variants.into_iter() {
                 if let Some(to_variant) = to_variants.remove(&name) {
                     check_variant(&mut violations, from_variant, to_variant)?;
+                } else if from_variant.deprecated.is_some() {
+                    violations.push(RemoveDeprecated(Minor, "variant", from_variant.span));
                 } else {
                     violations.push(RemoveVariant(Minor, from_variant.span.into()));
                 }
@@ -298,10 +343,14 @@ fn check_minor(from: &RpFile, to: &RpFile) -> Result<Vec<Violation>> {
             for (name, from_endpoint) in from_endpoints.into_iter() {
                 if let Some(to_endpoint) = to_endpoints.remove(&name) {
                     check_endpoint(&mut violations, from_endpoint, to_endpoint)?;
+                } else if from_endpoint.deprecated.is_some() {
+                    violations.push(RemoveDeprecated(Minor, "endpoint", from_endpoint.span()));
                 } else {
                     violations.push(RemoveEndpoint(Minor, from_endpoint.span().into()));
                 }
             }
+        } else if from_named.deprecated().is_some() {
+            violations.push(RemoveDeprecated(Minor, "declaration", from_named.span()));
         } else {
             violations.push(DeclRemoved(Minor, from_named.span().into()));
         }
@@ -355,12 +404,16 @@ fn check_patch(from: &RpFile, to: &RpFile) -> Result<Vec<Violation>> {
 
     for (name, from_named) in from_storage {
         if let Some(to_named) = to_storage.remove(&name) {
+            check_type_parameters(Patch, &mut violations, &from_named, &to_named);
+
             let from_fields = fields_to_map(fields(&from_named));
             let mut to_fields = fields_to_map(fields(&to_named));
 
             for (name, from_field) in from_fields.into_iter() {
                 if let Some(to_field) = to_fields.remove(&name) {
                     check_field(&mut violations, from_field, to_field)?;
+                } else if from_field.deprecated.is_some() {
+                    violations.push(RemoveDeprecated(Patch, "field", from_field.span()));
                 } else {
                     violations.push(RemoveField(Patch, from_field.span().into()));
                 }
@@ -377,6 +430,8 @@ fn check_patch(from: &RpFile, to: &RpFile) -> Result<Vec<Violation>> {
             for (name, from_variant) in from_variants.into_iter() {
                 if let Some(to_variant) = to_variants.remove(&name) {
                     check_variant(&mut violations, from_variant, to_variant)?;
+                } else if from_variant.deprecated.is_some() {
+                    violations.push(RemoveDeprecated(Patch, "variant", from_variant.span));
                 } else {
                     violations.push(RemoveVariant(Patch, from_variant.span.into()));
                 }
@@ -393,6 +448,8 @@ fn check_patch(from: &RpFile, to: &RpFile) -> Result<Vec<Violation>> {
             for (name, from_endpoint) in from_endpoints.into_iter() {
                 if let Some(to_endpoint) = to_endpoints.remove(&name) {
                     check_endpoint(&mut violations, from_endpoint, to_endpoint)?;
+                } else if from_endpoint.deprecated.is_some() {
+                    violations.push(RemoveDeprecated(Patch, "endpoint", from_endpoint.span()));
                 } else {
                     violations.push(RemoveEndpoint(Patch, from_endpoint.span().into()));
                 }
@@ -402,6 +459,8 @@ fn check_patch(from: &RpFile, to: &RpFile) -> Result<Vec<Violation>> {
             for (_, to_endpoint) in to_endpoints.into_iter() {
                 violations.push(AddEndpoint(Patch, to_endpoint.span().into()));
             }
+        } else if from_named.deprecated().is_some() {
+            violations.push(RemoveDeprecated(Patch, "declaration", from_named.span()));
         } else {
             violations.push(DeclRemoved(Patch, from_named.span().into()));
         }
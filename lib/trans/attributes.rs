@@ -5,7 +5,10 @@ use crate::into_model::IntoModel;
 use crate::scope::Scope;
 use reproto_core::errors::Error;
 use reproto_core::flavored::*;
-use reproto_core::{Diagnostics, Import, RpStringValidate, Span, Spanned, Version, WithSpan};
+use reproto_core::{
+    Diagnostics, Import, RpNumber, RpNumberType, RpNumberValidate, RpStringValidate, Span, Spanned,
+    Version, WithSpan,
+};
 use std::collections::HashMap;
 
 /// `#![feature(..)]` attributes.
@@ -115,6 +118,44 @@ pub fn reserved(
     Ok(reserved)
 }
 
+/// `#[deprecated]` or `#[deprecated(note = "..", since = "..")]` attribute.
+pub fn deprecated(
+    diag: &mut Diagnostics,
+    attributes: &mut Attributes,
+) -> Result<Option<RpDeprecated>, ()> {
+    if attributes.take_word("deprecated") {
+        return Ok(Some(RpDeprecated::default()));
+    }
+
+    let mut selection = match attributes.take_selection("deprecated") {
+        Some(selection) => selection,
+        None => return Ok(None),
+    };
+
+    let mut deprecated = RpDeprecated::default();
+
+    if let Some(note) = selection.take("note") {
+        let (note, span) = Spanned::take_pair(note);
+        let note = note.as_string().with_span(diag, span)?;
+        deprecated.note = Some(note.to_string());
+    }
+
+    if let Some(since) = selection.take("since") {
+        let (since, span) = Spanned::take_pair(since);
+
+        let v = since
+            .as_string()
+            .map_err(|_| Error::from("expected string"))
+            .and_then(|v| Version::parse(v).map_err(|e| format!("bad version: {}", e).into()))
+            .with_span(diag, span)?;
+
+        deprecated.since = Some(v);
+    }
+
+    check_selection!(diag, selection);
+    Ok(Some(deprecated))
+}
+
 /// `#[http(..)]` attribute for endpoints.
 pub fn endpoint_http<I>(
     diag: &mut Diagnostics,
@@ -346,7 +387,62 @@ pub fn string_format(
     Ok(Some(Spanned::new(format, attribute_span)))
 }
 
-/// `#[validate(pattern = "[a-z]+")]` attributes on string fields.
+/// `#[validate(min = 0, max = 100)]` attributes on numeric fields.
+///
+/// If `number` is specified, the bounds are checked so that they fit the given number type.
+/// Otherwise they are treated as floating point numbers.
+pub fn number_validate(
+    diag: &mut Diagnostics,
+    attributes: &mut Attributes,
+    number: Option<&RpNumberType>,
+) -> Result<RpNumberValidate, ()> {
+    let mut out = RpNumberValidate::default();
+
+    let mut validate = match attributes.take_selection("validate") {
+        Some(validate) => validate,
+        None => return Ok(out),
+    };
+
+    if let Some(min) = validate.take("min") {
+        out.min = Some(bound(diag, number, min)?);
+    }
+
+    if let Some(max) = validate.take("max") {
+        out.max = Some(bound(diag, number, max)?);
+    }
+
+    if let (Some(min), Some(max)) = (out.min.as_ref(), out.max.as_ref()) {
+        if min.to_f64() > max.to_f64() {
+            diag.err(validate.span(), "`min` must not be greater than `max`");
+            return Err(());
+        }
+    }
+
+    check_selection!(diag, validate);
+    return Ok(out);
+
+    /// Parse a numeric bound.
+    fn bound(
+        diag: &mut Diagnostics,
+        number: Option<&RpNumberType>,
+        bound: Spanned<RpValue>,
+    ) -> Result<RpNumber, ()> {
+        let (bound, span) = Spanned::take_pair(bound);
+
+        let bound = bound
+            .as_number()
+            .map_err(|_| Error::from("expected number"))
+            .and_then(|n| match number {
+                Some(number) => number.validate_number(n).map(|_| n.clone()),
+                None => Ok(n.clone()),
+            })
+            .with_span(diag, span)?;
+
+        Ok(bound)
+    }
+}
+
+/// `#[validate(pattern = "[a-z]+", min_length = 1, max_length = 64)]` attributes on string fields.
 pub fn string_validate(
     diag: &mut Diagnostics,
     attributes: &mut Attributes,
@@ -373,6 +469,40 @@ pub fn string_validate(
         out.pattern = Some(regex);
     }
 
+    if let Some(min_length) = validate.take("min_length") {
+        out.min_length = Some(length(diag, min_length)?);
+    }
+
+    if let Some(max_length) = validate.take("max_length") {
+        out.max_length = Some(length(diag, max_length)?);
+    }
+
+    if let (Some(min_length), Some(max_length)) = (out.min_length, out.max_length) {
+        if min_length > max_length {
+            diag.err(
+                validate.span(),
+                "`min_length` must not be greater than `max_length`",
+            );
+            return Err(());
+        }
+    }
+
     check_selection!(diag, validate);
-    Ok(out)
+    return Ok(out);
+
+    /// Parse a length constraint.
+    fn length(diag: &mut Diagnostics, length: Spanned<RpValue>) -> Result<usize, ()> {
+        let (length, span) = Spanned::take_pair(length);
+
+        let length = length
+            .as_number()
+            .map_err(|_| Error::from("expected number"))
+            .and_then(|n| {
+                n.to_usize()
+                    .ok_or_else(|| "expected positive whole number".into())
+            })
+            .with_span(diag, span)?;
+
+        Ok(length)
+    }
 }
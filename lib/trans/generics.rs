@@ -0,0 +1,460 @@
+//! Instantiation of generic declarations.
+//!
+//! Generic declarations are monomorphized. Every distinct set of type arguments that a generic
+//! declaration is used with results in a concrete declaration, which takes the place of the
+//! generic declaration it was instantiated from. Backends therefore only ever see concrete
+//! declarations.
+
+use reproto_core::flavored::*;
+use reproto_core::{Diagnostics, RpNumberKind, RpVersionedPackage, Spanned};
+use std::collections::{HashMap, VecDeque};
+use std::result;
+
+/// All error information is propagated to the diagnostics argument, but we signal that an error
+/// occurred by returning Err(()).
+pub type Result<T> = result::Result<T, ()>;
+
+/// Maximum number of instances.
+///
+/// Protects against generic declarations which instantiate themselves with ever-growing type
+/// arguments, like `type Nested<T> { next: Nested<[T]>; }`.
+const MAX_INSTANCES: usize = 1024;
+
+/// An instance of a generic declaration which has not been built yet.
+pub struct Pending {
+    /// Name of the generic declaration.
+    pub template: RpName,
+    /// Name of the instance.
+    pub name: RpName,
+    /// Type arguments of the instance.
+    pub arguments: Vec<RpType>,
+}
+
+#[derive(Default)]
+pub struct Generics {
+    /// Generic declarations, by name.
+    templates: HashMap<RpName, RpDecl>,
+    /// Type arguments of every instance, by the name of the instance.
+    instances: HashMap<RpName, Vec<RpType>>,
+    /// Instances which have not been built yet.
+    pending: VecDeque<Pending>,
+}
+
+impl Generics {
+    /// Register a generic declaration.
+    pub fn template(&mut self, decl: RpDecl) {
+        let name = Spanned::borrow(decl.name()).clone().without_prefix();
+        self.templates.insert(name, decl);
+    }
+
+    /// Take the next instance which needs to be built.
+    pub fn next_pending(&mut self) -> Option<Pending> {
+        self.pending.pop_front()
+    }
+
+    /// Build an instance of a generic declaration, by substituting its type parameters.
+    pub fn build(&self, pending: &Pending) -> RpDecl {
+        let mut decl = self.templates[&pending.template].clone();
+
+        let parameters = decl
+            .type_parameters()
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>();
+
+        for ty in types_mut(&mut decl) {
+            substitute(ty, &parameters, &pending.arguments);
+            relocate(ty, &pending.template.package);
+        }
+
+        rename(&mut decl, &pending.name);
+        decl
+    }
+
+    /// Replace all uses of generic declarations with the instances they refer to.
+    pub fn decl(&mut self, diag: &mut Diagnostics, decl: &mut RpDecl) -> Result<()> {
+        for ty in types_mut(decl) {
+            // NB: errors are collected in diagnostics.
+            let _ = self.ty(diag, ty);
+        }
+
+        if diag.has_errors() {
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+    fn ty(&mut self, diag: &mut Diagnostics, ty: &mut RpType) -> Result<()> {
+        let (name, arguments) = match *ty {
+            RpType::Array { ref mut inner } => return self.ty(diag, inner),
+            RpType::Map {
+                ref mut key,
+                ref mut value,
+            } => {
+                self.ty(diag, key)?;
+                return self.ty(diag, value);
+            }
+            RpType::Name { ref name } => {
+                let key = Spanned::borrow(name).clone().without_prefix();
+
+                if let Some(template) = self.templates.get(&key) {
+                    diag.err(
+                        name.span(),
+                        format!(
+                            "`{}` is generic and expects {} type argument(s)",
+                            name,
+                            template.type_parameters().len()
+                        ),
+                    );
+
+                    return Err(());
+                }
+
+                return Ok(());
+            }
+            RpType::Generic {
+                ref name,
+                ref mut arguments,
+            } => {
+                for argument in arguments.iter_mut() {
+                    self.ty(diag, argument)?;
+                }
+
+                (name.clone(), arguments.clone())
+            }
+            _ => return Ok(()),
+        };
+
+        let name = self.instance(diag, name, arguments)?;
+        *ty = RpType::Name { name };
+        Ok(())
+    }
+
+    /// Get the name of the instance of a generic declaration with the given type arguments,
+    /// queueing it up to be built if necessary.
+    fn instance(
+        &mut self,
+        diag: &mut Diagnostics,
+        name: Spanned<RpName>,
+        arguments: Vec<RpType>,
+    ) -> Result<Spanned<RpName>> {
+        let template = Spanned::borrow(&name).clone().without_prefix();
+
+        let parameters = match self.templates.get(&template) {
+            Some(decl) => decl.type_parameters().len(),
+            None => {
+                diag.err(
+                    name.span(),
+                    format!("`{}` is not a generic declaration", name),
+                );
+                return Err(());
+            }
+        };
+
+        if parameters != arguments.len() {
+            diag.err(
+                name.span(),
+                format!(
+                    "expected {} type argument(s), but got {}",
+                    parameters,
+                    arguments.len()
+                ),
+            );
+            return Err(());
+        }
+
+        let mut path = template.path.clone();
+
+        if let Some(ident) = path.last_mut() {
+            for argument in &arguments {
+                ident.push_str(&mangle(argument));
+            }
+        }
+
+        let instance = template.clone().with_parts(path.clone());
+
+        match self.instances.get(&instance) {
+            Some(existing) if *existing != arguments => {
+                diag.err(
+                    name.span(),
+                    format!(
+                        "instance `{}` conflicts with another instance of `{}`",
+                        instance, template
+                    ),
+                );
+                return Err(());
+            }
+            Some(_) => {}
+            None => {
+                if self.instances.len() >= MAX_INSTANCES {
+                    diag.err(name.span(), "too many instances of generic declarations");
+                    return Err(());
+                }
+
+                self.instances.insert(instance.clone(), arguments.clone());
+
+                self.pending.push_back(Pending {
+                    template,
+                    name: instance,
+                    arguments,
+                });
+            }
+        }
+
+        // NB: keep the prefix that the generic declaration was referenced through.
+        Ok(Spanned::map(name, |name| name.with_parts(path)))
+    }
+}
+
+/// Build the part of an instance name that corresponds to the given type argument.
+fn mangle(ty: &RpType) -> String {
+    match *ty {
+        RpType::Double => "Double".to_string(),
+        RpType::Float => "Float".to_string(),
+        RpType::Number(ref number) => match number.kind {
+            RpNumberKind::U32 => "U32".to_string(),
+            RpNumberKind::U64 => "U64".to_string(),
+            RpNumberKind::I32 => "I32".to_string(),
+            RpNumberKind::I64 => "I64".to_string(),
+        },
+        RpType::Boolean => "Boolean".to_string(),
+        RpType::String(..) => "String".to_string(),
+        RpType::DateTime => "DateTime".to_string(),
+        RpType::Bytes => "Bytes".to_string(),
+        RpType::Any => "Any".to_string(),
+        RpType::Name { ref name } | RpType::Generic { ref name, .. } => name.join(""),
+        RpType::Parameter { ref name } => name.to_string(),
+        RpType::Array { ref inner } => format!("{}Array", mangle(inner)),
+        RpType::Map { ref key, ref value } => format!("{}{}Map", mangle(key), mangle(value)),
+    }
+}
+
+/// Substitute type parameters with the given type arguments.
+fn substitute(ty: &mut RpType, parameters: &[String], arguments: &[RpType]) {
+    let argument = match *ty {
+        RpType::Parameter { ref name } => match parameters.iter().position(|p| *p == **name) {
+            Some(index) => arguments[index].clone(),
+            None => return,
+        },
+        RpType::Array { ref mut inner } => return substitute(inner, parameters, arguments),
+        RpType::Map {
+            ref mut key,
+            ref mut value,
+        } => {
+            substitute(key, parameters, arguments);
+            return substitute(value, parameters, arguments);
+        }
+        RpType::Generic {
+            arguments: ref mut inner,
+            ..
+        } => {
+            for ty in inner.iter_mut() {
+                substitute(ty, parameters, arguments);
+            }
+
+            return;
+        }
+        _ => return,
+    };
+
+    *ty = argument;
+}
+
+/// Make names usable from the package of an instance.
+///
+/// Type arguments come from the package where the generic declaration is used, so names from
+/// other packages need a prefix that backends can import them through.
+fn relocate(ty: &mut RpType, package: &RpVersionedPackage) {
+    match *ty {
+        RpType::Name { ref mut name } => relocate_name(name, package),
+        RpType::Generic {
+            ref mut name,
+            ref mut arguments,
+        } => {
+            relocate_name(name, package);
+
+            for argument in arguments {
+                relocate(argument, package);
+            }
+        }
+        RpType::Array { ref mut inner } => relocate(inner, package),
+        RpType::Map {
+            ref mut key,
+            ref mut value,
+        } => {
+            relocate(key, package);
+            relocate(value, package);
+        }
+        _ => {}
+    }
+}
+
+fn relocate_name(name: &mut Spanned<RpName>, package: &RpVersionedPackage) {
+    let span = name.span();
+
+    if name.package == *package {
+        name.prefix = None;
+    } else if name.prefix.is_none() {
+        let prefix = name.package.package.join("_");
+        name.prefix = Some(Spanned::new(prefix, span));
+    }
+}
+
+/// Give a copy of a generic declaration the name of the instance.
+fn rename(decl: &mut RpDecl, name: &RpName) {
+    let ident = name.path.last().cloned().unwrap_or_default();
+
+    match *decl {
+        RpDecl::Type(ref mut body) => {
+            body.name = Spanned::new(name.clone(), body.name.span());
+            body.ident = ident;
+            body.type_parameters.clear();
+        }
+        RpDecl::Tuple(ref mut body) => {
+            body.name = Spanned::new(name.clone(), body.name.span());
+            body.ident = ident;
+            body.type_parameters.clear();
+        }
+        RpDecl::Interface(ref mut body) => {
+            body.name = Spanned::new(name.clone(), body.name.span());
+            body.ident = ident;
+            body.type_parameters.clear();
+
+            for sub_type in &mut body.sub_types {
+                let span = sub_type.name.span();
+                let sub_type_name = name.push(sub_type.ident.clone());
+                sub_type.name = Spanned::new(sub_type_name, span);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect all types used in the given declaration, including in inner declarations.
+fn types_mut(decl: &mut RpDecl) -> Vec<&mut RpType> {
+    let mut out = Vec::new();
+    decl_types(&mut out, decl);
+    out
+}
+
+fn decl_types<'a>(out: &mut Vec<&'a mut RpType>, decl: &'a mut RpDecl) {
+    match *decl {
+        RpDecl::Type(ref mut body) => {
+            let RpTypeBody {
+                ref mut fields,
+                ref mut decls,
+                ..
+            } = **body;
+
+            out.extend(fields.iter_mut().map(|f| &mut f.ty));
+            decls.iter_mut().for_each(|d| decl_types(out, d));
+        }
+        RpDecl::Tuple(ref mut body) => {
+            let RpTupleBody {
+                ref mut fields,
+                ref mut decls,
+                ..
+            } = **body;
+
+            out.extend(fields.iter_mut().map(|f| &mut f.ty));
+            decls.iter_mut().for_each(|d| decl_types(out, d));
+        }
+        RpDecl::Interface(ref mut body) => {
+            let RpInterfaceBody {
+                ref mut fields,
+                ref mut decls,
+                ref mut sub_types,
+                ..
+            } = **body;
+
+            out.extend(fields.iter_mut().map(|f| &mut f.ty));
+            decls.iter_mut().for_each(|d| decl_types(out, d));
+
+            for sub_type in sub_types {
+                let RpSubType {
+                    ref mut fields,
+                    ref mut decls,
+                    ..
+                } = **sub_type;
+
+                out.extend(fields.iter_mut().map(|f| &mut f.ty));
+                decls.iter_mut().for_each(|d| decl_types(out, d));
+            }
+        }
+        RpDecl::Enum(ref mut body) => {
+            body.decls.iter_mut().for_each(|d| decl_types(out, d));
+        }
+        RpDecl::Service(ref mut body) => {
+            let RpServiceBody {
+                ref mut endpoints,
+                ref mut decls,
+                ..
+            } = **body;
+
+            for endpoint in endpoints {
+                endpoint_types(out, endpoint);
+            }
+
+            decls.iter_mut().for_each(|d| decl_types(out, d));
+        }
+        RpDecl::Const(ref mut body) => {
+            let RpConstBody {
+                ref mut ty,
+                ref mut decls,
+                ..
+            } = **body;
+
+            out.push(ty);
+            decls.iter_mut().for_each(|d| decl_types(out, d));
+        }
+        RpDecl::Alias(ref mut body) => {
+            let RpAliasBody {
+                ref mut ty,
+                ref mut decls,
+                ..
+            } = **body;
+
+            out.push(ty);
+            decls.iter_mut().for_each(|d| decl_types(out, d));
+        }
+    }
+}
+
+fn endpoint_types<'a>(out: &mut Vec<&'a mut RpType>, endpoint: &'a mut RpEndpoint) {
+    let RpEndpoint {
+        ref mut arguments,
+        ref mut request,
+        ref mut response,
+        ref mut http,
+        ..
+    } = *endpoint;
+
+    for argument in arguments.iter_mut().chain(request.iter_mut()) {
+        out.push(channel_type(&mut argument.channel));
+    }
+
+    if let Some(response) = response.as_mut() {
+        out.push(channel_type(response));
+    }
+
+    if let Some(body) = http.body.as_mut() {
+        out.push(channel_type(&mut body.channel));
+    }
+
+    if let Some(path) = http.path.as_mut() {
+        for step in &mut path.steps {
+            for part in &mut step.parts {
+                if let RpPathPart::Variable(ref mut argument) = *part {
+                    out.push(channel_type(&mut argument.channel));
+                }
+            }
+        }
+    }
+}
+
+fn channel_type(channel: &mut RpChannel) -> &mut RpType {
+    match *channel {
+        RpChannel::Unary { ref mut ty } | RpChannel::Streaming { ref mut ty } => ty,
+    }
+}
@@ -8,8 +8,9 @@ use naming::Naming;
 use reproto_core::errors::Error;
 use reproto_core::flavored::*;
 use reproto_core::{
-    BigInt, Diagnostics, Import, Range, RpEnabledFeature, RpNumberKind, RpNumberType,
-    RpNumberValidate, RpStringType, RpStringValidate, Span, Spanned, SymbolKind, WithSpan,
+    BigInt, Diagnostics, Import, Range, RpEnabledFeature, RpLiteral, RpNumberKind, RpNumberType,
+    RpNumberValidate, RpStringType, RpStringValidate, RpValidate, Span, Spanned, SymbolKind,
+    WithSpan,
 };
 use std::borrow::Cow;
 use std::collections::{hash_map, BTreeSet, HashMap};
@@ -275,22 +276,10 @@ impl<'input> IntoModel for (Option<&'input mut Attributes>, Spanned<Type<'input>
         let out = match ty {
             Double => RpType::Double,
             Float => RpType::Float,
-            Unsigned { size: 32 } => RpType::Number(RpNumberType {
-                kind: RpNumberKind::U32,
-                validate: RpNumberValidate::default(),
-            }),
-            Unsigned { size: 64 } => RpType::Number(RpNumberType {
-                kind: RpNumberKind::U64,
-                validate: RpNumberValidate::default(),
-            }),
-            Signed { size: 32 } => RpType::Number(RpNumberType {
-                kind: RpNumberKind::I32,
-                validate: RpNumberValidate::default(),
-            }),
-            Signed { size: 64 } => RpType::Number(RpNumberType {
-                kind: RpNumberKind::I64,
-                validate: RpNumberValidate::default(),
-            }),
+            Unsigned { size: 32 } => number(diag, attributes, RpNumberKind::U32)?,
+            Unsigned { size: 64 } => number(diag, attributes, RpNumberKind::U64)?,
+            Signed { size: 32 } => number(diag, attributes, RpNumberKind::I32)?,
+            Signed { size: 64 } => number(diag, attributes, RpNumberKind::I64)?,
             Boolean => RpType::Boolean,
             String => {
                 let validate = match attributes {
@@ -301,9 +290,31 @@ impl<'input> IntoModel for (Option<&'input mut Attributes>, Spanned<Type<'input>
                 RpType::String(RpStringType { validate })
             }
             DateTime => RpType::DateTime,
-            Name { name } => RpType::Name {
-                name: name.into_model(diag, scope)?,
-            },
+            Name { name, arguments } => {
+                if let Some(parameter) = type_parameter(&name, scope) {
+                    let parameter = parameter.to_string();
+
+                    if let Some(argument) = arguments.first() {
+                        diag.err(argument.span(), "type parameters can't have type arguments");
+                        return Err(());
+                    }
+
+                    return Ok(RpType::Parameter {
+                        name: Spanned::new(parameter, span),
+                    });
+                }
+
+                let name = name.into_model(diag, scope)?;
+
+                if arguments.is_empty() {
+                    RpType::Name { name }
+                } else {
+                    RpType::Generic {
+                        name,
+                        arguments: arguments.into_model(diag, scope)?,
+                    }
+                }
+            }
             Array { inner } => RpType::Array {
                 inner: inner.into_model(diag, scope)?,
             },
@@ -323,8 +334,147 @@ impl<'input> IntoModel for (Option<&'input mut Attributes>, Spanned<Type<'input>
             }
         };
 
-        Ok(out)
+        return Ok(out);
+
+        /// Test if the given name refers to a type parameter in scope.
+        fn type_parameter<'a, I>(name: &'a ast::Name, scope: &Scope<I>) -> Option<&'a str> {
+            if let ast::Name::Absolute {
+                prefix: None,
+                ref path,
+            } = *name
+            {
+                if let [ref ident] = path[..] {
+                    if scope.is_type_parameter(ident) {
+                        return Some(ident);
+                    }
+                }
+            }
+
+            None
+        }
+
+        fn number(
+            diag: &mut Diagnostics,
+            attributes: Option<&mut Attributes>,
+            kind: RpNumberKind,
+        ) -> Result<RpType> {
+            let mut number = RpNumberType {
+                kind,
+                validate: RpNumberValidate::default(),
+            };
+
+            if let Some(attributes) = attributes {
+                number.validate = attributes::number_validate(diag, attributes, Some(&number))?;
+            }
+
+            Ok(RpType::Number(number))
+        }
+    }
+}
+
+/// Build the validation rules for values of the given type.
+fn validate(
+    diag: &mut Diagnostics,
+    attributes: &mut Attributes,
+    ty: &RpType,
+) -> Result<Option<RpValidate>> {
+    // NB: floating point types carry no validation of their own, so it's attached to the item.
+    let validate = match *ty {
+        RpType::Float | RpType::Double => {
+            let validate = attributes::number_validate(diag, attributes, None)?;
+
+            if validate.is_empty() {
+                None
+            } else {
+                Some(RpValidate::Number(validate))
+            }
+        }
+        ref ty => ty.validate(),
+    };
+
+    Ok(validate)
+}
+
+/// Convert a literal value, checking it against the type it is used with.
+fn literal(ty: &RpType, value: RpValue) -> result::Result<RpLiteral, Error> {
+    let literal = match *ty {
+        RpType::Number(ref number_type) => {
+            let number = value.into_number()?;
+            number_type.validate_number(&number)?;
+            RpLiteral::Number(number)
+        }
+        RpType::Float | RpType::Double => RpLiteral::Number(value.into_number()?),
+        RpType::String(..) => RpLiteral::String(value.into_string()?),
+        RpType::Boolean => match value {
+            RpValue::Identifier(ref identifier) if identifier == "true" => RpLiteral::Boolean(true),
+            RpValue::Identifier(ref identifier) if identifier == "false" => {
+                RpLiteral::Boolean(false)
+            }
+            _ => return Err("expected `true` or `false`".into()),
+        },
+        ref ty => {
+            return Err(format!("literal values are not supported for `{}`", ty).into());
+        }
+    };
+
+    Ok(literal)
+}
+
+/// Convert the type parameters of a declaration.
+fn type_parameters<I>(
+    diag: &mut Diagnostics,
+    scope: &Scope<I>,
+    parameters: Vec<Spanned<Cow<str>>>,
+) -> Result<Vec<Spanned<String>>> {
+    let mut out = Vec::new();
+    let mut existing = HashMap::new();
+
+    for parameter in parameters {
+        let parameter = Spanned::map(parameter, |p| p.to_string());
+
+        // NB: instances are added next to the declaration they are instantiated from.
+        if scope.is_nested() {
+            diag.err(
+                parameter.span(),
+                "generic declarations must be declared at the top level",
+            );
+            return Err(());
+        }
+
+        check_conflict!(diag, existing, parameter, *parameter, "type parameter");
+        out.push(parameter);
+    }
+
+    if diag.has_errors() {
+        return Err(());
+    }
+
+    Ok(out)
+}
+
+/// Check that a generic declaration doesn't have inner declarations, since every instance would
+/// define them again.
+fn check_generic_decls(
+    diag: &mut Diagnostics,
+    type_parameters: &[Spanned<String>],
+    decls: &[RpDecl],
+) -> Result<()> {
+    if type_parameters.is_empty() {
+        return Ok(());
     }
+
+    for decl in decls {
+        diag.err(
+            decl,
+            "inner declarations are not supported in generic declarations",
+        );
+    }
+
+    if diag.has_errors() {
+        return Err(());
+    }
+
+    Ok(())
 }
 
 impl<'input> IntoModel for Decl<'input> {
@@ -344,6 +494,8 @@ impl<'input> IntoModel for Decl<'input> {
             Enum(body) => body.into_model(diag, scope).map(RpDecl::Enum),
             Tuple(body) => body.into_model(diag, scope).map(RpDecl::Tuple),
             Service(body) => body.into_model(diag, scope).map(RpDecl::Service),
+            Const(body) => body.into_model(diag, scope).map(RpDecl::Const),
+            Alias(body) => body.into_model(diag, scope).map(RpDecl::Alias),
         };
 
         scope.pop();
@@ -352,6 +504,99 @@ impl<'input> IntoModel for Decl<'input> {
     }
 }
 
+impl<'input> IntoModel for Item<'input, AliasBody<'input>> {
+    type Output = Spanned<RpAliasBody>;
+
+    fn into_model<I>(self, diag: &mut Diagnostics, scope: &mut Scope<I>) -> Result<Self::Output>
+    where
+        I: Import,
+    {
+        let Item {
+            comment,
+            attributes,
+            item,
+        } = self;
+
+        let (item, span) = Spanned::take_pair(item);
+
+        let name = scope.as_name(item.name.span());
+
+        diag.symbol(SymbolKind::Alias, span, &name);
+
+        let mut attributes = attributes.into_model(diag, scope)?;
+
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
+        let ty = (Some(&mut attributes), item.ty).into_model(diag, scope)?;
+        let validate = validate(diag, &mut attributes, &ty)?;
+
+        check_attributes!(diag, attributes);
+
+        Ok(Spanned::new(
+            RpAliasBody {
+                name,
+                ident: item.name.to_string(),
+                comment: Comment(&comment).into_model(diag, scope)?,
+                decls: Vec::new(),
+                decl_idents: LinkedHashMap::new(),
+                deprecated,
+                ty,
+                validate,
+            },
+            span,
+        ))
+    }
+}
+
+impl<'input> IntoModel for Item<'input, ConstBody<'input>> {
+    type Output = Spanned<RpConstBody>;
+
+    fn into_model<I>(self, diag: &mut Diagnostics, scope: &mut Scope<I>) -> Result<Self::Output>
+    where
+        I: Import,
+    {
+        let Item {
+            comment,
+            attributes,
+            item,
+        } = self;
+
+        let (item, span) = Spanned::take_pair(item);
+
+        let name = scope.as_name(item.name.span());
+
+        diag.symbol(SymbolKind::Const, span, &name);
+
+        let mut attributes = attributes.into_model(diag, scope)?;
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
+        check_attributes!(diag, attributes);
+
+        let ty = (None, item.ty).into_model(diag, scope)?;
+        let (value, value_span) = Spanned::take_pair(item.value.into_model(diag, scope)?);
+
+        let value = match literal(&ty, value) {
+            Err(e) => {
+                diag.err(value_span, e.display());
+                return Err(());
+            }
+            Ok(value) => value,
+        };
+
+        Ok(Spanned::new(
+            RpConstBody {
+                name,
+                ident: item.name.to_string(),
+                comment: Comment(&comment).into_model(diag, scope)?,
+                decls: Vec::new(),
+                decl_idents: LinkedHashMap::new(),
+                deprecated,
+                ty,
+                value,
+            },
+            span,
+        ))
+    }
+}
+
 impl<'input> IntoModel for Item<'input, EnumBody<'input>> {
     type Output = Spanned<RpEnumBody>;
 
@@ -440,7 +685,8 @@ impl<'input> IntoModel for Item<'input, EnumBody<'input>> {
             (Number, Number, NumberDefaultVariant)
         );
 
-        let attributes = attributes.into_model(diag, scope)?;
+        let mut attributes = attributes.into_model(diag, scope)?;
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
         check_attributes!(diag, attributes);
 
         return Ok(Spanned::new(
@@ -450,6 +696,7 @@ impl<'input> IntoModel for Item<'input, EnumBody<'input>> {
                 comment: Comment(&comment).into_model(diag, scope)?,
                 decls: vec![],
                 decl_idents: LinkedHashMap::new(),
+                deprecated,
                 enum_type: enum_type,
                 variants: variants,
                 codes: codes,
@@ -576,7 +823,8 @@ where
             default.next(&item).with_span(diag, span)?
         };
 
-        let attributes = attributes.into_model(diag, scope)?;
+        let mut attributes = attributes.into_model(diag, scope)?;
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
         check_attributes!(diag, attributes);
 
         Ok(Spanned::new(
@@ -584,6 +832,7 @@ where
                 name,
                 ident: Spanned::map(item.name.clone(), |s| s.to_string()),
                 comment: Comment(&comment).into_model(diag, scope)?,
+                deprecated,
                 value: value,
             },
             span,
@@ -674,12 +923,36 @@ impl<'input> IntoModel for Item<'input, Field<'input>> {
 
         let mut attributes = attributes.into_model(diag, scope)?;
 
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
+
         let ty = handle_format_attribute(diag, scope, &mut attributes, item.ty)?;
 
         let ty = (Some(&mut attributes), ty).into_model(diag, scope)?;
 
+        let validate = validate(diag, &mut attributes, &ty)?;
+
         check_attributes!(diag, attributes);
 
+        let default = match item.default {
+            Some(default) => {
+                let (value, value_span) = Spanned::take_pair(default.into_model(diag, scope)?);
+
+                if !item.required {
+                    diag.err(value_span, "optional fields can't have a default value");
+                    return Err(());
+                }
+
+                match literal(&ty, value) {
+                    Err(e) => {
+                        diag.err(value_span, e.display());
+                        return Err(());
+                    }
+                    Ok(default) => Some(default),
+                }
+            }
+            None => None,
+        };
+
         return Ok(Spanned::new(
             RpField {
                 required: item.required,
@@ -688,6 +961,9 @@ impl<'input> IntoModel for Item<'input, Field<'input>> {
                 comment: Comment(&comment).into_model(diag, scope)?,
                 ty,
                 field_as,
+                validate,
+                default,
+                deprecated,
             },
             span,
         ));
@@ -976,6 +1252,7 @@ impl<'input> IntoModel for Item<'input, InterfaceBody<'input>> {
         let mut attributes = attributes.into_model(diag, scope)?;
 
         let reserved = attributes::reserved(diag, &mut attributes)?;
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
 
         let mut sub_type_strategy = RpSubTypeStrategy::default();
 
@@ -986,6 +1263,20 @@ impl<'input> IntoModel for Item<'input, InterfaceBody<'input>> {
 
         check_attributes!(diag, attributes);
 
+        let type_parameters = type_parameters(diag, scope, item.type_parameters)?;
+
+        let members = {
+            let constraint = MemberConstraint {
+                sub_type_strategy: Some(&sub_type_strategy),
+                ..MemberConstraint::default()
+            };
+
+            scope.push_type_parameters(&type_parameters);
+            let members = (item.members, constraint).into_model(diag, scope);
+            scope.pop_type_parameters();
+            members
+        };
+
         let Members {
             fields,
             codes,
@@ -994,14 +1285,9 @@ impl<'input> IntoModel for Item<'input, InterfaceBody<'input>> {
             field_idents,
             field_names,
             ..
-        } = {
-            let constraint = MemberConstraint {
-                sub_type_strategy: Some(&sub_type_strategy),
-                ..MemberConstraint::default()
-            };
+        } = members?;
 
-            (item.members, constraint).into_model(diag, scope)?
-        };
+        check_generic_decls(diag, &type_parameters, &decls)?;
 
         let mut names = HashMap::new();
         let mut idents = HashMap::new();
@@ -1018,10 +1304,13 @@ impl<'input> IntoModel for Item<'input, InterfaceBody<'input>> {
             };
 
             scope.push(Spanned::borrow(&sub_type.name));
+            scope.push_type_parameters(&type_parameters);
             let out = (sub_type, constraint).into_model(diag, scope);
+            scope.pop_type_parameters();
             scope.pop();
 
             let sub_type = try_loop!(out);
+            try_loop!(check_generic_decls(diag, &type_parameters, &sub_type.decls));
 
             check_conflict!(diag, idents, sub_type, sub_type.ident, "sub-type");
             check_conflict!(diag, names, sub_type, sub_type.name(), "sub-type with name");
@@ -1093,6 +1382,8 @@ impl<'input> IntoModel for Item<'input, InterfaceBody<'input>> {
                 comment: Comment(&comment).into_model(diag, scope)?,
                 decls,
                 decl_idents,
+                deprecated,
+                type_parameters,
                 fields,
                 codes,
                 sub_types,
@@ -1300,6 +1591,8 @@ impl<'input> IntoModel for Item<'input, ServiceBody<'input>> {
 
         let mut attributes = attributes.into_model(diag, scope)?;
 
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
+
         let mut http = RpServiceBodyHttp::default();
 
         if let Some(selection) = attributes.take_selection("http") {
@@ -1317,6 +1610,7 @@ impl<'input> IntoModel for Item<'input, ServiceBody<'input>> {
                 comment: Comment(&comment).into_model(diag, scope)?,
                 decls,
                 decl_idents,
+                deprecated,
                 http,
                 endpoints,
             },
@@ -1405,6 +1699,8 @@ impl<'input> IntoModel for Item<'input, Endpoint<'input>> {
 
         let mut attributes = attributes.into_model(diag, scope)?;
 
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
+
         let http = attributes::endpoint_http(
             diag,
             scope,
@@ -1422,6 +1718,7 @@ impl<'input> IntoModel for Item<'input, Endpoint<'input>> {
                 safe_ident: safe_ident,
                 name: name,
                 comment: Comment(&comment).into_model(diag, scope)?,
+                deprecated,
                 attributes: attributes,
                 arguments: arguments,
                 request: request,
@@ -1486,6 +1783,7 @@ impl<'input> IntoModel for (Item<'input, SubType<'input>>, SubTypeConstraint<'in
 
         let mut attributes = attributes.into_model(diag, scope)?;
         let reserved = attributes::reserved(diag, &mut attributes)?;
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
         check_attributes!(diag, attributes);
 
         let mut fields = Vec::new();
@@ -1555,6 +1853,7 @@ impl<'input> IntoModel for (Item<'input, SubType<'input>>, SubTypeConstraint<'in
                 comment: Comment(&comment).into_model(diag, scope)?,
                 decls,
                 decl_idents,
+                deprecated,
                 fields,
                 codes,
                 sub_type_name,
@@ -1618,15 +1917,35 @@ impl<'input> IntoModel for Item<'input, TupleBody<'input>> {
 
         diag.symbol(SymbolKind::Tuple, &span, &name);
 
+        let type_parameters = type_parameters(diag, scope, item.type_parameters)?;
+
+        scope.push_type_parameters(&type_parameters);
+        let members = item.members.into_model(diag, scope);
+        scope.pop_type_parameters();
+
         let Members {
             fields,
             codes,
             decls,
             decl_idents,
             ..
-        } = item.members.into_model(diag, scope)?;
+        } = members?;
+
+        check_generic_decls(diag, &type_parameters, &decls)?;
+
+        // NB: tuples are encoded as arrays, so every element must be present.
+        for field in &fields {
+            if field.default.is_some() {
+                diag.err(field.span(), "default values are not supported in tuples");
+            }
+        }
+
+        if diag.has_errors() {
+            return Err(());
+        }
 
-        let attributes = attributes.into_model(diag, scope)?;
+        let mut attributes = attributes.into_model(diag, scope)?;
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
         check_attributes!(diag, attributes);
 
         Ok(Spanned::new(
@@ -1636,6 +1955,8 @@ impl<'input> IntoModel for Item<'input, TupleBody<'input>> {
                 comment: Comment(&comment).into_model(diag, scope)?,
                 decls,
                 decl_idents,
+                deprecated,
+                type_parameters,
                 fields,
                 codes,
             },
@@ -1665,23 +1986,33 @@ impl<'input> IntoModel for Item<'input, TypeBody<'input>> {
 
         let mut attributes = attributes.into_model(diag, scope)?;
         let reserved = attributes::reserved(diag, &mut attributes)?;
+        let deprecated = attributes::deprecated(diag, &mut attributes)?;
 
         check_attributes!(diag, attributes);
 
+        let type_parameters = type_parameters(diag, scope, item.type_parameters)?;
+
+        let members = {
+            let constraint = MemberConstraint {
+                reserved: Some(&reserved),
+                ..MemberConstraint::default()
+            };
+
+            scope.push_type_parameters(&type_parameters);
+            let members = (item.members, constraint).into_model(diag, scope);
+            scope.pop_type_parameters();
+            members
+        };
+
         let Members {
             fields,
             codes,
             decls,
             decl_idents,
             ..
-        } = {
-            let constraint = MemberConstraint {
-                reserved: Some(&reserved),
-                ..MemberConstraint::default()
-            };
+        } = members?;
 
-            (item.members, constraint).into_model(diag, scope)?
-        };
+        check_generic_decls(diag, &type_parameters, &decls)?;
 
         Ok(Spanned::new(
             RpTypeBody {
@@ -1690,6 +2021,8 @@ impl<'input> IntoModel for Item<'input, TypeBody<'input>> {
                 comment: Comment(&comment).into_model(diag, scope)?,
                 decls,
                 decl_idents,
+                deprecated,
+                type_parameters,
                 fields,
                 codes,
             },
@@ -1804,6 +2137,7 @@ impl<'input> IntoModel for Code<'input> {
                 "reproto" => RpContext::Reproto {},
                 "rust" => RpContext::Rust {},
                 "swift" => RpContext::Swift {},
+                "typescript" => RpContext::TypeScript {},
                 context => {
                     diag.err(span, format!("context `{}` not recognized", context));
                     return Err(());
@@ -26,6 +26,7 @@ macro_rules! check_selection {
 
 mod attributes;
 mod features;
+mod generics;
 mod into_model;
 mod scope;
 pub mod session;
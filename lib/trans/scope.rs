@@ -32,6 +32,8 @@ pub struct Scope<I> {
     pub prefixes: HashMap<String, RpVersionedPackage>,
     /// Path of the current scope.
     path: Vec<String>,
+    /// Type parameters of the generic declarations being processed.
+    type_parameters: Vec<Vec<String>>,
 }
 
 impl<I> Scope<I> {
@@ -58,6 +60,7 @@ impl<I> Scope<I> {
             field_naming: None,
             prefixes: HashMap::new(),
             path: vec![],
+            type_parameters: vec![],
         }
     }
 
@@ -78,6 +81,35 @@ impl<I> Scope<I> {
         self.path.pop();
     }
 
+    /// Check if the current scope is nested inside of another declaration.
+    pub fn is_nested(&self) -> bool {
+        self.path.len() > 1
+    }
+
+    /// Bring the type parameters of a generic declaration into scope.
+    pub fn push_type_parameters<'a, P>(&mut self, parameters: P)
+    where
+        P: IntoIterator<Item = &'a Spanned<String>>,
+    {
+        let parameters = parameters.into_iter().map(|p| p.to_string()).collect();
+        self.type_parameters.push(parameters);
+    }
+
+    /// Pop the last set of type parameters.
+    pub fn pop_type_parameters(&mut self) {
+        self.type_parameters.pop();
+    }
+
+    /// Check if the given name refers to a type parameter which is in scope.
+    ///
+    /// Only the parameters of the innermost generic declaration are visible.
+    pub fn is_type_parameter(&self, name: &str) -> bool {
+        match self.type_parameters.last() {
+            Some(parameters) => parameters.iter().any(|p| p == name),
+            None => false,
+        }
+    }
+
     /// Lookup what package a given prefix belongs to.
     pub fn lookup_prefix(&self, prefix: &str) -> Option<RpVersionedPackage> {
         self.prefixes.get(prefix).map(Clone::clone)
@@ -206,7 +238,7 @@ where
 mod tests {
     use crate::features::Features;
     use crate::scope::Scope;
-    use reproto_core::{RpPackage, RpVersionedPackage, Version};
+    use reproto_core::{RpPackage, RpVersionedPackage, Span, Spanned, Version};
     use std::collections::HashMap;
     use std::rc::Rc;
 
@@ -224,4 +256,23 @@ mod tests {
 
         assert_eq!(vec!["foo".to_owned(), "bar".to_owned()], s.path);
     }
+
+    #[test]
+    pub fn test_type_parameters() {
+        let package = RpVersionedPackage::new(RpPackage::empty(), None);
+        let keywords = Rc::new(HashMap::new());
+
+        let version = Rc::new(Version::new(0, 0, 0));
+        let features = Rc::new(Features::new().expect("failed to build features"));
+        let mut s = Scope::new(version, features, package, keywords, None, None, ());
+
+        let parameters = vec![Spanned::new("T".to_string(), Span::empty())];
+
+        assert!(!s.is_type_parameter("T"));
+        s.push_type_parameters(&parameters);
+        assert!(s.is_type_parameter("T"));
+        assert!(!s.is_type_parameter("E"));
+        s.pop_type_parameters();
+        assert!(!s.is_type_parameter("T"));
+    }
 }
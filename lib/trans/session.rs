@@ -1,4 +1,5 @@
 use crate::features::Features;
+use crate::generics::Generics;
 use crate::into_model::IntoModel;
 use crate::scope::Scope;
 use crate::translated::Translated;
@@ -8,8 +9,8 @@ use parser;
 use reproto_core::errors::{Error, Result};
 use reproto_core::{
     translator, CoreFlavor, Diagnostics, Flavor, FlavorTranslator, Import, PackageTranslator,
-    Reporter, Resolved, Resolver, RpFile, RpName, RpPackage, RpReg, RpRequiredPackage,
-    RpVersionedPackage, Source, Spanned, Translate, Version,
+    Reporter, Resolved, Resolver, RpDecl, RpFile, RpName, RpNamed, RpPackage, RpReg,
+    RpRequiredPackage, RpType, RpVersionedPackage, Source, Span, Spanned, Translate, Version,
 };
 use std::cell::RefCell;
 use std::collections::{btree_map, BTreeMap, HashMap, HashSet};
@@ -73,6 +74,8 @@ where
     keywords: Rc<HashMap<String, String>>,
     /// Whether to use safe packages or not.
     safe_packages: bool,
+    /// Whether to keep aliases as newtypes, instead of resolving them into the types they alias.
+    newtypes: bool,
     /// Package naming to apply.
     package_naming: Option<Rc<Box<dyn Naming>>>,
     /// Field naming to apply.
@@ -108,6 +111,7 @@ where
             types: Rc::new(LinkedHashMap::new()),
             keywords: Rc::new(HashMap::new()),
             safe_packages: false,
+            newtypes: false,
             package_naming: None,
             field_ident_naming: None,
             endpoint_ident_naming: None,
@@ -134,6 +138,11 @@ where
         }
     }
 
+    /// Configure a new session on whether to keep aliases as newtypes or not.
+    pub fn with_newtypes(self, newtypes: bool) -> Self {
+        Self { newtypes, ..self }
+    }
+
     /// Build the session with the given keywords.
     pub fn with_keywords(self, keywords: HashMap<String, String>) -> Self {
         Self {
@@ -231,7 +240,7 @@ impl<'a> Session<'a, CoreFlavor> {
     /// Translate the current session into another flavor.
     ///
     /// This is the final step of the compilation, the session is consumed by this.
-    pub fn translate<T>(self, flavor: T) -> Result<Translated<T::Target>>
+    pub fn translate<T>(mut self, flavor: T) -> Result<Translated<T::Target>>
     where
         T: FlavorTranslator<Source = CoreFlavor>,
     {
@@ -240,6 +249,14 @@ impl<'a> Session<'a, CoreFlavor> {
             return Err(Error::new("error in context"));
         }
 
+        self.instantiate()?;
+
+        let aliases = if self.newtypes {
+            None
+        } else {
+            Some(Rc::new(self.aliases()?))
+        };
+
         let mut files = BTreeMap::new();
         let collected = Rc::new(RefCell::new(LinkedHashMap::new()));
 
@@ -252,6 +269,7 @@ impl<'a> Session<'a, CoreFlavor> {
                     flavor: &flavor,
                     types: Rc::clone(&self.types),
                     decls: Some(collected.clone()),
+                    aliases: aliases.clone(),
                 };
 
                 let mut diag = Diagnostics::new(file.source.clone());
@@ -284,6 +302,7 @@ impl<'a> Session<'a, CoreFlavor> {
                 flavor: &flavor,
                 types: Rc::clone(&self.types),
                 decls: None,
+                aliases: aliases.clone(),
             };
 
             // NB: it must always be possible to translate name without declarations until all
@@ -295,6 +314,178 @@ impl<'a> Session<'a, CoreFlavor> {
         Ok(Translated::new(decls, files))
     }
 
+    /// Instantiate generic declarations with the type arguments that they are used with.
+    ///
+    /// The instances take the place of the generic declaration they were instantiated from.
+    fn instantiate(&mut self) -> Result<()> {
+        let mut generics = Generics::default();
+
+        for file in self.files.values() {
+            for decl in &file.file.decls {
+                if !decl.type_parameters().is_empty() {
+                    generics.template(decl.clone());
+                }
+            }
+        }
+
+        for file in self.files.values_mut() {
+            let mut diag = Diagnostics::new(file.source.clone());
+
+            for decl in &mut file.file.decls {
+                if decl.type_parameters().is_empty() {
+                    let _ = generics.decl(&mut diag, decl);
+                }
+            }
+
+            if diag.has_errors() {
+                self.reporter.diagnostics(diag);
+                return Err("error in environment".into());
+            }
+        }
+
+        let mut instances = HashMap::<_, Vec<_>>::new();
+
+        while let Some(pending) = generics.next_pending() {
+            let file = self
+                .files
+                .get(&pending.template.package)
+                .ok_or_else(|| format!("no file for generic `{}`", pending.template))?;
+
+            let mut diag = Diagnostics::new(file.source.clone());
+            let mut decl = generics.build(&pending);
+
+            if generics.decl(&mut diag, &mut decl).is_err() {
+                self.reporter.diagnostics(diag);
+                return Err("error in environment".into());
+            }
+
+            instances
+                .entry(pending.template)
+                .or_insert_with(Vec::new)
+                .push(decl);
+        }
+
+        let types = Rc::get_mut(&mut self.types)
+            .ok_or_else(|| Error::from("non-unique access to environment"))?;
+
+        for file in self.files.values_mut() {
+            if file
+                .file
+                .decls
+                .iter()
+                .all(|d| d.type_parameters().is_empty())
+            {
+                continue;
+            }
+
+            let mut diag = Diagnostics::new(file.source.clone());
+
+            for decl in std::mem::take(&mut file.file.decls) {
+                if decl.type_parameters().is_empty() {
+                    file.file.decls.push(decl);
+                    continue;
+                }
+
+                for (key, _, _) in decl.to_reg() {
+                    types.remove(&Spanned::borrow(key).clone().without_prefix());
+                }
+
+                let name = Spanned::borrow(decl.name()).clone().without_prefix();
+
+                for instance in instances.remove(&name).unwrap_or_default() {
+                    register(&mut diag, types, &instance);
+                    file.file.decls.push(instance);
+                }
+            }
+
+            file.file.decl_idents = file
+                .file
+                .decls
+                .iter()
+                .enumerate()
+                .map(|(index, decl)| (decl.ident().to_string(), index))
+                .collect();
+
+            if diag.has_errors() {
+                self.reporter.diagnostics(diag);
+                return Err("error in environment".into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collect all aliases, so that they can be resolved into the types they alias.
+    ///
+    /// Aliases which refer to themselves can't be resolved and are reported as errors.
+    fn aliases(&mut self) -> Result<translator::Aliases<CoreFlavor>> {
+        let mut aliases = HashMap::new();
+
+        for file in self.files.values() {
+            for decl in file.file.decls.iter().flat_map(|d| d.to_named()) {
+                if let RpNamed::Alias(body) = decl {
+                    let name = Spanned::borrow(&body.name).clone().without_prefix();
+                    aliases.insert(name, Spanned::borrow(body).clone());
+                }
+            }
+        }
+
+        for file in self.files.values() {
+            let mut diag = Diagnostics::new(file.source.clone());
+
+            for decl in file.file.decls.iter().flat_map(|d| d.to_named()) {
+                if let RpNamed::Alias(body) = decl {
+                    let name = Spanned::borrow(&body.name).clone().without_prefix();
+
+                    if refers_to(&aliases, &name, &body.ty, &mut HashSet::new()) {
+                        diag.err(
+                            body.name.span(),
+                            format!("alias `{}` refers to itself", body.name),
+                        );
+                    }
+                }
+            }
+
+            if diag.has_errors() {
+                self.reporter.diagnostics(diag);
+                return Err("error in environment".into());
+            }
+        }
+
+        return Ok(aliases);
+
+        /// Test if the given type refers to the alias with the given name.
+        fn refers_to<'a>(
+            aliases: &'a translator::Aliases<CoreFlavor>,
+            name: &RpName<CoreFlavor>,
+            ty: &'a RpType<CoreFlavor>,
+            visited: &mut HashSet<&'a RpName<CoreFlavor>>,
+        ) -> bool {
+            match *ty {
+                RpType::Array { ref inner } => refers_to(aliases, name, inner, visited),
+                RpType::Map { ref key, ref value } => {
+                    refers_to(aliases, name, key, visited)
+                        || refers_to(aliases, name, value, visited)
+                }
+                RpType::Name { name: ref other } => {
+                    let other = Spanned::borrow(other);
+
+                    if other.clone().without_prefix() == *name {
+                        return true;
+                    }
+
+                    match aliases.get_key_value(&other.clone().without_prefix()) {
+                        Some((key, alias)) if visited.insert(key) => {
+                            refers_to(aliases, name, &alias.ty, visited)
+                        }
+                        _ => false,
+                    }
+                }
+                _ => false,
+            }
+        }
+    }
+
     /// Translation to simplified packages.
     pub fn packages(&self) -> Result<Rc<Packages>> {
         let mut queue = self
@@ -507,8 +698,6 @@ impl<'a> Session<'a, CoreFlavor> {
         package: RpVersionedPackage,
         file: RpFile<CoreFlavor>,
     ) -> result::Result<(), ()> {
-        use linked_hash_map::Entry::*;
-
         let file = match self.files.entry(package) {
             btree_map::Entry::Vacant(entry) => entry.insert(File {
                 file,
@@ -519,31 +708,16 @@ impl<'a> Session<'a, CoreFlavor> {
             }
         };
 
-        for (key, _, t) in file.file.decls.iter().flat_map(|d| d.to_reg()) {
-            let (key, span) = Spanned::borrow_pair(key);
-            let key = key.clone().without_prefix();
-
-            log::debug!("new reg ty: {}", key);
-
-            let types = match Rc::get_mut(&mut self.types) {
-                None => {
-                    diag.err(span, "non-unique access to environment");
-                    continue;
-                }
-                Some(types) => types,
-            };
+        let types = match Rc::get_mut(&mut self.types) {
+            None => {
+                diag.err(Span::empty(), "non-unique access to environment");
+                return Err(());
+            }
+            Some(types) => types,
+        };
 
-            match types.entry(key.clone()) {
-                Vacant(entry) => entry.insert(Spanned::new(t, span)),
-                Occupied(entry) => {
-                    diag.err(
-                        span,
-                        format!("`{}` conflicts with existing declaration", key),
-                    );
-                    diag.info(entry.get().span(), "existing declaration here");
-                    continue;
-                }
-            };
+        for decl in &file.file.decls {
+            register(diag, types, decl);
         }
 
         if diag.has_errors() {
@@ -554,6 +728,34 @@ impl<'a> Session<'a, CoreFlavor> {
     }
 }
 
+/// Register the types of a declaration, reporting conflicts with existing declarations.
+fn register(
+    diag: &mut Diagnostics,
+    types: &mut LinkedHashMap<RpName<CoreFlavor>, Spanned<RpReg>>,
+    decl: &RpDecl<CoreFlavor>,
+) {
+    use linked_hash_map::Entry::*;
+
+    for (key, _, t) in decl.to_reg() {
+        let (key, span) = Spanned::borrow_pair(key);
+        let key = key.clone().without_prefix();
+
+        log::debug!("new reg ty: {}", key);
+
+        match types.entry(key.clone()) {
+            Vacant(entry) => entry.insert(Spanned::new(t, span)),
+            Occupied(entry) => {
+                diag.err(
+                    span,
+                    format!("`{}` conflicts with existing declaration", key),
+                );
+                diag.info(entry.get().span(), "existing declaration here");
+                continue;
+            }
+        };
+    }
+}
+
 impl<'e> Import for Session<'e, CoreFlavor> {
     /// Import a package based on a package and version criteria.
     fn import(&mut self, required: &RpRequiredPackage) -> Result<Option<RpVersionedPackage>> {
@@ -0,0 +1,233 @@
+//! Token-based authorization of requests.
+
+use std::collections::HashMap;
+
+use hyper::header::{self, HeaderMap, HeaderValue};
+use reproto_core::RpPackage;
+
+use crate::config::Token;
+use crate::errors::Error;
+
+/// What a single token grants access to.
+pub struct Grant {
+    /// Package prefixes that the grant applies to, or all packages if empty.
+    packages: Vec<RpPackage>,
+    /// If the grant permits publishing.
+    publish: bool,
+}
+
+impl Grant {
+    /// Check if the grant covers the given package.
+    fn covers(&self, package: &RpPackage) -> bool {
+        self.packages.is_empty() || self.packages.iter().any(|p| package.starts_with(p))
+    }
+}
+
+/// The identity that a request is performed with.
+pub enum Principal<'a> {
+    /// No token was provided.
+    Anonymous,
+    /// A valid token was provided.
+    Token(&'a Grant),
+}
+
+pub struct Auth {
+    /// Permit reading without a token.
+    public: bool,
+    /// Grants, keyed by their token.
+    grants: HashMap<String, Grant>,
+}
+
+impl Auth {
+    pub fn new(public: bool, tokens: Vec<Token>) -> Self {
+        let grants = tokens
+            .into_iter()
+            .map(|t| {
+                let grant = Grant {
+                    packages: t.packages.iter().map(|p| RpPackage::parse(p)).collect(),
+                    publish: t.publish,
+                };
+
+                (t.token, grant)
+            })
+            .collect();
+
+        Self { public, grants }
+    }
+
+    /// Identify the principal of a request from its headers.
+    pub fn identify(&self, headers: &HeaderMap<HeaderValue>) -> Result<Principal<'_>, Error> {
+        let value = match headers.get(header::AUTHORIZATION) {
+            Some(value) => value,
+            None => return Ok(Principal::Anonymous),
+        };
+
+        let token = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Error::Unauthorized("bad authorization header".into()))?;
+
+        match self.grants.get(token.trim()) {
+            Some(grant) => Ok(Principal::Token(grant)),
+            None => Err(Error::Unauthorized("bad token".into())),
+        }
+    }
+
+    /// Check if the principal can read the given package, or any package if `None`.
+    pub fn can_read(&self, principal: &Principal<'_>, package: Option<&RpPackage>) -> bool {
+        if self.public {
+            return true;
+        }
+
+        match (principal, package) {
+            (Principal::Anonymous, _) => false,
+            (Principal::Token(_), None) => true,
+            (Principal::Token(grant), Some(package)) => grant.covers(package),
+        }
+    }
+
+    /// Require that the principal can read the given package, or any package if `None`.
+    pub fn check_read(
+        &self,
+        principal: &Principal<'_>,
+        package: Option<&RpPackage>,
+    ) -> Result<(), Error> {
+        if self.can_read(principal, package) {
+            return Ok(());
+        }
+
+        match principal {
+            Principal::Anonymous => Err(Error::Unauthorized("token required".into())),
+            Principal::Token(_) => Err(Error::Forbidden("not permitted to read package".into())),
+        }
+    }
+
+    /// Require that the principal can read an object referenced by the given packages.
+    ///
+    /// Objects can be read if any package referencing them can be read. Objects which are not
+    /// referenced by any package require a grant which covers all packages.
+    pub fn check_read_object(
+        &self,
+        principal: &Principal<'_>,
+        packages: &[RpPackage],
+    ) -> Result<(), Error> {
+        self.check_read(principal, None)?;
+
+        if self.public {
+            return Ok(());
+        }
+
+        match principal {
+            Principal::Token(grant)
+                if grant.packages.is_empty() || packages.iter().any(|p| grant.covers(p)) =>
+            {
+                Ok(())
+            }
+            _ => Err(Error::Forbidden("not permitted to read object".into())),
+        }
+    }
+
+    /// Require that the principal can publish the given package, or any package if `None`.
+    ///
+    /// Publishing is open to anyone if no tokens are configured.
+    pub fn check_publish(
+        &self,
+        principal: &Principal<'_>,
+        package: Option<&RpPackage>,
+    ) -> Result<(), Error> {
+        if self.grants.is_empty() {
+            return Ok(());
+        }
+
+        let grant = match principal {
+            Principal::Anonymous => return Err(Error::Unauthorized("token required".into())),
+            Principal::Token(grant) => grant,
+        };
+
+        if !grant.publish {
+            return Err(Error::Forbidden("token is read-only".into()));
+        }
+
+        if let Some(package) = package {
+            if !grant.covers(package) {
+                return Err(Error::Forbidden(
+                    format!("not permitted to publish: {}", package).into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Auth, Principal};
+    use crate::config::Token;
+    use hyper::header::{self, HeaderMap, HeaderValue};
+    use reproto_core::RpPackage;
+
+    fn auth(public: bool) -> Auth {
+        let tokens = vec![
+            Token {
+                token: "reader".to_string(),
+                packages: vec!["io.reproto".to_string()],
+                publish: false,
+            },
+            Token {
+                token: "publisher".to_string(),
+                packages: vec!["io.reproto".to_string()],
+                publish: true,
+            },
+        ];
+
+        Auth::new(public, tokens)
+    }
+
+    fn headers(token: &str) -> HeaderMap<HeaderValue> {
+        let mut headers = HeaderMap::new();
+        let value = HeaderValue::from_str(&format!("Bearer {}", token)).unwrap();
+        headers.insert(header::AUTHORIZATION, value);
+        headers
+    }
+
+    #[test]
+    fn test_identify() {
+        let auth = auth(true);
+
+        assert!(matches!(
+            auth.identify(&HeaderMap::new()),
+            Ok(Principal::Anonymous)
+        ));
+        assert!(matches!(
+            auth.identify(&headers("reader")),
+            Ok(Principal::Token(_))
+        ));
+        assert!(auth.identify(&headers("unknown")).is_err());
+    }
+
+    #[test]
+    fn test_permissions() {
+        let auth = auth(false);
+        let package = RpPackage::parse("io.reproto.foo");
+        let other = RpPackage::parse("com.example");
+
+        let anonymous = Principal::Anonymous;
+        let reader = auth.identify(&headers("reader")).ok().unwrap();
+        let publisher = auth.identify(&headers("publisher")).ok().unwrap();
+
+        assert!(!auth.can_read(&anonymous, Some(&package)));
+        assert!(auth.can_read(&reader, Some(&package)));
+        assert!(!auth.can_read(&reader, Some(&other)));
+
+        assert!(auth.check_publish(&anonymous, Some(&package)).is_err());
+        assert!(auth.check_publish(&reader, Some(&package)).is_err());
+        assert!(auth.check_publish(&publisher, Some(&package)).is_ok());
+        assert!(auth.check_publish(&publisher, Some(&other)).is_err());
+
+        let open = Auth::new(true, vec![]);
+        assert!(open.can_read(&anonymous, Some(&other)));
+        assert!(open.check_publish(&anonymous, Some(&other)).is_ok());
+    }
+}
@@ -5,6 +5,19 @@ use std::path::{Path, PathBuf};
 use reproto_core::errors::Result;
 use serde::Deserialize;
 
+/// A token which grants access to the repository.
+#[derive(Debug, Deserialize)]
+pub struct Token {
+    /// The secret token, as sent by clients in the `Authorization: Bearer <token>` header.
+    pub token: String,
+    /// Package prefixes that the token grants access to, or all packages if empty.
+    #[serde(default)]
+    pub packages: Vec<String>,
+    /// If the token permits publishing, otherwise it is read-only.
+    #[serde(default)]
+    pub publish: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Config {
     /// Address to listen to.
@@ -16,9 +29,19 @@ pub struct Config {
     /// Index path.
     #[serde(default = "default_index")]
     pub index: PathBuf,
+    /// Path to store rendered documentation in.
+    #[serde(default = "default_doc_cache")]
+    pub doc_cache: PathBuf,
     /// Max file size permitted during upload.
     #[serde(default = "default_max_file_size")]
     pub max_file_size: u64,
+    /// Permit reading without a token.
+    #[serde(default = "default_public")]
+    pub public: bool,
+    /// Tokens which grant access to the repository.
+    /// If no tokens are configured, anyone is permitted to publish.
+    #[serde(default)]
+    pub tokens: Vec<Token>,
 }
 
 fn default_listen_address() -> String {
@@ -33,17 +56,28 @@ fn default_index() -> PathBuf {
     Path::new("./index").to_owned()
 }
 
+fn default_doc_cache() -> PathBuf {
+    Path::new("./doc-cache").to_owned()
+}
+
 fn default_max_file_size() -> u64 {
     1_000_000u64
 }
 
+fn default_public() -> bool {
+    true
+}
+
 impl Default for Config {
     fn default() -> Config {
         Config {
             listen_address: default_listen_address(),
             objects: default_objects(),
             index: default_index(),
+            doc_cache: default_doc_cache(),
             max_file_size: default_max_file_size(),
+            public: default_public(),
+            tokens: Vec::new(),
         }
     }
 }
@@ -0,0 +1,121 @@
+//! Render documentation for published packages.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use reproto_core::errors::{Result, ResultExt as _};
+use reproto_core::{
+    Diagnostic, Reported, Resolved, ResolvedByPrefix, Resolver, RpPackage, RpRequiredPackage,
+    RpVersionedPackage, Source,
+};
+use reproto_repository::{Deployment, Index, Objects};
+
+/// Resolver which looks up dependencies of rendered packages in the repository of the server.
+///
+/// Locks are only held for each individual lookup, so that other requests can be served while
+/// documentation is being rendered.
+struct IndexResolver<'a> {
+    index: &'a Mutex<Box<dyn Index>>,
+    objects: &'a Mutex<Box<dyn Objects>>,
+}
+
+impl<'a> IndexResolver<'a> {
+    fn index(&self) -> Result<MutexGuard<'a, Box<dyn Index>>> {
+        self.index.lock().map_err(|_| "lock poisoned".into())
+    }
+
+    fn get_object(&mut self, deployment: &Deployment) -> Result<Source> {
+        let object = match self.objects.lock() {
+            Ok(mut objects) => objects.get_object(&deployment.object)?,
+            Err(_) => return Err("lock poisoned".into()),
+        };
+
+        match object {
+            Some(source) => Ok(source.with_read_only(true)),
+            None => Err(format!("missing object: {}", deployment.object).into()),
+        }
+    }
+}
+
+impl<'a> Resolver for IndexResolver<'a> {
+    fn resolve(&mut self, package: &RpRequiredPackage) -> Result<Option<Resolved>> {
+        let deployments = self.index()?.resolve(&package.package, &package.range)?;
+
+        match deployments.into_iter().next_back() {
+            Some(deployment) => Ok(Some(Resolved {
+                source: self.get_object(&deployment)?,
+                version: Some(deployment.version),
+            })),
+            None => Ok(None),
+        }
+    }
+
+    fn resolve_by_prefix(&mut self, package: &RpPackage) -> Result<Vec<ResolvedByPrefix>> {
+        let mut out = Vec::new();
+
+        let deployments = self.index()?.resolve_by_prefix(package)?;
+
+        for (deployment, package) in deployments {
+            let source = self.get_object(&deployment)?;
+            let package = RpVersionedPackage::new(package, Some(deployment.version));
+            out.push(ResolvedByPrefix { package, source });
+        }
+
+        Ok(out)
+    }
+
+    fn resolve_packages(&mut self) -> Result<Vec<ResolvedByPrefix>> {
+        Ok(vec![])
+    }
+}
+
+/// Render documentation for the given deployment, unless it has already been rendered.
+///
+/// Returns the directory containing the documentation.
+pub fn render(
+    doc_cache: &Path,
+    index: &Mutex<Box<dyn Index>>,
+    objects: &Mutex<Box<dyn Objects>>,
+    package: &RpPackage,
+    deployment: &Deployment,
+) -> Result<PathBuf> {
+    let out = doc_cache.join(deployment.object.to_string());
+
+    if out.is_dir() {
+        return Ok(out);
+    }
+
+    let mut resolver = IndexResolver { index, objects };
+    let source = resolver.get_object(deployment)?;
+    let package = RpVersionedPackage::new(package.clone(), Some(deployment.version.clone()));
+
+    // render into a temporary directory, so that partial renders are never served.
+    let tmp = doc_cache.join(format!("{}.tmp", deployment.object));
+
+    if tmp.is_dir() {
+        fs::remove_dir_all(&tmp)?;
+    }
+
+    fs::create_dir_all(&tmp)?;
+
+    let mut reporter: Vec<Reported> = Vec::new();
+
+    let result = trans::Session::new(None, &mut reporter, &mut resolver).and_then(|mut session| {
+        session.import_source(source, Some(package.clone()))?;
+        doc::compile_to_path(session, &tmp)
+    });
+
+    if let Err(e) = result {
+        for (source, diagnostic) in reporter.iter().flat_map(|r| r.diagnostics_with_sources()) {
+            if let Diagnostic::Error { ref message, .. } = *diagnostic {
+                log::error!("{}: {}", source, message);
+            }
+        }
+
+        return Err(e).chain_err(|| format!("failed to render documentation: {}", package));
+    }
+
+    fs::rename(&tmp, &out)?;
+    Ok(out)
+}
@@ -5,6 +5,9 @@ use std::borrow::Cow;
 pub enum Error {
     NotFound,
     BadRequest(Cow<'static, str>),
+    Unauthorized(Cow<'static, str>),
+    Forbidden(Cow<'static, str>),
+    Conflict(Cow<'static, str>),
     InternalServerError(Cow<'static, str>),
     Core(core::Error),
 }
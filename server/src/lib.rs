@@ -1,3 +1,5 @@
+pub mod auth;
 pub mod config;
+mod doc;
 mod errors;
 pub mod reproto_service;
@@ -35,7 +35,10 @@ async fn entry() -> Result<()> {
         listen_address,
         objects,
         index,
+        doc_cache,
         max_file_size,
+        public,
+        tokens,
     } = config;
     let listen_address = listen_address.parse()?;
 
@@ -45,7 +48,10 @@ async fn entry() -> Result<()> {
     let index = index_from_path(index)?;
     let index = Mutex::new(index);
 
-    let service = reproto_service::ReprotoService::new(max_file_size, objects, index);
+    let auth = reproto_server::auth::Auth::new(public, tokens);
+
+    let service =
+        reproto_service::ReprotoService::new(max_file_size, objects, index, doc_cache, auth);
 
     let setup = make_service_fn(move |_| {
         let service = service.clone();
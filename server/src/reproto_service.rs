@@ -1,5 +1,6 @@
 use std::future::Future;
 use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
@@ -8,19 +9,32 @@ use flate2::read::GzDecoder;
 use hyper::header::{self, HeaderMap, HeaderValue};
 use hyper::service::Service;
 use hyper::{self, Body, Method, Request, Response, StatusCode};
-use reproto_repository::{to_checksum, Checksum, Index, Objects};
+use reproto_core::{Range, RpPackage, Version};
+use reproto_repository::{to_checksum, Checksum, Deployment, Index, Objects};
+use serde::Serialize;
+use std::sync::MutexGuard;
 use tokio::io::AsyncReadExt;
 use tokio_stream::StreamExt;
 
+use crate::auth::{Auth, Principal};
+use crate::doc;
 use crate::errors::Error;
 
 const CHECKSUM_MISMATCH: &'static str = "checksum mismatch";
 
+/// A deployment of a package, as returned when resolving packages by prefix.
+#[derive(Serialize)]
+struct PackageDeployment<'a> {
+    package: &'a RpPackage,
+    deployment: &'a Deployment,
+}
+
 struct Inner {
     pub max_file_size: u64,
     pub objects: Mutex<Box<dyn Objects>>,
-    #[allow(unused)]
     pub index: Mutex<Box<dyn Index>>,
+    pub doc_cache: PathBuf,
+    pub auth: Auth,
 }
 
 #[derive(Clone)]
@@ -35,12 +49,16 @@ impl ReprotoService {
         max_file_size: u64,
         objects: Mutex<Box<dyn Objects>>,
         index: Mutex<Box<dyn Index>>,
+        doc_cache: PathBuf,
+        auth: Auth,
     ) -> Self {
         Self {
             inner: Arc::new(Inner {
                 max_file_size,
                 objects,
                 index,
+                doc_cache,
+                auth,
             }),
         }
     }
@@ -63,29 +81,158 @@ impl ReprotoService {
         Self::no_encoding
     }
 
-    fn build_index(&self, m: &mut String) -> Result<(), Error> {
+    fn build_index(&self, m: &mut String, principal: &Principal<'_>) -> Result<(), Error> {
         use std::fmt::Write;
 
+        let packages = self
+            .lock_index()?
+            .all_by_prefix(&RpPackage::empty())?
+            .into_iter()
+            .filter(|(_, package)| self.inner.auth.can_read(principal, Some(package)))
+            .collect::<Vec<_>>();
+
         writeln!(m, "<html>")?;
         writeln!(m, "<head>")?;
         writeln!(m, "<title>Reproto Repository</title>")?;
         writeln!(m, "</head>")?;
         writeln!(m, "<body>")?;
+        writeln!(m, "<h1>Reproto Repository</h1>")?;
+
+        if packages.is_empty() {
+            writeln!(m, "<p>No packages have been published.</p>")?;
+        } else {
+            writeln!(m, "<table>")?;
+            writeln!(
+                m,
+                "<tr><th>Package</th><th>Version</th><th>Object</th></tr>"
+            )?;
+
+            for (deployments, package) in packages {
+                let name = escape(&package.to_string());
+
+                // most recent versions first.
+                for deployment in deployments.iter().rev() {
+                    let version = escape(&deployment.version.to_string());
+
+                    writeln!(
+                        m,
+                        "<tr><td>{name}</td>\
+                         <td><a href=\"doc/{name}/{version}/\">{version}</a></td>\
+                         <td><a href=\"objects/{object}\"><code>{object}</code></a></td></tr>",
+                        name = name,
+                        version = version,
+                        object = deployment.object,
+                    )?;
+                }
+            }
+
+            writeln!(m, "</table>")?;
+        }
+
         writeln!(m, "</body>")?;
         writeln!(m, "</html>")?;
 
         Ok(())
     }
 
-    async fn get_index(&self) -> Result<Response<Body>, Error> {
+    async fn get_index(&self, principal: &Principal<'_>) -> Result<Response<Body>, Error> {
+        self.inner.auth.check_read(principal, None)?;
+
         let mut m = String::new();
-        self.build_index(&mut m)?;
-        let response = Response::new(Body::from(m));
+        self.build_index(&mut m, principal)?;
+        let mut response = Response::new(Body::from(m));
+
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static("text/html"));
+
+        Ok(response)
+    }
+
+    /// Get rendered documentation for a version of a package.
+    ///
+    /// Documentation is rendered the first time that it is requested.
+    async fn get_doc(
+        &self,
+        principal: &Principal<'_>,
+        package: &str,
+        version: &str,
+        path: Vec<String>,
+    ) -> Result<Response<Body>, Error> {
+        let package = RpPackage::parse(package);
+        self.inner.auth.check_read(principal, Some(&package))?;
+        let version = Self::parse_version(version)?;
+
+        if path.iter().any(|p| p == ".." || p == ".") {
+            return Err(Error::BadRequest("bad path".into()));
+        }
+
+        let inner = self.inner.clone();
+
+        let root = tokio::task::spawn_blocking(move || {
+            let deployments = match inner.index.lock() {
+                Ok(index) => index.get_deployments(&package, &version)?,
+                Err(_) => return Err(Error::InternalServerError("lock poisoned".into())),
+            };
+
+            let deployment = match deployments.into_iter().next_back() {
+                Some(deployment) => deployment,
+                None => return Err(Error::NotFound),
+            };
+
+            // NB: the resolver only locks the index and objects for each lookup.
+            let root = doc::render(
+                &inner.doc_cache,
+                &inner.index,
+                &inner.objects,
+                &package,
+                &deployment,
+            )?;
+
+            Ok::<_, Error>(root)
+        })
+        .await
+        .map_err(|_| Error::InternalServerError("task failed".into()))??;
+
+        let mut file = root;
+
+        for part in &path {
+            file.push(part);
+        }
+
+        if path.last().map(|p| p.is_empty()).unwrap_or(true) {
+            file.push("index.html");
+        }
+
+        let content_type = match file.extension().and_then(|e| e.to_str()) {
+            Some("html") => "text/html",
+            Some("css") => "text/css",
+            _ => "application/octet-stream",
+        };
+
+        let mut f = tokio::fs::File::open(file)
+            .await
+            .map_err(|_| Error::NotFound)?;
+        let mut buf: Vec<u8> = Vec::new();
+        f.read_to_end(&mut buf)
+            .await
+            .map_err(|_| Error::InternalServerError("error sending file".into()))?;
+
+        let mut response = Response::new(Body::from(buf));
+
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+
         Ok(response)
     }
 
     /// Get an object from an object repository.
-    async fn get_objects(&self, id: &str) -> Result<Response<Body>, Error> {
+    async fn get_objects(
+        &self,
+        principal: &Principal<'_>,
+        id: &str,
+    ) -> Result<Response<Body>, Error> {
         let checksum = match Checksum::from_str(id) {
             Ok(checksum) => checksum,
             Err(_) => {
@@ -93,6 +240,17 @@ impl ReprotoService {
             }
         };
 
+        // packages which have a version deployed with the requested object.
+        let packages = self
+            .lock_index()?
+            .all_by_prefix(&RpPackage::empty())?
+            .into_iter()
+            .filter(|(deployments, _)| deployments.iter().any(|d| d.object == checksum))
+            .map(|(_, package)| package)
+            .collect::<Vec<_>>();
+
+        self.inner.auth.check_read_object(principal, &packages)?;
+
         let path = {
             let mut objects = match self.inner.objects.lock() {
                 Ok(objects) => objects,
@@ -166,7 +324,14 @@ impl ReprotoService {
         result.map_err(|_| Error::InternalServerError("task failed".into()))?
     }
 
-    async fn put_objects(&self, id: &str, mut req: Request<Body>) -> Result<Response<Body>, Error> {
+    async fn put_objects(
+        &self,
+        principal: &Principal<'_>,
+        id: &str,
+        mut req: Request<Body>,
+    ) -> Result<Response<Body>, Error> {
+        self.inner.auth.check_publish(principal, None)?;
+
         let checksum = Checksum::from_str(id)?;
 
         {
@@ -208,26 +373,213 @@ impl ReprotoService {
             .await
     }
 
+    /// Build a JSON response.
+    fn json_response<T>(value: &T) -> Result<Response<Body>, Error>
+    where
+        T: Serialize,
+    {
+        let body = serde_json::to_vec(value)?;
+        let mut response = Response::new(Body::from(body));
+
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        Ok(response)
+    }
+
+    /// Build a permanent redirect to the given location.
+    fn redirect(location: &str) -> Result<Response<Body>, Error> {
+        let location = HeaderValue::from_str(location)
+            .map_err(|_| Error::BadRequest("bad location".into()))?;
+
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::MOVED_PERMANENTLY;
+        response.headers_mut().insert(header::LOCATION, location);
+        Ok(response)
+    }
+
+    /// Find the value of the given query parameter.
+    fn query_param(req: &Request<Body>, key: &str) -> Option<String> {
+        let query = req.uri().query()?;
+
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    }
+
+    fn lock_index(&self) -> Result<MutexGuard<'_, Box<dyn Index>>, Error> {
+        match self.inner.index.lock() {
+            Ok(index) => Ok(index),
+            Err(_) => Err(Error::InternalServerError("lock poisoned".into())),
+        }
+    }
+
+    fn lock_objects(&self) -> Result<MutexGuard<'_, Box<dyn Objects>>, Error> {
+        match self.inner.objects.lock() {
+            Ok(objects) => Ok(objects),
+            Err(_) => Err(Error::InternalServerError("lock poisoned".into())),
+        }
+    }
+
+    fn parse_version(version: &str) -> Result<Version, Error> {
+        Version::parse(version)
+            .map_err(|e| Error::BadRequest(format!("bad version: {}: {}", version, e).into()))
+    }
+
+    /// Resolve all packages matching the `prefix` query parameter.
+    async fn get_packages(
+        &self,
+        principal: &Principal<'_>,
+        req: &Request<Body>,
+    ) -> Result<Response<Body>, Error> {
+        self.inner.auth.check_read(principal, None)?;
+
+        let prefix = match Self::query_param(req, "prefix") {
+            Some(prefix) => RpPackage::parse(&prefix),
+            None => RpPackage::empty(),
+        };
+
+        let deployments = self.lock_index()?.resolve_by_prefix(&prefix)?;
+
+        let deployments = deployments
+            .iter()
+            .filter(|(_, package)| self.inner.auth.can_read(principal, Some(package)))
+            .map(|(deployment, package)| PackageDeployment {
+                package,
+                deployment,
+            })
+            .collect::<Vec<_>>();
+
+        Self::json_response(&deployments)
+    }
+
+    /// Get deployments of a package, optionally matching the `range` query parameter.
+    async fn get_package(
+        &self,
+        principal: &Principal<'_>,
+        package: &str,
+        req: &Request<Body>,
+    ) -> Result<Response<Body>, Error> {
+        let package = RpPackage::parse(package);
+        self.inner.auth.check_read(principal, Some(&package))?;
+
+        let deployments = match Self::query_param(req, "range") {
+            Some(range) => {
+                let range = Range::parse(&range).map_err(|e| {
+                    Error::BadRequest(format!("bad range: {}: {}", range, e).into())
+                })?;
+
+                self.lock_index()?.resolve(&package, &range)?
+            }
+            None => self.lock_index()?.all(&package)?,
+        };
+
+        Self::json_response(&deployments)
+    }
+
+    /// Get deployments of a specific version of a package.
+    async fn get_version(
+        &self,
+        principal: &Principal<'_>,
+        package: &str,
+        version: &str,
+    ) -> Result<Response<Body>, Error> {
+        let package = RpPackage::parse(package);
+        self.inner.auth.check_read(principal, Some(&package))?;
+        let version = Self::parse_version(version)?;
+        let deployments = self.lock_index()?.get_deployments(&package, &version)?;
+        Self::json_response(&deployments)
+    }
+
+    /// Publish a version of a package, pointing to the given object.
+    async fn put_version(
+        &self,
+        principal: &Principal<'_>,
+        package: &str,
+        version: &str,
+        id: &str,
+        req: &Request<Body>,
+    ) -> Result<Response<Body>, Error> {
+        let package = RpPackage::parse(package);
+        self.inner.auth.check_publish(principal, Some(&package))?;
+        let version = Self::parse_version(version)?;
+
+        let checksum = Checksum::from_str(id)
+            .map_err(|_| Error::BadRequest(format!("bad object id: {}", id).into()))?;
+
+        let force = Self::query_param(req, "force").as_deref() == Some("true");
+
+        // NB: the object must be uploaded before it can be indexed.
+        if self.lock_objects()?.get_object(&checksum)?.is_none() {
+            return Err(Error::BadRequest(
+                format!("{}: object does not exist", checksum).into(),
+            ));
+        }
+
+        let index = self.lock_index()?;
+
+        if !force && !index.get_deployments(&package, &version)?.is_empty() {
+            return Err(Error::Conflict(
+                format!("{}@{}: already published", package, version).into(),
+            ));
+        }
+
+        log::info!("Publishing: {}@{}: {}", package, version, checksum);
+        index.put_version(&checksum, &package, &version, force)?;
+        Ok(Response::new(Body::empty()))
+    }
+
     async fn inner_call<'a>(
         &self,
         req: Request<Body>,
         path: impl IntoIterator<Item = &str>,
     ) -> Result<Response<Body>, Error> {
+        let principal = self.inner.auth.identify(req.headers())?;
+
         let mut it = path.into_iter();
 
         let a = it.next();
         let b = it.next();
         let c = it.next();
+        let d = it.next();
 
-        match (req.method(), a, b, c) {
-            (&Method::GET, Some(""), None, None) => {
-                return self.get_index().await;
+        match (req.method(), a, b, c, d) {
+            (&Method::GET, Some(""), None, None, None) => {
+                return self.get_index(&principal).await;
+            }
+            (&Method::GET, Some("index"), None, None, None)
+            | (&Method::GET, Some("index"), Some(""), None, None) => {
+                return self.get_packages(&principal, &req).await;
+            }
+            (&Method::GET, Some("index"), Some(package), None, None) => {
+                return self.get_package(&principal, package, &req).await;
             }
-            (&Method::GET, Some("objects"), Some(id), None) => {
-                return self.get_objects(id).await;
+            (&Method::GET, Some("index"), Some(package), Some(version), None) => {
+                return self.get_version(&principal, package, version).await;
             }
-            (&Method::PUT, Some("objects"), Some(id), None) => {
-                return self.put_objects(id, req).await;
+            (&Method::PUT, Some("index"), Some(package), Some(version), Some(id)) => {
+                return self
+                    .put_version(&principal, package, version, id, &req)
+                    .await;
+            }
+            (&Method::GET, Some("doc"), Some(_), Some(_), None) => {
+                // relative links in the documentation requires a trailing slash.
+                let location = format!("{}/", req.uri().path());
+                return Self::redirect(&location);
+            }
+            (&Method::GET, Some("doc"), Some(package), Some(version), Some(first)) => {
+                let path = Some(first).into_iter().chain(it).map(String::from);
+                return self
+                    .get_doc(&principal, package, version, path.collect())
+                    .await;
+            }
+            (&Method::GET, Some("objects"), Some(id), None, None) => {
+                return self.get_objects(&principal, id).await;
+            }
+            (&Method::PUT, Some("objects"), Some(id), None, None) => {
+                return self.put_objects(&principal, id, req).await;
             }
             _ => {}
         }
@@ -251,6 +603,22 @@ impl ReprotoService {
                 *response.body_mut() = Body::from(message);
                 *response.status_mut() = StatusCode::BAD_REQUEST;
             }
+            Error::Unauthorized(message) => {
+                *response.body_mut() = Body::from(message);
+                *response.status_mut() = StatusCode::UNAUTHORIZED;
+
+                response
+                    .headers_mut()
+                    .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            }
+            Error::Forbidden(message) => {
+                *response.body_mut() = Body::from(message);
+                *response.status_mut() = StatusCode::FORBIDDEN;
+            }
+            Error::Conflict(message) => {
+                *response.body_mut() = Body::from(message);
+                *response.status_mut() = StatusCode::CONFLICT;
+            }
             Error::InternalServerError(message) => {
                 *response.body_mut() = Body::from("internal server error");
                 *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
@@ -277,6 +645,24 @@ impl ReprotoService {
     }
 }
 
+/// Escape a string to be included in HTML.
+fn escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+
+    for c in input.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
 impl Service<Request<Body>> for ReprotoService {
     type Response = Response<Body>;
     type Error = hyper::Error;
@@ -61,6 +61,15 @@ pub async fn setup(
             }
 
             for instance in &language.instances {
+                if !suite.supports_instance(&instance.name) {
+                    log::trace!(
+                        "instance `{}` not supported by suite `{}`",
+                        instance.name,
+                        suite.name
+                    );
+                    continue;
+                }
+
                 if filter(&["project", &suite.name, &instance.name, &language.name]) {
                     any_project = true;
 
@@ -243,6 +243,9 @@ impl Reproto {
         // Path to resolve packages from.
         cmd.arg("--path");
         cmd.arg(&check.proto_path);
+        // Checked packages are unversioned, so that specifications without errors can be checked.
+        cmd.arg("--version");
+        cmd.arg("0.0.0");
         cmd.arg(check.package);
 
         let output = cmd
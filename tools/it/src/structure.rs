@@ -29,6 +29,15 @@ pub async fn setup(
             }
 
             for instance in &language.instances {
+                if !suite.supports_instance(&instance.name) {
+                    log::trace!(
+                        "instance `{}` not supported by suite `{}`",
+                        instance.name,
+                        suite.name
+                    );
+                    continue;
+                }
+
                 if !filter(&["structure", &suite.name, &instance.name, &language.name]) {
                     continue;
                 }
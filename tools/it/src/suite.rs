@@ -9,6 +9,9 @@ pub struct SuiteYaml {
     /// Only enable suite for these specified languages.
     #[serde(default)]
     enabled: HashSet<String>,
+    /// Only enable suite for these specified language instances.
+    #[serde(default)]
+    instances: HashSet<String>,
 }
 
 impl SuiteYaml {
@@ -35,6 +38,8 @@ pub struct Suite {
     pub(crate) packages: Vec<String>,
     /// Languages suite is enabled for.
     pub(crate) enabled: Option<HashSet<String>>,
+    /// Language instances suite is enabled for.
+    pub(crate) instances: Option<HashSet<String>>,
 }
 
 impl Suite {
@@ -47,6 +52,16 @@ impl Suite {
             None => true,
         }
     }
+
+    /// Check if suite supports the given language instance.
+    ///
+    /// Unless a set of enabled instances is configured, all instances are supported.
+    pub(crate) fn supports_instance(&self, instance: &str) -> bool {
+        match &self.instances {
+            Some(instances) => instances.contains(instance),
+            None => true,
+        }
+    }
 }
 
 pub fn discover_suites(root: &Path) -> Result<Vec<Suite>> {
@@ -70,6 +85,7 @@ pub fn discover_suites(root: &Path) -> Result<Vec<Suite>> {
         let mut json = Vec::new();
         let mut proto = Vec::new();
         let mut enabled = None;
+        let mut instances = None;
 
         let suite_yaml_path = path.join("suite.yaml");
         let input_path = path.join("input");
@@ -84,6 +100,12 @@ pub fn discover_suites(root: &Path) -> Result<Vec<Suite>> {
             enabled
                 .get_or_insert_with(HashSet::new)
                 .extend(spec.enabled);
+
+            if !spec.instances.is_empty() {
+                instances
+                    .get_or_insert_with(HashSet::new)
+                    .extend(spec.instances);
+            }
         }
 
         if input_path.is_dir() {
@@ -104,6 +126,7 @@ pub fn discover_suites(root: &Path) -> Result<Vec<Suite>> {
             proto,
             packages,
             enabled,
+            instances,
         });
     }
 